@@ -0,0 +1,35 @@
+use chemquiz::{
+    Catalog, check_catalog_functional_groups, check_catalog_katex, check_catalog_salt_names,
+    check_catalog_structures, check_catalog_systematic_names,
+};
+
+fn main() {
+    let root = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "catalog".to_string());
+    let catalog = match Catalog::from_directory(&root) {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("failed to load catalog from {}: {}", root, error);
+            std::process::exit(2);
+        }
+    };
+
+    let mut issues = check_catalog_structures(&catalog);
+    issues.extend(check_catalog_katex(&catalog));
+    issues.extend(check_catalog_functional_groups(&catalog));
+    issues.extend(check_catalog_salt_names(&catalog));
+    issues.extend(check_catalog_systematic_names(&catalog));
+    for issue in &issues {
+        println!("{}", issue);
+    }
+
+    println!(
+        "{} issue(s) across {} entries",
+        issues.len(),
+        catalog.entries().len()
+    );
+    if !issues.is_empty() {
+        std::process::exit(1);
+    }
+}
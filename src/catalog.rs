@@ -1,8 +1,10 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
+use std::ops::RangeBounds;
 use std::path::Path;
 
 use crate::compound::Compound;
+use crate::functional_groups::FunctionalGroupKind;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct CatalogEntry {
@@ -74,6 +76,10 @@ impl Catalog {
         Ok(Self { entries })
     }
 
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
     pub fn all_compounds(&self) -> Vec<Compound> {
         self.entries
             .iter()
@@ -93,15 +99,20 @@ impl Catalog {
         paths
     }
 
+    /// Compounds filed under `path` or any of its subcategories. A compound listed both in a
+    /// leaf file and in an aggregate file above it is returned once, from whichever entry
+    /// loaded first.
     pub fn compounds_for(&self, path: &[String]) -> Result<Vec<Compound>, CatalogError> {
         if path.is_empty() {
             return Err(CatalogError::EmptyPath);
         }
 
+        let mut seen = HashSet::new();
         let matches: Vec<Compound> = self
             .entries
             .iter()
             .filter(|entry| entry.categories.starts_with(path))
+            .filter(|entry| seen.insert(entry.compound.equivalence_key()))
             .map(|entry| entry.compound.clone())
             .collect();
 
@@ -113,6 +124,127 @@ impl Catalog {
 
         Ok(matches)
     }
+
+    /// Compounds containing the functional group `kind`, detected from SMILES where
+    /// available. Chemically equivalent copies from aggregate files appear once.
+    pub fn compounds_with_functional_group(&self, kind: FunctionalGroupKind) -> Vec<Compound> {
+        let mut seen = HashSet::new();
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.compound.functional_group_kinds().contains(&kind))
+            .filter(|entry| seen.insert(entry.compound.equivalence_key()))
+            .map(|entry| entry.compound.clone())
+            .collect()
+    }
+
+    /// Returns every entry's compound that is chemically equivalent to `compound`, including
+    /// copies of it found under other categories.
+    pub fn equivalent_compounds(&self, compound: &Compound) -> Vec<Compound> {
+        let key = compound.equivalence_key();
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.compound.equivalence_key() == key)
+            .map(|entry| entry.compound.clone())
+            .collect()
+    }
+
+    /// Groups of entries describing the same compound, such as a leaf-file entry and its copy
+    /// in an aggregate `compounds.json` higher up the tree. Entries are matched by
+    /// [`Compound::equivalence_key`], so the canonical structure key decides where SMILES
+    /// are given. Groups and their entries follow load order; compounds listed once are
+    /// left out.
+    pub fn duplicate_entries(&self) -> Vec<Vec<&CatalogEntry>> {
+        let mut groups: Vec<Vec<&CatalogEntry>> = Vec::new();
+        let mut group_of: HashMap<String, usize> = HashMap::new();
+
+        for entry in &self.entries {
+            let key = entry.compound.equivalence_key();
+            match group_of.get(&key) {
+                Some(&index) => groups[index].push(entry),
+                None => {
+                    group_of.insert(key, groups.len());
+                    groups.push(vec![entry]);
+                }
+            }
+        }
+
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+
+    /// Groups compounds by Hill-notation molecular formula, keeping only formulas shared by at
+    /// least two distinct compounds. Chemically equivalent copies from aggregate files appear
+    /// once per group.
+    pub fn isomer_groups(&self) -> BTreeMap<String, Vec<Compound>> {
+        let mut seen = HashSet::new();
+        let mut groups: BTreeMap<String, Vec<Compound>> = BTreeMap::new();
+
+        for entry in &self.entries {
+            let Some(formula) = entry.compound.normalized_formula() else {
+                continue;
+            };
+
+            if seen.insert(entry.compound.equivalence_key()) {
+                groups
+                    .entry(formula)
+                    .or_default()
+                    .push(entry.compound.clone());
+            }
+        }
+
+        groups.retain(|_, compounds| compounds.len() > 1);
+        groups
+    }
+
+    /// Returns the distinct compounds that share `compound`'s molecular formula but are not
+    /// equivalent to it.
+    pub fn isomers_of(&self, compound: &Compound) -> Vec<Compound> {
+        let Some(formula) = compound.normalized_formula() else {
+            return Vec::new();
+        };
+        let key = compound.equivalence_key();
+
+        self.isomer_groups()
+            .remove(&formula)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|candidate| candidate.equivalence_key() != key)
+            .collect()
+    }
+
+    /// Returns every compound with a computable molar mass, lightest first.
+    pub fn sorted_by_molar_mass(&self) -> Vec<Compound> {
+        let mut weighted: Vec<(f64, Compound)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .compound
+                    .molar_mass()
+                    .map(|mass| (mass, entry.compound.clone()))
+            })
+            .collect();
+
+        weighted.sort_by(|(left, _), (right, _)| left.total_cmp(right));
+        weighted.into_iter().map(|(_, compound)| compound).collect()
+    }
+
+    /// Returns compounds whose molar mass in g/mol falls within `range`. Compounds without
+    /// a computable molar mass are excluded.
+    pub fn compounds_with_molar_mass(&self, range: impl RangeBounds<f64>) -> Vec<Compound> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .compound
+                    .molar_mass()
+                    .is_some_and(|mass| range.contains(&mass))
+            })
+            .map(|entry| entry.compound.clone())
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -152,6 +284,16 @@ fn collect_entries(
     Ok(())
 }
 
+/// Fills what a catalog file may leave out, for every loader of catalog files: the
+/// structure of entries written by name only, then the formula annotations. Names the parser
+/// cannot read stay without SMILES and are reported by the validation checks.
+pub fn fill_loaded_compound(compound: &mut Compound) {
+    if compound.smiles.is_none() && compound.molecular_formula.trim().is_empty() {
+        let _ = compound.fill_structure_from_name();
+    }
+    compound.fill_formula_annotations();
+}
+
 fn append_from_file(
     path: &Path,
     categories: &[String],
@@ -181,7 +323,8 @@ fn append_from_file(
             source,
         })?;
 
-    for compound in parsed.compounds {
+    for mut compound in parsed.compounds {
+        fill_loaded_compound(&mut compound);
         entries.push(CatalogEntry {
             compound,
             categories: categories.to_vec(),
@@ -210,6 +353,11 @@ mod tests {
                     functional_groups: Vec::new(),
                     notes: None,
                     smiles: Some("CCO".to_string()),
+                    katex_skeletal_formula: None,
+                    katex_molecular_formula: None,
+                    state: None,
+                    charge: None,
+                    hydrate_water: None,
                 },
                 categories: vec![
                     "Organic".to_string(),
@@ -229,6 +377,11 @@ mod tests {
                     functional_groups: Vec::new(),
                     notes: None,
                     smiles: Some("Cl[Na]".to_string()),
+                    katex_skeletal_formula: None,
+                    katex_molecular_formula: None,
+                    state: None,
+                    charge: None,
+                    hydrate_water: None,
                 },
                 categories: vec!["Inorganic".to_string(), "Salts".to_string()],
             },
@@ -289,6 +442,108 @@ mod tests {
         assert_eq!(error, CatalogError::EmptyPath);
     }
 
+    #[test]
+    fn sorts_and_filters_by_molar_mass() {
+        let catalog = sample_catalog();
+
+        let sorted: Vec<String> = catalog
+            .sorted_by_molar_mass()
+            .into_iter()
+            .map(|compound| compound.iupac_name)
+            .collect();
+        assert_eq!(sorted, vec!["ethanol", "sodium chloride"]);
+
+        let light = catalog.compounds_with_molar_mass(..50.0);
+        assert_eq!(light.len(), 1);
+        assert_eq!(light[0].iupac_name, "ethanol");
+
+        assert!(catalog.compounds_with_molar_mass(100.0..).is_empty());
+    }
+
+    #[test]
+    fn finds_equivalent_compounds() {
+        let catalog = sample_catalog();
+        let ethanol = Compound {
+            skeletal_formula: "C2H5OH".to_string(),
+            smiles: Some("OCC".to_string()),
+            ..catalog.entries()[0].compound.clone()
+        };
+
+        let matches = catalog.equivalent_compounds(&ethanol);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].iupac_name, "ethanol");
+    }
+
+    #[test]
+    fn groups_isomers_by_formula() {
+        let mut entries = sample_catalog().entries().to_vec();
+        let ethanol = entries[0].clone();
+        entries.push(CatalogEntry {
+            categories: vec!["Organic".to_string()],
+            ..ethanol.clone()
+        });
+        entries.push(CatalogEntry {
+            compound: Compound {
+                iupac_name: "methoxymethane".to_string(),
+                common_name: Some("dimethyl ether".to_string()),
+                local_name: None,
+                skeletal_formula: "CH3-O-CH3".to_string(),
+                smiles: Some("COC".to_string()),
+                ..ethanol.compound.clone()
+            },
+            categories: ethanol.categories.clone(),
+        });
+        let catalog = Catalog::new(entries);
+
+        let groups = catalog.isomer_groups();
+        assert_eq!(groups.len(), 1);
+        let names: Vec<&str> = groups["C2H6O"]
+            .iter()
+            .map(|compound| compound.iupac_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["ethanol", "methoxymethane"]);
+
+        let isomers = catalog.isomers_of(&ethanol.compound);
+        assert_eq!(isomers.len(), 1);
+        assert_eq!(isomers[0].iupac_name, "methoxymethane");
+    }
+
+    #[test]
+    fn deduplicates_aggregate_copies_by_structure() {
+        let mut entries = sample_catalog().entries().to_vec();
+        let ethanol = entries[0].clone();
+        entries.push(CatalogEntry {
+            compound: Compound {
+                skeletal_formula: "C2H5OH".to_string(),
+                smiles: Some("OCC".to_string()),
+                ..ethanol.compound.clone()
+            },
+            categories: ethanol.categories[..2].to_vec(),
+        });
+        let catalog = Catalog::new(entries);
+
+        let aliphatic = catalog
+            .compounds_for(&["Organic".to_string()])
+            .expect("category exists");
+        assert_eq!(aliphatic.len(), 1);
+        assert_eq!(aliphatic[0].skeletal_formula, "CH3-CH2-OH");
+
+        let duplicates = catalog.duplicate_entries();
+        assert_eq!(duplicates.len(), 1);
+        let paths: Vec<String> = duplicates[0]
+            .iter()
+            .map(|entry| entry.category_path())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "Organic / Aliphatic_compounds / Alcohols_and_ethers / Primary_alcohols",
+                "Organic / Aliphatic_compounds",
+            ]
+        );
+        assert_eq!(ethanol.compound.structure_key().as_deref(), Some("CCO"));
+    }
+
     #[test]
     fn loads_entries_from_directory_tree() {
         let root = tempfile::tempdir().expect("temporary directory should be created");
@@ -315,6 +570,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CCO".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             }],
         );
 
@@ -330,6 +590,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("Cl[Na]".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             }],
         );
 
@@ -350,6 +615,24 @@ mod tests {
         assert_eq!(catalog.all_compounds().len(), 2);
     }
 
+    #[test]
+    fn fills_structure_for_entries_written_by_name() {
+        let root = tempfile::tempdir().expect("temporary directory should be created");
+        let ketones = root.path().join("Organic").join("Ketones");
+        fs::create_dir_all(&ketones).expect("directory tree should be created");
+        fs::write(
+            ketones.join("compounds.json"),
+            r#"{"compounds": [{"iupac_name": "butan-2-one", "common_name": null, "local_name": null}]}"#,
+        )
+        .expect("compound list should be written");
+
+        let catalog = Catalog::from_directory(root.path()).expect("catalog should load");
+        let compound = &catalog.entries()[0].compound;
+
+        assert_eq!(compound.molecular_formula, "C4H8O");
+        assert!(compound.smiles.is_some());
+    }
+
     #[test]
     fn errors_when_category_path_missing() {
         let root = tempfile::tempdir().expect("temporary directory should be created");
@@ -366,6 +649,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("C".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             }],
         );
 
@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+
+use crate::compound::Formula;
+use crate::periodic_table;
+
+/// Largest multiplier tried when turning mole ratios into whole numbers (C4H9 needs ×4
+/// from H/C = 2.25).
+pub const MAX_RATIO_MULTIPLIER: u32 = 8;
+
+/// How far a scaled mole ratio, or a molar mass divided by the empirical formula mass, may
+/// sit from a whole number and still count as one.
+const WHOLE_NUMBER_TOLERANCE: f64 = 0.1;
+
+/// How far the given percentages may add up from 100 before they are rejected, allowing
+/// for values rounded to one decimal place.
+const TOTAL_TOLERANCE: f64 = 1.0;
+
+/// Mass share of one element in a formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementShare {
+    pub element: String,
+    pub atoms: u32,
+    /// Percentage of the formula mass, from 0 to 100.
+    pub mass_percent: f64,
+}
+
+/// Mass-percent composition of a formula, one share per element in Hill order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MassComposition {
+    shares: Vec<ElementShare>,
+}
+
+impl MassComposition {
+    pub fn shares(&self) -> &[ElementShare] {
+        &self.shares
+    }
+
+    /// Mass percent of `symbol`, or `None` when the element is absent.
+    pub fn percent_of(&self, symbol: &str) -> Option<f64> {
+        self.shares
+            .iter()
+            .find(|share| share.element == symbol)
+            .map(|share| share.mass_percent)
+    }
+
+    /// Percentages rounded to one decimal place, as a problem would state them.
+    pub fn rounded(&self) -> Vec<(String, f64)> {
+        self.shares
+            .iter()
+            .map(|share| {
+                (
+                    share.element.clone(),
+                    (share.mass_percent * 10.0).round() / 10.0,
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CompositionError {
+    #[error("no element percentages given")]
+    Empty,
+    #[error("unknown element symbol '{symbol}'")]
+    UnknownElement { symbol: String },
+    #[error("{element} is listed more than once")]
+    DuplicateElement { element: String },
+    #[error("percentage of {element} must be positive")]
+    InvalidPercent { element: String },
+    #[error("percentages add up to {total:.1}% rather than 100%")]
+    IncompleteTotal { total: f64 },
+    #[error("no whole-number ratio up to ×{MAX_RATIO_MULTIPLIER} fits the percentages")]
+    NoWholeNumberRatio,
+    #[error(
+        "molar mass {molar_mass:.2} g/mol is not a whole multiple of the empirical formula mass {empirical_mass:.2} g/mol"
+    )]
+    MolarMassMismatch {
+        molar_mass: f64,
+        empirical_mass: f64,
+    },
+}
+
+/// Mass-percent composition of `formula`, including any hydrate water.
+pub fn mass_composition(formula: &Formula) -> MassComposition {
+    let total = formula.molar_mass();
+    let shares = hill_order(formula)
+        .into_iter()
+        .map(|(element, atoms)| {
+            let mass = periodic_table::element(&element)
+                .map_or(0.0, |data| data.atomic_weight * f64::from(atoms));
+            ElementShare {
+                element,
+                atoms,
+                mass_percent: if total > 0.0 {
+                    mass / total * 100.0
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect();
+
+    MassComposition { shares }
+}
+
+/// Simplest whole-number ratio of the atoms in `formula` (`C6H12O6` → `CH2O`). The charge
+/// is dropped.
+pub fn empirical_formula(formula: &Formula) -> Formula {
+    let divisor = formula
+        .elements()
+        .values()
+        .fold(0, |divisor, count| gcd(divisor, *count))
+        .max(1);
+    let counts = formula
+        .elements()
+        .iter()
+        .map(|(symbol, count)| (symbol.clone(), count / divisor))
+        .collect();
+
+    Formula::from_counts(counts, 0)
+}
+
+/// Solves for the empirical formula from mass percentages such as
+/// `[("C", 40.0), ("H", 6.7), ("O", 53.3)]` → `CH2O`.
+///
+/// Each percentage is divided by the atomic weight, the mole amounts are divided by the
+/// smallest, and the ratios are scaled by 1, 2, … up to [`MAX_RATIO_MULTIPLIER`] until every
+/// one is within 0.1 of a whole number.
+///
+/// # Errors
+/// * Returns [`CompositionError::Empty`] when no percentages are given.
+/// * Returns [`CompositionError::UnknownElement`], [`CompositionError::DuplicateElement`] or
+///   [`CompositionError::InvalidPercent`] for a malformed entry.
+/// * Returns [`CompositionError::IncompleteTotal`] when the percentages do not add up to
+///   100 within rounding.
+/// * Returns [`CompositionError::NoWholeNumberRatio`] when no multiplier gives whole numbers.
+pub fn empirical_formula_from_percentages<S: AsRef<str>>(
+    percentages: &[(S, f64)],
+) -> Result<Formula, CompositionError> {
+    if percentages.is_empty() {
+        return Err(CompositionError::Empty);
+    }
+
+    let mut moles = BTreeMap::new();
+    for (symbol, percent) in percentages {
+        let symbol = symbol.as_ref().trim();
+        let element =
+            periodic_table::element(symbol).ok_or_else(|| CompositionError::UnknownElement {
+                symbol: symbol.to_string(),
+            })?;
+        if !percent.is_finite() || *percent <= 0.0 {
+            return Err(CompositionError::InvalidPercent {
+                element: symbol.to_string(),
+            });
+        }
+        if moles
+            .insert(symbol.to_string(), percent / element.atomic_weight)
+            .is_some()
+        {
+            return Err(CompositionError::DuplicateElement {
+                element: symbol.to_string(),
+            });
+        }
+    }
+
+    let total: f64 = percentages.iter().map(|(_, percent)| percent).sum();
+    if (total - 100.0).abs() > TOTAL_TOLERANCE {
+        return Err(CompositionError::IncompleteTotal { total });
+    }
+
+    let smallest = moles.values().copied().fold(f64::INFINITY, f64::min);
+    for multiplier in 1..=MAX_RATIO_MULTIPLIER {
+        let scaled: Vec<f64> = moles
+            .values()
+            .map(|amount| amount / smallest * f64::from(multiplier))
+            .collect();
+        if scaled
+            .iter()
+            .all(|ratio| (ratio - ratio.round()).abs() <= WHOLE_NUMBER_TOLERANCE)
+        {
+            let counts = moles
+                .keys()
+                .cloned()
+                .zip(scaled.iter().map(|ratio| ratio.round() as u32))
+                .collect();
+            return Ok(Formula::from_counts(counts, 0));
+        }
+    }
+
+    Err(CompositionError::NoWholeNumberRatio)
+}
+
+/// Scales an empirical formula to the molecular formula with the given molar mass
+/// (`CH2O` at 180 g/mol → `C6H12O6`).
+///
+/// # Errors
+/// Returns [`CompositionError::MolarMassMismatch`] when `molar_mass` is not within 0.1 of a
+/// whole multiple of the empirical formula mass.
+pub fn molecular_formula_from_empirical(
+    empirical: &Formula,
+    molar_mass: f64,
+) -> Result<Formula, CompositionError> {
+    let empirical_mass = empirical.molar_mass();
+    let mismatch = CompositionError::MolarMassMismatch {
+        molar_mass,
+        empirical_mass,
+    };
+    if empirical_mass <= 0.0 || !molar_mass.is_finite() {
+        return Err(mismatch);
+    }
+
+    let multiple = molar_mass / empirical_mass;
+    if multiple.round() < 1.0 || (multiple - multiple.round()).abs() > WHOLE_NUMBER_TOLERANCE {
+        return Err(mismatch);
+    }
+
+    Ok(multiply(empirical, multiple.round() as u32))
+}
+
+/// Multiplies every atom count of `formula` by `factor`.
+pub(crate) fn multiply(formula: &Formula, factor: u32) -> Formula {
+    let counts = formula
+        .elements()
+        .iter()
+        .map(|(symbol, count)| (symbol.clone(), count * factor))
+        .collect();
+    Formula::from_counts(counts, formula.charge() * factor as i32)
+}
+
+/// Element symbols and counts of `formula` in Hill order.
+fn hill_order(formula: &Formula) -> Vec<(String, u32)> {
+    let mut elements: Vec<(String, u32)> = formula
+        .elements()
+        .iter()
+        .map(|(symbol, count)| (symbol.clone(), *count))
+        .collect();
+    if formula.count("C") > 0 {
+        elements.sort_by_key(|(symbol, _)| match symbol.as_str() {
+            "C" => 0,
+            "H" => 1,
+            _ => 2,
+        });
+    }
+    elements
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formula(text: &str) -> Formula {
+        Formula::parse(text).expect("formula should parse")
+    }
+
+    #[test]
+    fn computes_mass_percentages_in_hill_order() {
+        let ethanol = mass_composition(&formula("CH3CH2OH"));
+        let elements: Vec<&str> = ethanol
+            .shares()
+            .iter()
+            .map(|share| share.element.as_str())
+            .collect();
+        assert_eq!(elements, ["C", "H", "O"]);
+        assert!((ethanol.percent_of("C").unwrap() - 52.14).abs() < 0.01);
+        assert!((ethanol.percent_of("O").unwrap() - 34.73).abs() < 0.01);
+
+        let total: f64 = ethanol
+            .shares()
+            .iter()
+            .map(|share| share.mass_percent)
+            .sum();
+        assert!((total - 100.0).abs() < 1e-9);
+        assert_eq!(ethanol.percent_of("N"), None);
+
+        let blue_vitriol = mass_composition(&formula("CuSO4·5H2O"));
+        assert!((blue_vitriol.percent_of("Cu").unwrap() - 25.45).abs() < 0.01);
+    }
+
+    #[test]
+    fn reduces_formulas_to_their_empirical_formula() {
+        assert_eq!(
+            empirical_formula(&formula("C6H12O6")).hill_notation(),
+            "CH2O"
+        );
+        assert_eq!(empirical_formula(&formula("C8H18")).hill_notation(), "C4H9");
+        assert_eq!(empirical_formula(&formula("H2O")).hill_notation(), "H2O");
+    }
+
+    #[test]
+    fn solves_empirical_formula_from_percentages() {
+        let glucose = [("C", 40.0), ("H", 6.7), ("O", 53.3)];
+        let empirical = empirical_formula_from_percentages(&glucose).expect("should solve");
+        assert_eq!(empirical.hill_notation(), "CH2O");
+
+        let molecular = molecular_formula_from_empirical(&empirical, 180.2).expect("should scale");
+        assert_eq!(molecular.hill_notation(), "C6H12O6");
+
+        let octane = mass_composition(&formula("C8H18")).rounded();
+        let empirical = empirical_formula_from_percentages(&octane).expect("should solve");
+        assert_eq!(empirical.hill_notation(), "C4H9");
+        assert_eq!(
+            molecular_formula_from_empirical(&empirical, 114.2)
+                .map(|formula| formula.hill_notation()),
+            Ok("C8H18".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_inconsistent_problems() {
+        assert_eq!(
+            empirical_formula_from_percentages::<&str>(&[]),
+            Err(CompositionError::Empty)
+        );
+        assert_eq!(
+            empirical_formula_from_percentages(&[("Xx", 50.0), ("H", 50.0)]),
+            Err(CompositionError::UnknownElement {
+                symbol: "Xx".to_string()
+            })
+        );
+        assert!(matches!(
+            empirical_formula_from_percentages(&[("C", 40.0), ("H", 6.7)]),
+            Err(CompositionError::IncompleteTotal { .. })
+        ));
+        assert!(matches!(
+            molecular_formula_from_empirical(&formula("CH2O"), 100.0),
+            Err(CompositionError::MolarMassMismatch { .. })
+        ));
+    }
+}
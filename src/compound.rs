@@ -1,5 +1,20 @@
+pub mod descriptors;
+pub mod formula;
+pub mod katex;
+
+pub use descriptors::StructuralDescriptors;
+pub use formula::{Formula, FormulaError, PhysicalState};
 use std::fmt;
 
+use crate::composition::{self, MassComposition};
+use crate::functional_groups::{FunctionalGroupKind, detect_functional_groups};
+use crate::homologous_series::{HOMOLOGOUS_SERIES, HomologousSeries};
+use crate::ionic::IonicCompound;
+use crate::nomenclature::{self, NameParseError};
+use crate::oxidation::{OxidationStates, oxidation_states};
+use crate::smiles::Molecule;
+use crate::stereo::{self, StereoDescriptors};
+
 /// Functional group metadata that appears within compound definitions.
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct FunctionalGroup {
@@ -30,8 +45,10 @@ pub struct Compound {
     /// Name commonly used in Japan.
     pub local_name: Option<String>,
     /// Skeletal structure formula shown as the primary structural representation.
+    #[serde(default)]
     pub skeletal_formula: String,
     /// Molecular formula shown as a compact representation.
+    #[serde(default)]
     pub molecular_formula: String,
     /// Generalized formula shared across a series, when applicable.
     #[serde(default)]
@@ -45,6 +62,21 @@ pub struct Compound {
     /// SMILES string used for structure rendering when available.
     #[serde(default)]
     pub smiles: Option<String>,
+    /// mhchem markup for `skeletal_formula`, e.g. `\ce{CH3-C#CH}`.
+    #[serde(default)]
+    pub katex_skeletal_formula: Option<String>,
+    /// mhchem markup for `molecular_formula`, e.g. `\ce{CuSO4\cdot 5H2O}`.
+    #[serde(default)]
+    pub katex_molecular_formula: Option<String>,
+    /// Physical state written after the formula, such as the `(aq)` of `HF (aq)`.
+    #[serde(default)]
+    pub state: Option<PhysicalState>,
+    /// Net charge, `Some(0)` for neutral compounds and `Some(2)` for `Cu(NH3)4^{2+}`.
+    #[serde(default)]
+    pub charge: Option<i32>,
+    /// Water of crystallisation per formula unit, `Some(5)` for `CuSO4·5H2O`.
+    #[serde(default)]
+    pub hydrate_water: Option<u32>,
 }
 
 impl Compound {
@@ -74,8 +106,285 @@ impl Compound {
         format!("{} ({})", self.skeletal_formula, self.molecular_formula)
     }
 
+    /// KaTeX markup for the skeletal formula: `katex_skeletal_formula` when present,
+    /// otherwise generated from `skeletal_formula`.
+    pub fn katex_skeletal(&self) -> String {
+        markup_or_generated(&self.katex_skeletal_formula, &self.skeletal_formula)
+    }
+
+    /// KaTeX markup for the molecular formula: `katex_molecular_formula` when present,
+    /// otherwise generated from `molecular_formula`.
+    pub fn katex_molecular(&self) -> String {
+        markup_or_generated(&self.katex_molecular_formula, &self.molecular_formula)
+    }
+
+    /// Parses `molecular_formula` into element counts, charge, and annotations.
+    pub fn formula(&self) -> Result<Formula, FormulaError> {
+        Formula::parse(&self.molecular_formula)
+    }
+
+    /// Molar mass in g/mol, or `None` when `molecular_formula` cannot be parsed
+    /// (generic polymers, descriptive placeholders).
+    pub fn molar_mass(&self) -> Option<f64> {
+        self.formula().ok().map(|formula| formula.molar_mass())
+    }
+
+    /// Mass-percent composition of `molecular_formula`, or `None` when it cannot be parsed.
+    pub fn mass_composition(&self) -> Option<MassComposition> {
+        self.formula()
+            .ok()
+            .map(|formula| composition::mass_composition(&formula))
+    }
+
+    /// Simplest whole-number ratio of the atoms in `molecular_formula` (`CH2O` for glucose),
+    /// or `None` when it cannot be parsed.
+    pub fn empirical_formula(&self) -> Option<Formula> {
+        self.formula()
+            .ok()
+            .map(|formula| composition::empirical_formula(&formula))
+    }
+
+    /// Oxidation numbers of the elements in `molecular_formula`, or `None` when the formula
+    /// cannot be parsed or leaves several elements undetermined.
+    pub fn oxidation_states(&self) -> Option<OxidationStates> {
+        oxidation_states(&self.molecular_formula).ok()
+    }
+
+    /// Cations and anion of `molecular_formula` when it is a salt, oxide or hydroxide of
+    /// known ions.
+    pub fn ionic_composition(&self) -> Option<IonicCompound> {
+        IonicCompound::decompose(&self.molecular_formula)
+    }
+
+    /// Molecular formula rewritten in Hill notation, or `None` when it cannot be parsed.
+    pub fn normalized_formula(&self) -> Option<String> {
+        self.formula().ok().map(|formula| formula.hill_notation())
+    }
+
+    /// Canonical structure key from the SMILES graph, identical for every way of writing
+    /// the same structure, or `None` without a parsable SMILES string. See
+    /// [`Molecule::canonical_key`].
+    pub fn structure_key(&self) -> Option<String> {
+        self.molecule().map(|molecule| molecule.canonical_key())
+    }
+
+    /// Key shared by entries that describe the same substance.
+    ///
+    /// Parsable SMILES contribute their [`structure_key`](Self::structure_key) when they
+    /// agree with the molecular formula; SMILES that stand in for something else, such as
+    /// `C` for diamond (read as methane), fall back to the IUPAC name. Without SMILES the
+    /// skeletal formula is compared with bond dashes and whitespace removed. The structure is
+    /// paired with the Hill formula and any typed physical state, so isomers and `NH3 (aq)`
+    /// stay distinct while notation variants such as `CH3OH` and `CH3-OH` collapse together.
+    pub fn equivalence_key(&self) -> String {
+        let formula = self.normalized_formula();
+        let structure = match self.molecule() {
+            Some(molecule)
+                if formula
+                    .as_ref()
+                    .is_none_or(|formula| *formula == molecule.formula().hill_notation()) =>
+            {
+                format!("smiles:{}", molecule.canonical_key())
+            }
+            Some(_) => format!("name:{}", self.iupac_name.trim().to_lowercase()),
+            None => {
+                let skeletal: String = self
+                    .skeletal_formula
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '–' | '—'))
+                    .collect();
+                format!("skeletal:{}", skeletal)
+            }
+        };
+
+        let formula = formula.unwrap_or_else(|| self.molecular_formula.trim().to_string());
+        match self.state {
+            Some(state) => format!("{} {} {}", formula, structure, state),
+            None => format!("{} {}", formula, structure),
+        }
+    }
+
+    /// Degree of unsaturation, ring count, heavy-atom and heteroatom counts for
+    /// carbon-containing compounds. Counts come from `molecular_formula`, falling back to the
+    /// SMILES graph when the formula is generic; rings always come from the SMILES graph.
+    pub fn descriptors(&self) -> Option<StructuralDescriptors> {
+        let molecule = self.molecule();
+        let formula = self
+            .formula()
+            .ok()
+            .or_else(|| molecule.as_ref().map(Molecule::formula))?;
+
+        StructuralDescriptors::from_formula(&formula, molecule.as_ref().map(Molecule::ring_count))
+    }
+
+    /// Stereocentres and stereogenic double bonds with their R/S and E/Z configuration where
+    /// the SMILES string specifies it, or `None` without a parsable SMILES string.
+    pub fn stereo_descriptors(&self) -> Option<StereoDescriptors> {
+        self.molecule()
+            .map(|molecule| stereo::stereo_descriptors(&molecule))
+    }
+
+    /// Functional groups found by substructure matching on the SMILES graph, or `None`
+    /// without a parsable SMILES string.
+    pub fn detected_functional_groups(&self) -> Option<Vec<FunctionalGroupKind>> {
+        self.molecule()
+            .map(|molecule| detect_functional_groups(&molecule))
+    }
+
+    /// Functional groups for filtering and quizzes: detected from SMILES when available,
+    /// otherwise recognised from the hand-entered `functional_groups` labels.
+    pub fn functional_group_kinds(&self) -> Vec<FunctionalGroupKind> {
+        self.detected_functional_groups().unwrap_or_else(|| {
+            let mut kinds: Vec<FunctionalGroupKind> = self
+                .functional_groups
+                .iter()
+                .filter_map(|group| FunctionalGroupKind::from_label(&group.name_en))
+                .collect();
+            kinds.sort();
+            kinds.dedup();
+            kinds
+        })
+    }
+
+    /// Series of `dataset/organic_homologous_series.json` that `series_general_formula`
+    /// names before its colon ("Alkynes (one C≡C): CnH2n-2") and whose general formula the
+    /// molecular formula fits, or `None` when no listed series qualifies.
+    pub fn homologous_series(&self) -> Option<&'static HomologousSeries> {
+        let described = self.series_general_formula.as_deref()?;
+        let name = described
+            .split(':')
+            .next()
+            .unwrap_or(described)
+            .to_lowercase();
+        let formula = self.formula().ok()?;
+
+        HOMOLOGOUS_SERIES
+            .iter()
+            .find(|series| name.contains(&series.keyword()) && series.fits(&formula))
+    }
+
+    /// Fills an empty `functional_groups` list with the groups detected from SMILES.
+    /// Returns `true` when groups were added; hand-entered lists are left untouched.
+    pub fn fill_functional_groups(&mut self) -> bool {
+        if !self.functional_groups.is_empty() {
+            return false;
+        }
+
+        let detected = self.detected_functional_groups().unwrap_or_default();
+        self.functional_groups = detected
+            .into_iter()
+            .map(FunctionalGroupKind::to_functional_group)
+            .collect();
+        !self.functional_groups.is_empty()
+    }
+
+    /// Fills missing `state`, `charge` and `hydrate_water` from the formula strings, so
+    /// `HF (aq)`, `Ag(NH3)2^+` and `CuSO4·5H2O` carry their annotations as typed fields.
+    /// The state is read from the molecular formula or else the skeletal formula, and the
+    /// charge falls back to the SMILES graph. Returns `true` when any field was filled.
+    pub fn fill_formula_annotations(&mut self) -> bool {
+        let formula = self.formula().ok();
+        let mut filled = false;
+
+        if self.state.is_none() {
+            self.state = formula.as_ref().and_then(Formula::state).or_else(|| {
+                Formula::parse(&self.skeletal_formula)
+                    .ok()
+                    .and_then(|skeletal| skeletal.state())
+            });
+            filled |= self.state.is_some();
+        }
+        if self.charge.is_none() {
+            self.charge = formula
+                .as_ref()
+                .map(Formula::charge)
+                .or_else(|| self.molecule().map(|molecule| molecule.net_charge()));
+            filled |= self.charge.is_some();
+        }
+        if self.hydrate_water.is_none() {
+            self.hydrate_water = formula.as_ref().map(Formula::hydrate_water);
+            filled |= self.hydrate_water.is_some();
+        }
+
+        filled
+    }
+
+    /// Fills a missing `smiles` and empty formula fields from the systematic `iupac_name`,
+    /// so catalog entries can be written by name only. The skeletal formula falls back to
+    /// the molecular formula. Returns `Ok(false)` when the entry already has SMILES.
+    pub fn fill_structure_from_name(&mut self) -> Result<bool, NameParseError> {
+        if self.molecule().is_some() {
+            return Ok(false);
+        }
+
+        let smiles = nomenclature::smiles_from_name(&self.iupac_name)?;
+        let formula = Molecule::parse(&smiles)?.formula().hill_notation();
+        if self.molecular_formula.trim().is_empty() {
+            self.molecular_formula = formula.clone();
+        }
+        if self.skeletal_formula.trim().is_empty() {
+            self.skeletal_formula = formula;
+        }
+        self.smiles = Some(smiles);
+        Ok(true)
+    }
+
+    /// Returns `true` when a typed `answer` names this compound: one of its names ignoring
+    /// case and spacing, or a systematic name for the same structure that the name parser
+    /// accepts, with the preferred parent and lowest locants.
+    pub fn accepts_name(&self, answer: &str) -> bool {
+        let normalize = |name: &str| name.split_whitespace().collect::<String>().to_lowercase();
+        let typed = normalize(answer);
+        if typed.is_empty() {
+            return false;
+        }
+        let names = [
+            Some(&self.iupac_name),
+            self.common_name.as_ref(),
+            self.local_name.as_ref(),
+        ];
+        if names
+            .into_iter()
+            .flatten()
+            .any(|name| normalize(name) == typed)
+        {
+            return true;
+        }
+
+        match (self.molecule(), nomenclature::parse_name(answer)) {
+            (Some(expected), Ok(parsed)) => expected.canonical_key() == parsed.canonical_key(),
+            _ => false,
+        }
+    }
+
+    /// Skeletal-formula SVG drawn natively from `smiles`, or `None` without a parsable SMILES.
+    pub fn skeletal_svg(&self) -> Option<String> {
+        self.molecule()
+            .map(|molecule| crate::depiction::skeletal_svg(&molecule))
+    }
+
+    /// Expanded structural-formula SVG (every atom and hydrogen shown) drawn from `smiles`,
+    /// or `None` without a parsable SMILES.
+    pub fn full_structure_svg(&self) -> Option<String> {
+        self.molecule()
+            .map(|molecule| crate::depiction::full_structure_svg(&molecule))
+    }
+
+    fn molecule(&self) -> Option<Molecule> {
+        self.smiles
+            .as_deref()
+            .filter(|smiles| !smiles.trim().is_empty())
+            .and_then(|smiles| Molecule::parse(smiles).ok())
+    }
+
+    /// Returns `true` when both compounds share an [`equivalence_key`](Self::equivalence_key).
+    pub fn is_equivalent(&self, other: &Compound) -> bool {
+        self.equivalence_key() == other.equivalence_key()
+    }
+
     /// Builds descriptive sections for optional metadata such as series formulas,
-    /// functional groups, and notes. Empty or whitespace-only values are ignored.
+    /// functional groups, molar mass, structural descriptors, stereochemistry, and notes.
+    /// Empty or whitespace-only values are ignored.
     pub fn detail_sections(&self) -> Vec<CompoundDetailSection> {
         let mut sections = Vec::new();
 
@@ -95,7 +404,11 @@ impl Compound {
             }
         }
 
-        add_section_if_present(&mut sections, "Series formula", &self.series_general_formula);
+        add_section_if_present(
+            &mut sections,
+            "Series formula",
+            &self.series_general_formula,
+        );
 
         if !self.functional_groups.is_empty() {
             let groups = self
@@ -110,6 +423,47 @@ impl Compound {
             });
         }
 
+        if let Some(molar_mass) = self.molar_mass() {
+            sections.push(CompoundDetailSection {
+                label: "Molar mass".to_string(),
+                entries: vec![format!("{:.2} g/mol", molar_mass)],
+            });
+        }
+
+        if let Some(descriptors) = self.descriptors() {
+            sections.push(CompoundDetailSection {
+                label: "Structure".to_string(),
+                entries: descriptors.summary(),
+            });
+        }
+
+        if let Some(molecule) = self.molecule() {
+            let stereo = stereo::stereo_descriptors(&molecule);
+            if !stereo.is_empty() {
+                sections.push(CompoundDetailSection {
+                    label: "Stereochemistry".to_string(),
+                    entries: stereo.summary(&molecule),
+                });
+            }
+        }
+
+        let mut form = Vec::new();
+        if let Some(state) = self.state {
+            form.push(format!("{} {}", state.name(), state));
+        }
+        if let Some(charge) = self.charge.filter(|charge| *charge != 0) {
+            form.push(format!("charge {:+}", charge));
+        }
+        if let Some(water) = self.hydrate_water.filter(|water| *water > 0) {
+            form.push(format!("hydrate with {} H2O", water));
+        }
+        if !form.is_empty() {
+            sections.push(CompoundDetailSection {
+                label: "Form".to_string(),
+                entries: form,
+            });
+        }
+
         add_section_if_present(&mut sections, "Notes", &self.notes);
 
         sections
@@ -122,6 +476,15 @@ impl fmt::Display for Compound {
     }
 }
 
+fn markup_or_generated(markup: &Option<String>, plain: &str) -> String {
+    markup
+        .as_deref()
+        .map(str::trim)
+        .filter(|markup| !markup.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| katex::to_mhchem(plain))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +500,11 @@ mod tests {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("CCO".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         }
     }
 
@@ -158,6 +526,11 @@ mod tests {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("c1ccccc1".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         };
 
         assert_eq!(compound.english_label(), "benzene");
@@ -181,6 +554,11 @@ mod tests {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("c1ccccc1".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         };
 
         let name = compound.display_name();
@@ -203,6 +581,58 @@ mod tests {
         assert!(formatted.contains("CH3-CH2-OH (C2H6O)"));
     }
 
+    #[test]
+    fn normalized_formula_uses_hill_notation() {
+        assert_eq!(ethanol().normalized_formula().as_deref(), Some("C2H6O"));
+    }
+
+    #[test]
+    fn equivalence_ignores_notation_but_not_isomers() {
+        let methanol = |skeletal: &str, smiles: Option<&str>| Compound {
+            iupac_name: "methanol".to_string(),
+            common_name: None,
+            local_name: None,
+            skeletal_formula: skeletal.to_string(),
+            molecular_formula: "CH4O".to_string(),
+            series_general_formula: None,
+            functional_groups: Vec::new(),
+            notes: None,
+            smiles: smiles.map(str::to_string),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
+        };
+
+        assert!(methanol("CH3OH", None).is_equivalent(&methanol("CH3-OH", None)));
+        assert!(methanol("CH3OH", Some("CO")).is_equivalent(&methanol("H3C-OH", Some("OC"))));
+
+        let dimethyl_ether = Compound {
+            iupac_name: "methoxymethane".to_string(),
+            skeletal_formula: "CH3-O-CH3".to_string(),
+            molecular_formula: "C2H6O".to_string(),
+            smiles: Some("COC".to_string()),
+            ..ethanol()
+        };
+        assert!(!ethanol().is_equivalent(&dimethyl_ether));
+    }
+
+    #[test]
+    fn descriptors_fall_back_to_smiles_for_generic_formulas() {
+        let compound = Compound {
+            iupac_name: "benzene".to_string(),
+            molecular_formula: "aromatic hydrocarbon".to_string(),
+            smiles: Some("c1ccccc1".to_string()),
+            ..ethanol()
+        };
+
+        let descriptors = compound.descriptors().expect("SMILES provides counts");
+        assert_eq!(descriptors.degree_of_unsaturation, Some(4));
+        assert_eq!(descriptors.ring_count, Some(1));
+        assert_eq!(descriptors.heavy_atom_count, 6);
+    }
+
     #[test]
     fn smiles_defaults_to_none_on_missing_field() {
         let json = r#"{
@@ -237,6 +667,114 @@ mod tests {
         assert_eq!(parsed.smiles.as_deref(), Some("CC(=O)O"));
     }
 
+    #[test]
+    fn fills_functional_groups_from_smiles() {
+        let mut lactic_acid = Compound {
+            iupac_name: "2-hydroxypropanoic acid".to_string(),
+            common_name: Some("lactic acid".to_string()),
+            local_name: None,
+            skeletal_formula: "CH3-CH(OH)-COOH".to_string(),
+            molecular_formula: "C3H6O3".to_string(),
+            series_general_formula: None,
+            functional_groups: Vec::new(),
+            notes: None,
+            smiles: Some("CC(O)C(=O)O".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
+        };
+
+        assert_eq!(
+            lactic_acid.detected_functional_groups(),
+            Some(vec![
+                FunctionalGroupKind::Hydroxyl,
+                FunctionalGroupKind::Carboxyl
+            ])
+        );
+        assert!(lactic_acid.fill_functional_groups());
+        let names: Vec<&str> = lactic_acid
+            .functional_groups
+            .iter()
+            .map(|group| group.name_en.as_str())
+            .collect();
+        assert_eq!(names, vec!["Hydroxyl", "Carboxyl"]);
+        assert!(!lactic_acid.fill_functional_groups());
+
+        let unparsed = Compound {
+            smiles: None,
+            functional_groups: vec![FunctionalGroup {
+                name_en: "Ester linkage".to_string(),
+                name_ja: "エステル結合".to_string(),
+                pattern: "R–COO–R'".to_string(),
+            }],
+            ..lactic_acid
+        };
+        assert_eq!(unparsed.detected_functional_groups(), None);
+        assert_eq!(
+            unparsed.functional_group_kinds(),
+            vec![FunctionalGroupKind::Ester]
+        );
+    }
+
+    #[test]
+    fn homologous_series_needs_the_name_and_a_fitting_formula() {
+        let series_of = |text: &str, formula: &str| {
+            Compound {
+                series_general_formula: Some(text.to_string()),
+                molecular_formula: formula.to_string(),
+                ..ethanol()
+            }
+            .homologous_series()
+            .map(|series| series.name_en.as_str())
+        };
+
+        assert_eq!(
+            series_of("Primary alcohols (1°): CnH2n+1OH (n>=1)", "C2H6O"),
+            Some("Alcohols")
+        );
+        assert_eq!(
+            series_of("Cycloalkanes: CnH2n (n>=3)", "C6H12"),
+            Some("Cycloalkanes")
+        );
+        assert_eq!(
+            series_of(
+                "Simple ethers: CnH2n+2O (same as monohydric alcohols)",
+                "C2H6O"
+            ),
+            None
+        );
+        assert_eq!(
+            series_of("Saturated dicarboxylic acids: HOOC-(CH2)n-COOH", "C2H2O4"),
+            None
+        );
+        assert_eq!(ethanol().homologous_series(), None);
+    }
+
+    #[test]
+    fn katex_markup_prefers_catalog_fields_and_falls_back_to_conversion() {
+        let json = r#"{
+            "iupac_name": "propyne",
+            "common_name": null,
+            "local_name": "プロピン",
+            "skeletal_formula": "CH3-C≡CH",
+            "molecular_formula": "C3H4",
+            "katex_skeletal_formula": "\\ce{CH3-C#CH}"
+        }"#;
+
+        let parsed: Compound =
+            serde_json::from_str(json).expect("compound should parse with katex fields");
+
+        assert_eq!(
+            parsed.katex_skeletal_formula.as_deref(),
+            Some("\\ce{CH3-C#CH}")
+        );
+        assert!(parsed.katex_molecular_formula.is_none());
+        assert_eq!(parsed.katex_skeletal(), "\\ce{CH3-C#CH}");
+        assert_eq!(parsed.katex_molecular(), "\\ce{C3H4}");
+    }
+
     #[test]
     fn detail_sections_include_all_metadata() {
         let compound = Compound {
@@ -260,10 +798,15 @@ mod tests {
             ],
             notes: Some("Weak acid found in vinegar".to_string()),
             smiles: Some("CC(=O)O".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         };
 
         let sections = compound.detail_sections();
-        assert_eq!(sections.len(), 3);
+        assert_eq!(sections.len(), 5);
         assert_eq!(sections[0].label, "Series formula");
         assert_eq!(sections[0].entries, vec!["C_nH_{2n+2}O_2".to_string()]);
         assert_eq!(sections[1].label, "Functional groups");
@@ -274,8 +817,23 @@ mod tests {
                 "Hydroxyl / ヒドロキシ基: -OH".to_string(),
             ]
         );
-        assert_eq!(sections[2].label, "Notes");
-        assert_eq!(sections[2].entries, vec!["Weak acid found in vinegar".to_string()]);
+        assert_eq!(sections[2].label, "Molar mass");
+        assert_eq!(sections[2].entries, vec!["60.05 g/mol".to_string()]);
+        assert_eq!(sections[3].label, "Structure");
+        assert_eq!(
+            sections[3].entries,
+            vec![
+                "Degree of unsaturation: 1".to_string(),
+                "Rings: 0".to_string(),
+                "Heavy atoms: 4".to_string(),
+                "Heteroatoms: O 2".to_string(),
+            ]
+        );
+        assert_eq!(sections[4].label, "Notes");
+        assert_eq!(
+            sections[4].entries,
+            vec!["Weak acid found in vinegar".to_string()]
+        );
     }
 
     #[test]
@@ -290,10 +848,47 @@ mod tests {
             functional_groups: Vec::new(),
             notes: Some("   ".to_string()),
             smiles: Some("C".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         };
 
         let sections = compound.detail_sections();
-        assert!(sections.is_empty());
+        let labels: Vec<&str> = sections
+            .iter()
+            .map(|section| section.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["Molar mass", "Structure"]);
+    }
+
+    #[test]
+    fn detail_sections_show_stereochemistry() {
+        let mut alanine = ethanol();
+        alanine.iupac_name = "2-aminopropanoic acid".to_string();
+        alanine.molecular_formula = "C3H7NO2".to_string();
+        alanine.smiles = Some("C[C@@H](C(=O)O)N".to_string());
+
+        let stereo = alanine
+            .detail_sections()
+            .into_iter()
+            .find(|section| section.label == "Stereochemistry")
+            .expect("alanine has a stereocentre");
+        assert_eq!(stereo.entries, vec!["Chiral centre C2: S"]);
+
+        alanine.smiles = Some("CC(N)C(=O)O".to_string());
+        let descriptors = alanine.stereo_descriptors().expect("SMILES should parse");
+        assert_eq!(descriptors.centres.len(), 1);
+        assert_eq!(descriptors.centres[0].configuration, None);
+
+        let mut maleic = alanine.clone();
+        maleic.molecular_formula = "C4H4O4".to_string();
+        maleic.smiles = Some("OC(=O)/C=C\\C(=O)O".to_string());
+        let mut fumaric = alanine.clone();
+        fumaric.smiles = Some("OC(=O)/C=C/C(=O)O".to_string());
+        assert!(!maleic.is_equivalent(&fumaric));
+        assert!(ethanol().stereo_descriptors().is_some_and(|d| d.is_empty()));
     }
 
     #[test]
@@ -323,6 +918,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn formula_parses_molecular_formula() {
+        let formula = ethanol().formula().expect("ethanol formula should parse");
+
+        assert_eq!(formula.count("C"), 2);
+        assert_eq!(formula.count("H"), 6);
+        assert_eq!(formula.count("O"), 1);
+    }
+
+    #[test]
+    fn molar_mass_is_none_for_generic_formulas() {
+        let mut compound = ethanol();
+        assert!((compound.molar_mass().expect("ethanol has a molar mass") - 46.069).abs() < 0.01);
+
+        compound.molecular_formula = "(C6H10O5)n".to_string();
+        assert!(compound.molar_mass().is_none());
+    }
+
     #[test]
     fn optional_fields_default_to_none() {
         let json = r#"{
@@ -339,5 +952,84 @@ mod tests {
         assert!(parsed.series_general_formula.is_none());
         assert!(parsed.notes.is_none());
         assert!(parsed.smiles.is_none());
+        assert!(parsed.state.is_none());
+        assert!(parsed.charge.is_none());
+    }
+
+    #[test]
+    fn fills_state_charge_and_hydrate_from_formulas() {
+        let mut hydrofluoric = Compound {
+            iupac_name: "hydrogen fluoride".to_string(),
+            skeletal_formula: "HF (aq)".to_string(),
+            molecular_formula: "HF".to_string(),
+            smiles: None,
+            ..ethanol()
+        };
+        assert!(hydrofluoric.fill_formula_annotations());
+        assert_eq!(hydrofluoric.state, Some(PhysicalState::Aqueous));
+        assert_eq!(hydrofluoric.charge, Some(0));
+        assert_eq!(hydrofluoric.hydrate_water, Some(0));
+
+        let mut tetraammine = Compound {
+            molecular_formula: "Cu(NH3)4^{2+}".to_string(),
+            skeletal_formula: "[Cu(NH3)4]2+".to_string(),
+            smiles: None,
+            ..ethanol()
+        };
+        tetraammine.fill_formula_annotations();
+        assert_eq!(tetraammine.state, None);
+        assert_eq!(tetraammine.charge, Some(2));
+
+        let mut blue_vitriol = Compound {
+            molecular_formula: "CuSO4·5H2O".to_string(),
+            smiles: None,
+            state: Some(PhysicalState::Solid),
+            ..ethanol()
+        };
+        blue_vitriol.fill_formula_annotations();
+        assert_eq!(blue_vitriol.state, Some(PhysicalState::Solid));
+        assert_eq!(blue_vitriol.hydrate_water, Some(5));
+        let form = blue_vitriol
+            .detail_sections()
+            .into_iter()
+            .find(|section| section.label == "Form")
+            .expect("annotations are listed");
+        assert_eq!(form.entries, vec!["solid (s)", "hydrate with 5 H2O"]);
+
+        let mut polymer = Compound {
+            molecular_formula: "(C6H10O5)n".to_string(),
+            smiles: Some("[NH4+]".to_string()),
+            ..ethanol()
+        };
+        polymer.fill_formula_annotations();
+        assert_eq!(polymer.charge, Some(1));
+        assert_eq!(polymer.hydrate_water, None);
+    }
+
+    #[test]
+    fn accepts_typed_names_by_structure() {
+        let compound = ethanol();
+
+        assert!(compound.accepts_name("Ethyl  Alcohol"));
+        assert!(compound.accepts_name("ethan-1-ol"));
+        assert!(!compound.accepts_name("ethan-2-ol"));
+        assert!(!compound.accepts_name("methanol"));
+        assert!(!compound.accepts_name("not a name"));
+    }
+
+    #[test]
+    fn fills_structure_from_systematic_name() {
+        let json = r#"{
+            "iupac_name": "2-methylpropan-2-ol",
+            "common_name": null,
+            "local_name": null
+        }"#;
+        let mut compound: Compound = serde_json::from_str(json).expect("name-only entry");
+
+        assert_eq!(compound.fill_structure_from_name(), Ok(true));
+        assert_eq!(compound.molecular_formula, "C4H10O");
+        assert_eq!(compound.skeletal_formula, "C4H10O");
+        assert!(compound.smiles.is_some());
+        assert_eq!(compound.fill_structure_from_name(), Ok(false));
     }
 }
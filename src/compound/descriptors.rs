@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use super::Formula;
+
+/// Structural descriptors derived from a compound's formula and, when available, its SMILES
+/// graph. They hint at the shape of a molecule without revealing the formula itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuralDescriptors {
+    /// Rings plus π bonds, or `None` when the formula contains elements the usual
+    /// valence rule does not cover (metals, noble gases) or describes an ion.
+    pub degree_of_unsaturation: Option<u32>,
+    /// Independent rings in the SMILES graph, or `None` without a parsable SMILES string.
+    pub ring_count: Option<usize>,
+    /// Atoms other than hydrogen.
+    pub heavy_atom_count: u32,
+    /// Counts of atoms other than carbon and hydrogen, keyed by element symbol.
+    pub heteroatom_counts: BTreeMap<String, u32>,
+}
+
+impl StructuralDescriptors {
+    /// Computes descriptors for carbon-containing formulas. Returns `None` for formulas
+    /// without carbon, where these descriptors carry little meaning.
+    pub fn from_formula(formula: &Formula, ring_count: Option<usize>) -> Option<Self> {
+        if formula.count("C") == 0 {
+            return None;
+        }
+
+        let heavy_atom_count = formula
+            .elements()
+            .iter()
+            .filter(|(symbol, _)| symbol.as_str() != "H")
+            .map(|(_, count)| count)
+            .sum();
+
+        let heteroatom_counts = formula
+            .elements()
+            .iter()
+            .filter(|(symbol, _)| !matches!(symbol.as_str(), "C" | "H"))
+            .map(|(symbol, count)| (symbol.clone(), *count))
+            .collect();
+
+        Some(Self {
+            degree_of_unsaturation: degree_of_unsaturation(formula),
+            ring_count,
+            heavy_atom_count,
+            heteroatom_counts,
+        })
+    }
+
+    /// Human-readable lines such as `"Degree of unsaturation: 4"`, in a fixed order.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        if let Some(unsaturation) = self.degree_of_unsaturation {
+            lines.push(format!("Degree of unsaturation: {}", unsaturation));
+        }
+
+        if let Some(rings) = self.ring_count {
+            lines.push(format!("Rings: {}", rings));
+        }
+
+        lines.push(format!("Heavy atoms: {}", self.heavy_atom_count));
+
+        let heteroatoms = if self.heteroatom_counts.is_empty() {
+            "none".to_string()
+        } else {
+            self.heteroatom_counts
+                .iter()
+                .map(|(symbol, count)| format!("{} {}", symbol, count))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        lines.push(format!("Heteroatoms: {}", heteroatoms));
+
+        lines
+    }
+}
+
+/// Applies `1 + Σ n(v - 2) / 2` over the elements of a neutral formula.
+fn degree_of_unsaturation(formula: &Formula) -> Option<u32> {
+    if formula.charge() != 0 {
+        return None;
+    }
+
+    let mut doubled: i64 = 2;
+    for (symbol, count) in formula.elements() {
+        let valence: i64 = match symbol.as_str() {
+            "H" | "F" | "Cl" | "Br" | "I" => 1,
+            "O" | "S" | "Se" => 2,
+            "B" | "N" | "P" | "As" => 3,
+            "C" | "Si" | "Ge" => 4,
+            _ => return None,
+        };
+        doubled += i64::from(*count) * (valence - 2);
+    }
+
+    if doubled < 0 || doubled % 2 != 0 {
+        return None;
+    }
+
+    u32::try_from(doubled / 2).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptors(formula: &str, rings: Option<usize>) -> Option<StructuralDescriptors> {
+        let formula = Formula::parse(formula).expect("formula should parse");
+        StructuralDescriptors::from_formula(&formula, rings)
+    }
+
+    #[test]
+    fn computes_degree_of_unsaturation() {
+        let unsaturation = |formula: &str| {
+            descriptors(formula, None)
+                .expect("organic formula")
+                .degree_of_unsaturation
+        };
+
+        assert_eq!(unsaturation("C6H14"), Some(0));
+        assert_eq!(unsaturation("C2H4O2"), Some(1));
+        assert_eq!(unsaturation("C6H6"), Some(4));
+        assert_eq!(unsaturation("C5H5N"), Some(4));
+        assert_eq!(unsaturation("C2H3Cl"), Some(1));
+        assert_eq!(unsaturation("CH3COONa"), None);
+        assert_eq!(unsaturation("CH3COO^-"), None);
+    }
+
+    #[test]
+    fn counts_heavy_atoms_and_heteroatoms() {
+        let glycine = descriptors("C2H5NO2", Some(0)).expect("organic formula");
+
+        assert_eq!(glycine.heavy_atom_count, 5);
+        assert_eq!(glycine.heteroatom_counts.get("N"), Some(&1));
+        assert_eq!(glycine.heteroatom_counts.get("O"), Some(&2));
+        assert_eq!(
+            glycine.summary(),
+            vec![
+                "Degree of unsaturation: 1",
+                "Rings: 0",
+                "Heavy atoms: 5",
+                "Heteroatoms: N 1, O 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_formulas_without_carbon() {
+        assert!(descriptors("NaCl", None).is_none());
+    }
+}
@@ -0,0 +1,816 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::periodic_table;
+
+/// Physical state annotation such as the `(aq)` in `HCl (aq)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhysicalState {
+    Solid,
+    Liquid,
+    Gas,
+    Aqueous,
+}
+
+impl PhysicalState {
+    /// Returns the short annotation used inside formulas, e.g. `aq`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Solid => "s",
+            Self::Liquid => "l",
+            Self::Gas => "g",
+            Self::Aqueous => "aq",
+        }
+    }
+
+    /// Returns the English name of the state, e.g. `aqueous`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Solid => "solid",
+            Self::Liquid => "liquid",
+            Self::Gas => "gas",
+            Self::Aqueous => "aqueous",
+        }
+    }
+
+    pub(crate) fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "s" => Some(Self::Solid),
+            "l" => Some(Self::Liquid),
+            "g" => Some(Self::Gas),
+            "aq" => Some(Self::Aqueous),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PhysicalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.symbol())
+    }
+}
+
+/// Errors raised while parsing a formula string. Positions are character offsets
+/// into the trimmed input.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum FormulaError {
+    #[error("formula is empty")]
+    Empty,
+    #[error("unknown element symbol '{symbol}' at position {position}")]
+    UnknownElement { symbol: String, position: usize },
+    #[error("unexpected character '{character}' at position {position}")]
+    UnexpectedCharacter { character: char, position: usize },
+    #[error("unclosed '{open}' opened at position {position}")]
+    UnclosedBracket { open: char, position: usize },
+    #[error("closing '{close}' at position {position} has no matching opening bracket")]
+    UnmatchedBracket { close: char, position: usize },
+    #[error("count at position {position} is not a positive integer")]
+    InvalidCount { position: usize },
+    #[error("invalid charge notation '{notation}'")]
+    InvalidCharge { notation: String },
+}
+
+/// Element composition parsed from a molecular or condensed formula.
+///
+/// Counts include every part of the formula, so `CuSO4·5H2O` contributes ten hydrogens
+/// and nine oxygens. Charges (`CO3^2-`, `NH4+`) and state suffixes (`HCl (aq)`) are kept
+/// separately from the element counts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Formula {
+    elements: BTreeMap<String, u32>,
+    charge: i32,
+    hydrate_water: u32,
+    state: Option<PhysicalState>,
+}
+
+impl Formula {
+    /// Parses a formula such as `NH4HCO3`, `(CH3)2CHOH`, `[Cu(NH3)4]^{2+}` or `CuSO4·5H2O`.
+    ///
+    /// Bond markers (`-`, `=`, `≡`) between groups are accepted so condensed structural
+    /// formulas like `CH3-CH2-OH` parse to the same composition as `C2H6O`.
+    pub fn parse(input: &str) -> Result<Self, FormulaError> {
+        let text: Vec<char> = input.trim().chars().collect();
+        if text.is_empty() {
+            return Err(FormulaError::Empty);
+        }
+
+        let (body_end, state) = split_state(&text);
+        let (body_end, charge) = split_charge(&text[..body_end])?;
+        if body_end == 0 {
+            return Err(FormulaError::Empty);
+        }
+
+        let mut elements = BTreeMap::new();
+        let mut hydrate_water = 0;
+        let mut start = 0;
+
+        for (index, part) in split_parts(&text[..body_end]).into_iter().enumerate() {
+            let (multiplier, counts) = Parser::new(&text, start, part).parse_part(index > 0)?;
+            if index > 0 && is_water(&counts) {
+                hydrate_water += multiplier;
+            }
+            merge_counts(&mut elements, &counts, multiplier);
+            start = part + 1;
+        }
+
+        Ok(Self {
+            elements,
+            charge,
+            hydrate_water,
+            state,
+        })
+    }
+
+    /// Builds a formula from precomputed element counts, e.g. those derived from a
+    /// structure graph.
+    pub(crate) fn from_counts(elements: BTreeMap<String, u32>, charge: i32) -> Self {
+        Self {
+            elements,
+            charge,
+            hydrate_water: 0,
+            state: None,
+        }
+    }
+
+    /// Element symbols mapped to their total atom counts.
+    pub fn elements(&self) -> &BTreeMap<String, u32> {
+        &self.elements
+    }
+
+    /// Number of atoms of `symbol`, or zero when the element is absent.
+    pub fn count(&self, symbol: &str) -> u32 {
+        self.elements.get(symbol).copied().unwrap_or(0)
+    }
+
+    /// Total number of atoms across all elements.
+    pub fn atom_count(&self) -> u32 {
+        self.elements.values().sum()
+    }
+
+    /// Net charge; zero for neutral species.
+    pub fn charge(&self) -> i32 {
+        self.charge
+    }
+
+    /// Number of water molecules written after a hydrate dot.
+    pub fn hydrate_water(&self) -> u32 {
+        self.hydrate_water
+    }
+
+    /// State suffix such as `(aq)` when one was written.
+    pub fn state(&self) -> Option<PhysicalState> {
+        self.state
+    }
+
+    /// Molar mass in g/mol computed from standard atomic weights.
+    pub fn molar_mass(&self) -> f64 {
+        self.elements
+            .iter()
+            .filter_map(|(symbol, count)| {
+                periodic_table::element(symbol)
+                    .map(|element| element.atomic_weight * f64::from(*count))
+            })
+            .sum()
+    }
+}
+
+impl Formula {
+    /// Writes the composition in Hill order: carbon, then hydrogen, then the remaining
+    /// elements alphabetically. Without carbon every element is alphabetical. A non-zero
+    /// charge is appended as `^2-`/`^+`; hydrate dots and states are not reproduced.
+    pub fn hill_notation(&self) -> String {
+        let mut symbols: Vec<&String> = self.elements.keys().collect();
+        if self.elements.contains_key("C") {
+            symbols.sort_by_key(|symbol| match symbol.as_str() {
+                "C" => 0,
+                "H" => 1,
+                _ => 2,
+            });
+        }
+
+        let mut text = String::new();
+        for symbol in symbols {
+            text.push_str(symbol);
+            let count = self.elements[symbol];
+            if count > 1 {
+                text.push_str(&count.to_string());
+            }
+        }
+
+        match self.charge {
+            0 => {}
+            1 => text.push_str("^+"),
+            -1 => text.push_str("^-"),
+            charge if charge > 0 => text.push_str(&format!("^{}+", charge)),
+            charge => text.push_str(&format!("^{}-", -charge)),
+        }
+
+        text
+    }
+
+    /// Returns `true` when both formulas have the same element counts and charge,
+    /// ignoring how they were written (`CH3-CH2-OH` vs `C2H6O`).
+    pub fn same_composition(&self, other: &Formula) -> bool {
+        self.elements == other.elements && self.charge == other.charge
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.hill_notation())
+    }
+}
+
+impl FromStr for Formula {
+    type Err = FormulaError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+/// Splits `input` into the formula body and its net charge, dropping any state suffix, for
+/// callers that need the written grouping rather than merged element counts.
+pub(crate) fn split_body(input: &str) -> Result<(String, i32), FormulaError> {
+    let text: Vec<char> = input.trim().chars().collect();
+    let (body_end, _) = split_state(&text);
+    let (body_end, charge) = split_charge(&text[..body_end])?;
+    Ok((text[..body_end].iter().collect(), charge))
+}
+
+/// Element symbol or bracketed group as written in a formula, with its count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Atom(String, u32),
+    Group(Vec<Unit>, u32),
+}
+
+/// Splits a formula body from [`split_body`] at hydrate separators and reads each part as
+/// written, keeping bracketed groups. Parts after the first carry their leading multiplier
+/// (`5` in `CuSO4·5H2O`).
+pub(crate) fn written_parts(body: &str) -> Vec<(u32, Vec<Unit>)> {
+    let text: Vec<char> = body.chars().collect();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+
+    for index in 0..=text.len() {
+        let boundary = match text.get(index) {
+            None => true,
+            Some('(' | '[') => {
+                depth += 1;
+                false
+            }
+            Some(')' | ']') => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            Some(separator) => depth == 0 && HYDRATE_SEPARATORS.contains(separator),
+        };
+        if boundary {
+            let mut position = start;
+            while text.get(position).is_some_and(|c| c.is_whitespace()) {
+                position += 1;
+            }
+            let multiplier = if parts.is_empty() {
+                1
+            } else {
+                read_count(&text[..index], &mut position).unwrap_or(1)
+            };
+            let units = parse_units(&text[..index], &mut position);
+            parts.push((multiplier, units));
+            start = index + 1;
+        }
+    }
+
+    parts
+}
+
+/// Reads a single part without hydrate separators, such as an ion body like `"H2PO4"`.
+pub(crate) fn written_units(text: &str) -> Vec<Unit> {
+    let text: Vec<char> = text.chars().collect();
+    parse_units(&text, &mut 0)
+}
+
+/// Reads element symbols and bracketed groups; the input has already been validated by
+/// [`Formula::parse`].
+fn parse_units(text: &[char], position: &mut usize) -> Vec<Unit> {
+    let mut units = Vec::new();
+
+    while let Some(&character) = text.get(*position) {
+        match character {
+            'A'..='Z' => {
+                let mut symbol = character.to_string();
+                *position += 1;
+                if let Some(&next) = text.get(*position).filter(|c| c.is_ascii_lowercase()) {
+                    symbol.push(next);
+                    *position += 1;
+                }
+                let count = read_count(text, position).unwrap_or(1);
+                units.push(Unit::Atom(symbol, count));
+            }
+            '(' | '[' => {
+                *position += 1;
+                let inner = parse_units(text, position);
+                let count = read_count(text, position).unwrap_or(1);
+                units.push(Unit::Group(inner, count));
+            }
+            ')' | ']' => {
+                *position += 1;
+                return units;
+            }
+            _ => {
+                debug_assert!(character.is_whitespace() || BOND_MARKERS.contains(&character));
+                *position += 1;
+            }
+        }
+    }
+
+    units
+}
+
+fn read_count(text: &[char], position: &mut usize) -> Option<u32> {
+    let start = *position;
+    while text.get(*position).is_some_and(char::is_ascii_digit) {
+        *position += 1;
+    }
+    text[start..*position]
+        .iter()
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+impl Unit {
+    /// The `(symbol, count)` sequence of `units` when none of them is a bracketed group.
+    pub(crate) fn atom_run(units: &[Unit]) -> Option<Vec<(String, u32)>> {
+        units
+            .iter()
+            .map(|unit| match unit {
+                Unit::Atom(symbol, count) => Some((symbol.clone(), *count)),
+                Unit::Group(..) => None,
+            })
+            .collect()
+    }
+}
+
+const HYDRATE_SEPARATORS: [char; 4] = ['·', '•', '.', '*'];
+const BOND_MARKERS: [char; 4] = ['-', '=', '≡', '–'];
+
+fn split_state(text: &[char]) -> (usize, Option<PhysicalState>) {
+    if text.last() != Some(&')') {
+        return (text.len(), None);
+    }
+
+    let Some(open) = text.iter().rposition(|character| *character == '(') else {
+        return (text.len(), None);
+    };
+
+    let symbol: String = text[open + 1..text.len() - 1].iter().collect();
+    match PhysicalState::from_symbol(&symbol) {
+        Some(state) => {
+            let mut end = open;
+            while end > 0 && text[end - 1].is_whitespace() {
+                end -= 1;
+            }
+            (end, Some(state))
+        }
+        None => (text.len(), None),
+    }
+}
+
+/// Strips a trailing charge. `^` introduces an explicit charge (`^2-`, `^{2+}`); a bare
+/// trailing sign is also accepted, in which case digits directly before it are read as the
+/// charge magnitude only for monatomic ions (`Cu2+`, `S2-`) and as a count otherwise (`NH4+`).
+fn split_charge(text: &[char]) -> Result<(usize, i32), FormulaError> {
+    if let Some(caret) = text.iter().rposition(|character| *character == '^') {
+        let mut notation: &[char] = &text[caret + 1..];
+        if notation.first() == Some(&'{') && notation.last() == Some(&'}') {
+            notation = &notation[1..notation.len() - 1];
+        }
+        return Ok((caret, parse_charge_notation(notation)?));
+    }
+
+    let Some(&sign) = text.last().filter(|last| matches!(last, '+' | '-')) else {
+        return Ok((text.len(), 0));
+    };
+    let sign = if sign == '+' { 1 } else { -1 };
+    let sign_at = text.len() - 1;
+
+    let digits_start = text[..sign_at]
+        .iter()
+        .rposition(|character| !character.is_ascii_digit())
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    if digits_start < sign_at && is_single_symbol(&text[..digits_start]) {
+        let magnitude = parse_number(&text[digits_start..sign_at]).ok_or_else(|| {
+            FormulaError::InvalidCharge {
+                notation: text[digits_start..].iter().collect(),
+            }
+        })?;
+        return Ok((digits_start, sign * magnitude as i32));
+    }
+
+    Ok((sign_at, sign))
+}
+
+fn parse_charge_notation(notation: &[char]) -> Result<i32, FormulaError> {
+    let invalid = || FormulaError::InvalidCharge {
+        notation: notation.iter().collect(),
+    };
+
+    let (sign_char, digits) = match (notation.first(), notation.last()) {
+        (Some(first @ ('+' | '-')), _) => (*first, &notation[1..]),
+        (_, Some(last @ ('+' | '-'))) => (*last, &notation[..notation.len() - 1]),
+        _ => return Err(invalid()),
+    };
+
+    let magnitude = if digits.is_empty() {
+        1
+    } else {
+        parse_number(digits)
+            .filter(|value| *value > 0)
+            .ok_or_else(invalid)?
+    };
+
+    let sign = if sign_char == '+' { 1 } else { -1 };
+    Ok(sign * magnitude as i32)
+}
+
+fn is_single_symbol(text: &[char]) -> bool {
+    match text {
+        [first] => first.is_ascii_uppercase(),
+        [first, second] => first.is_ascii_uppercase() && second.is_ascii_lowercase(),
+        _ => false,
+    }
+}
+
+fn parse_number(digits: &[char]) -> Option<u32> {
+    if digits.is_empty() || !digits.iter().all(char::is_ascii_digit) {
+        return None;
+    }
+    digits.iter().collect::<String>().parse().ok()
+}
+
+/// Returns the end offsets of each hydrate-separated part. Separators inside brackets are
+/// not treated as part boundaries.
+fn split_parts(text: &[char]) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut depth = 0usize;
+
+    for (index, character) in text.iter().enumerate() {
+        match character {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            separator if depth == 0 && HYDRATE_SEPARATORS.contains(separator) => ends.push(index),
+            _ => {}
+        }
+    }
+
+    ends.push(text.len());
+    ends
+}
+
+fn is_water(counts: &BTreeMap<String, u32>) -> bool {
+    counts.len() == 2 && counts.get("H") == Some(&2) && counts.get("O") == Some(&1)
+}
+
+fn merge_counts(target: &mut BTreeMap<String, u32>, source: &BTreeMap<String, u32>, factor: u32) {
+    for (symbol, count) in source {
+        *target.entry(symbol.clone()).or_insert(0) += count * factor;
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [char],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a [char], start: usize, end: usize) -> Self {
+        Self {
+            text,
+            position: start,
+            end,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        (self.position < self.end).then(|| self.text[self.position])
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn parse_part(
+        &mut self,
+        allow_multiplier: bool,
+    ) -> Result<(u32, BTreeMap<String, u32>), FormulaError> {
+        self.skip_whitespace();
+        let multiplier = if allow_multiplier {
+            self.parse_count()?.unwrap_or(1)
+        } else {
+            1
+        };
+
+        let counts = self.parse_sequence(None)?;
+        if counts.is_empty() {
+            return Err(match self.peek() {
+                Some(character) => FormulaError::UnexpectedCharacter {
+                    character,
+                    position: self.position,
+                },
+                None => FormulaError::Empty,
+            });
+        }
+
+        Ok((multiplier, counts))
+    }
+
+    /// Parses groups until the end of the part or the closing bracket matching `open`.
+    fn parse_sequence(
+        &mut self,
+        open: Option<(char, usize)>,
+    ) -> Result<BTreeMap<String, u32>, FormulaError> {
+        let mut counts = BTreeMap::new();
+        let mut after_bond = false;
+
+        loop {
+            self.skip_whitespace();
+            let Some(character) = self.peek() else {
+                if let Some((open, position)) = open {
+                    return Err(FormulaError::UnclosedBracket { open, position });
+                }
+                break;
+            };
+
+            match character {
+                'A'..='Z' => {
+                    let symbol = self.parse_symbol()?;
+                    let count = self.parse_count()?.unwrap_or(1);
+                    *counts.entry(symbol).or_insert(0) += count;
+                }
+                '(' | '[' => {
+                    let position = self.position;
+                    self.position += 1;
+                    let inner = self.parse_sequence(Some((character, position)))?;
+                    let count = self.parse_count()?.unwrap_or(1);
+                    merge_counts(&mut counts, &inner, count);
+                }
+                ')' | ']' => {
+                    let expected = match open {
+                        Some(('(', _)) => ')',
+                        Some(('[', _)) => ']',
+                        _ => {
+                            return Err(FormulaError::UnmatchedBracket {
+                                close: character,
+                                position: self.position,
+                            });
+                        }
+                    };
+                    if character != expected || after_bond {
+                        return Err(FormulaError::UnexpectedCharacter {
+                            character,
+                            position: self.position,
+                        });
+                    }
+                    self.position += 1;
+                    break;
+                }
+                bond if BOND_MARKERS.contains(&bond) && !counts.is_empty() && !after_bond => {
+                    self.position += 1;
+                    after_bond = true;
+                    continue;
+                }
+                _ => {
+                    return Err(FormulaError::UnexpectedCharacter {
+                        character,
+                        position: self.position,
+                    });
+                }
+            }
+
+            after_bond = false;
+        }
+
+        if after_bond {
+            return Err(FormulaError::UnexpectedCharacter {
+                character: self.text[self.position - 1],
+                position: self.position - 1,
+            });
+        }
+
+        Ok(counts)
+    }
+
+    fn parse_symbol(&mut self) -> Result<String, FormulaError> {
+        let start = self.position;
+        let mut symbol = String::new();
+        symbol.push(self.text[self.position]);
+        self.position += 1;
+
+        if let Some(next) = self.peek().filter(char::is_ascii_lowercase) {
+            symbol.push(next);
+            self.position += 1;
+        }
+
+        if periodic_table::element(&symbol).is_none() {
+            return Err(FormulaError::UnknownElement {
+                symbol,
+                position: start,
+            });
+        }
+
+        Ok(symbol)
+    }
+
+    fn parse_count(&mut self) -> Result<Option<u32>, FormulaError> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|character| character.is_ascii_digit())
+        {
+            self.position += 1;
+        }
+
+        if start == self.position {
+            return Ok(None);
+        }
+
+        parse_number(&self.text[start..self.position])
+            .filter(|count| *count > 0)
+            .map(Some)
+            .ok_or(FormulaError::InvalidCount { position: start })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(formula: &Formula) -> Vec<(&str, u32)> {
+        formula
+            .elements()
+            .iter()
+            .map(|(symbol, count)| (symbol.as_str(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn parses_simple_molecular_formula() {
+        let formula = Formula::parse("NH4HCO3").expect("formula should parse");
+
+        assert_eq!(
+            counts(&formula),
+            vec![("C", 1), ("H", 5), ("N", 1), ("O", 3)]
+        );
+        assert_eq!(formula.charge(), 0);
+        assert_eq!(formula.atom_count(), 10);
+    }
+
+    #[test]
+    fn expands_parenthesised_groups() {
+        let formula = Formula::parse("(CH3)2CHOH").expect("formula should parse");
+
+        assert_eq!(counts(&formula), vec![("C", 3), ("H", 8), ("O", 1)]);
+    }
+
+    #[test]
+    fn condensed_formula_with_bonds_matches_molecular_formula() {
+        let condensed = Formula::parse("CH3-CH2-OH").expect("condensed formula should parse");
+        let molecular = Formula::parse("C2H6O").expect("molecular formula should parse");
+
+        assert_eq!(condensed, molecular);
+        assert_eq!(
+            Formula::parse("HC≡CH").expect("triple bond should parse"),
+            Formula::parse("C2H2").expect("molecular formula should parse"),
+        );
+    }
+
+    #[test]
+    fn reads_state_suffix() {
+        let formula = Formula::parse("HCl (aq)").expect("formula should parse");
+
+        assert_eq!(counts(&formula), vec![("Cl", 1), ("H", 1)]);
+        assert_eq!(formula.state(), Some(PhysicalState::Aqueous));
+    }
+
+    #[test]
+    fn reads_explicit_and_trailing_charges() {
+        let carbonate = Formula::parse("CO3^2-").expect("carbonate should parse");
+        assert_eq!(carbonate.charge(), -2);
+        assert_eq!(carbonate.count("O"), 3);
+
+        let complex = Formula::parse("[Cu(NH3)4]^{2+}").expect("complex ion should parse");
+        assert_eq!(complex.charge(), 2);
+        assert_eq!(counts(&complex), vec![("Cu", 1), ("H", 12), ("N", 4)]);
+
+        let ammonium = Formula::parse("NH4+").expect("ammonium should parse");
+        assert_eq!(ammonium.charge(), 1);
+        assert_eq!(ammonium.count("H"), 4);
+
+        let copper = Formula::parse("Cu2+").expect("monatomic cation should parse");
+        assert_eq!(copper.charge(), 2);
+        assert_eq!(copper.count("Cu"), 1);
+    }
+
+    #[test]
+    fn counts_hydrate_water() {
+        let formula = Formula::parse("CuSO4·5H2O").expect("hydrate should parse");
+
+        assert_eq!(formula.hydrate_water(), 5);
+        assert_eq!(
+            counts(&formula),
+            vec![("Cu", 1), ("H", 10), ("O", 9), ("S", 1)]
+        );
+
+        let alum = Formula::parse("KAl(SO4)2·12H2O").expect("alum should parse");
+        assert_eq!(alum.hydrate_water(), 12);
+        assert_eq!(alum.count("O"), 20);
+    }
+
+    #[test]
+    fn computes_molar_mass() {
+        let water = Formula::parse("H2O").expect("water should parse");
+        assert!((water.molar_mass() - 18.015).abs() < 0.001);
+
+        let blue_vitriol = Formula::parse("CuSO4·5H2O").expect("hydrate should parse");
+        assert!((blue_vitriol.molar_mass() - 249.68).abs() < 0.01);
+    }
+
+    #[test]
+    fn writes_hill_notation() {
+        let cases = [
+            ("CH3-CH2-OH", "C2H6O"),
+            ("(CH3)2CHOH", "C3H8O"),
+            ("NH4HCO3", "CH5NO3"),
+            ("H2SO4", "H2O4S"),
+            ("CuSO4·5H2O", "CuH10O9S"),
+            ("CO3^2-", "CO3^2-"),
+            ("NH4+", "H4N^+"),
+        ];
+
+        for (input, expected) in cases {
+            let formula = Formula::parse(input).expect("formula should parse");
+            assert_eq!(formula.to_string(), expected, "input {}", input);
+        }
+    }
+
+    #[test]
+    fn compares_composition_regardless_of_notation() {
+        let condensed = Formula::parse("HCl (aq)").expect("formula should parse");
+        let molecular = Formula::parse("HCl").expect("formula should parse");
+        let chloride = Formula::parse("Cl-").expect("formula should parse");
+
+        assert!(condensed.same_composition(&molecular));
+        assert!(!molecular.same_composition(&chloride));
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(Formula::parse("  "), Err(FormulaError::Empty));
+        assert_eq!(
+            Formula::parse("(C6H10O5)n"),
+            Err(FormulaError::UnexpectedCharacter {
+                character: 'n',
+                position: 9
+            })
+        );
+        assert_eq!(
+            Formula::parse("Ca(OH2"),
+            Err(FormulaError::UnclosedBracket {
+                open: '(',
+                position: 2
+            })
+        );
+        assert_eq!(
+            Formula::parse("CaOH)2"),
+            Err(FormulaError::UnmatchedBracket {
+                close: ')',
+                position: 4
+            })
+        );
+        assert_eq!(
+            Formula::parse("H0O"),
+            Err(FormulaError::InvalidCount { position: 1 })
+        );
+        assert_eq!(
+            Formula::parse("RCOOM"),
+            Err(FormulaError::UnknownElement {
+                symbol: "R".to_string(),
+                position: 0
+            })
+        );
+        assert!(matches!(
+            Formula::parse("SO4^x"),
+            Err(FormulaError::InvalidCharge { .. })
+        ));
+    }
+}
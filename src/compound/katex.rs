@@ -0,0 +1,242 @@
+use super::PhysicalState;
+
+/// Converts a plain formula such as `CuSO4·5H2O`, `CH3-C≡CH` or `[-CH2-CH2-]n` into mhchem
+/// markup (`\ce{CuSO4\cdot 5H2O}`, `\ce{CH3-C#CH}`, `\ce{[-CH2-CH2-]_n}`).
+///
+/// Descriptive parentheticals such as `C6H6 (aromatic ring)` are dropped; physical states
+/// like `(aq)` are kept. Values that read as prose rather than a formula are wrapped in
+/// `\text{}` instead.
+pub fn to_mhchem(plain: &str) -> String {
+    let formula = strip_descriptions(plain.trim());
+    if formula.is_empty() || reads_as_prose(&formula) {
+        let text = if formula.is_empty() {
+            plain.trim()
+        } else {
+            &formula
+        };
+        return format!("\\text{{{}}}", text);
+    }
+
+    let characters: Vec<char> = formula.chars().collect();
+    let mut markup = String::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let character = characters[index];
+        match character {
+            '≡' => markup.push('#'),
+            '–' | '—' => markup.push('-'),
+            '·' | '•' => {
+                while markup.ends_with(' ') {
+                    markup.pop();
+                }
+                markup.push_str("\\cdot ");
+                while characters.get(index + 1) == Some(&' ') {
+                    index += 1;
+                }
+            }
+            '^' if characters.get(index + 1) == Some(&'{') => {
+                let close = characters[index..]
+                    .iter()
+                    .position(|c| *c == '}')
+                    .map(|offset| index + offset);
+                match close {
+                    Some(close)
+                        if characters[index + 2..close]
+                            .iter()
+                            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-')) =>
+                    {
+                        markup.push('^');
+                        markup.extend(&characters[index + 2..close]);
+                        index = close;
+                    }
+                    _ => markup.push('^'),
+                }
+            }
+            repeat
+                if repeat.is_ascii_lowercase()
+                    && index > 0
+                    && matches!(characters[index - 1], ')' | ']')
+                    && !characters
+                        .get(index + 1)
+                        .is_some_and(|next| next.is_ascii_lowercase()) =>
+            {
+                markup.push('_');
+                markup.push(repeat);
+            }
+            other => markup.push(other),
+        }
+        index += 1;
+    }
+
+    format!("\\ce{{{}}}", markup)
+}
+
+/// Recovers a plain formula from `\ce{...}` markup so it can be compared with the plain
+/// fields. Returns `None` for `\text{}` or other markup that is not a single `\ce{}` formula.
+pub fn from_mhchem(markup: &str) -> Option<String> {
+    let inner = markup.trim().strip_prefix("\\ce{")?.strip_suffix('}')?;
+
+    let mut depth = 0usize;
+    for character in inner.chars() {
+        match character {
+            '{' => depth += 1,
+            '}' => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+
+    let inner = inner.replace("\\cdot", "·");
+    if inner.contains('\\') {
+        return None;
+    }
+
+    let characters: Vec<char> = inner.chars().collect();
+    let mut plain = String::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        match characters[index] {
+            '#' => plain.push('≡'),
+            '_' if characters.get(index + 1) == Some(&'{') => {
+                let close = characters[index..].iter().position(|c| *c == '}')? + index;
+                plain.extend(&characters[index + 2..close]);
+                index = close;
+            }
+            '_' => {}
+            '·' => {
+                while plain.ends_with(' ') {
+                    plain.pop();
+                }
+                plain.push('·');
+                while characters.get(index + 1) == Some(&' ') {
+                    index += 1;
+                }
+            }
+            other => plain.push(other),
+        }
+        index += 1;
+    }
+
+    Some(plain)
+}
+
+/// Removes free-standing parentheticals that are not physical states, e.g. the
+/// ` (network solid)` in `SiO2 (network solid)`. Groups with a repeat count such as the
+/// `(C3H3N)m` in `(C3H3N)m(C4H6)n` are kept.
+fn strip_descriptions(text: &str) -> String {
+    let characters: Vec<char> = text.chars().collect();
+    let mut kept = String::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let character = characters[index];
+        let after_space = index > 0 && characters[index - 1].is_whitespace();
+        if character == '(' && after_space {
+            let standalone = |close: usize| {
+                characters
+                    .get(close + 1)
+                    .is_none_or(|next| next.is_whitespace() || matches!(next, ',' | ';'))
+            };
+            if let Some(close) = matching_paren(&characters, index).filter(|&c| standalone(c)) {
+                let content: String = characters[index + 1..close].iter().collect();
+                if PhysicalState::from_symbol(&content).is_none() {
+                    while kept.ends_with(char::is_whitespace) {
+                        kept.pop();
+                    }
+                    index = close + 1;
+                    continue;
+                }
+            }
+        }
+        kept.push(character);
+        index += 1;
+    }
+
+    kept.trim().to_string()
+}
+
+fn matching_paren(characters: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, character) in characters.iter().enumerate().skip(open) {
+        match character {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Treats runs of three lowercase letters (no element symbol has more than two letters) and
+/// characters outside formula notation as prose.
+fn reads_as_prose(text: &str) -> bool {
+    let mut lowercase_run = 0;
+    for character in text.chars() {
+        if character.is_ascii_lowercase() {
+            lowercase_run += 1;
+            if lowercase_run >= 3 {
+                return true;
+            }
+        } else {
+            lowercase_run = 0;
+        }
+
+        let formula_character = character.is_ascii_alphanumeric()
+            || character.is_whitespace()
+            || "()[]{}+-=^'*≡–—·•".contains(character);
+        if !formula_character {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_plain_formulas_to_mhchem() {
+        assert_eq!(to_mhchem("CH3-CH2-OH"), "\\ce{CH3-CH2-OH}");
+        assert_eq!(to_mhchem("CH3-C≡CH"), "\\ce{CH3-C#CH}");
+        assert_eq!(to_mhchem("CuSO4·5H2O"), "\\ce{CuSO4\\cdot 5H2O}");
+        assert_eq!(to_mhchem("[Cu(NH3)4]^{2+}"), "\\ce{[Cu(NH3)4]^2+}");
+        assert_eq!(to_mhchem("[-CH2-CH2-]n"), "\\ce{[-CH2-CH2-]_n}");
+        assert_eq!(to_mhchem("HCl (aq)"), "\\ce{HCl (aq)}");
+    }
+
+    #[test]
+    fn drops_descriptions_and_wraps_prose_in_text() {
+        assert_eq!(to_mhchem("SiO2 (network solid)"), "\\ce{SiO2}");
+        assert_eq!(
+            to_mhchem("(C2H4O)n (before crosslinking)"),
+            "\\ce{(C2H4O)_n}"
+        );
+        assert_eq!(
+            to_mhchem("network polymer (no simple formula)"),
+            "\\text{network polymer}"
+        );
+        assert_eq!(to_mhchem("Gal(β1→4)Glc"), "\\text{Gal(β1→4)Glc}");
+    }
+
+    #[test]
+    fn recovers_plain_formulas_from_mhchem() {
+        assert_eq!(
+            from_mhchem("\\ce{KAl(SO4)2\\cdot 12H2O}").as_deref(),
+            Some("KAl(SO4)2·12H2O")
+        );
+        assert_eq!(from_mhchem("\\ce{HC#CH}").as_deref(), Some("HC≡CH"));
+        assert_eq!(from_mhchem("\\ce{(C2H4)_n}").as_deref(), Some("(C2H4)n"));
+        assert_eq!(from_mhchem("\\text{network polymer}"), None);
+        assert_eq!(from_mhchem("\\ce{(C2H3NO)_n + side\\ chains}"), None);
+    }
+}
@@ -15,7 +15,7 @@ fn build_compound(
     molecular_formula: &str,
     smiles: Option<&str>,
 ) -> Compound {
-    Compound {
+    let mut compound = Compound {
         iupac_name: iupac_name.to_string(),
         common_name: common_name.map(str::to_string),
         local_name: local_name.map(str::to_string),
@@ -25,7 +25,15 @@ fn build_compound(
         functional_groups: Vec::new(),
         notes: None,
         smiles: smiles.map(str::to_string),
-    }
+        katex_skeletal_formula: None,
+        katex_molecular_formula: None,
+        state: None,
+        charge: None,
+        hydrate_water: None,
+    };
+    compound.fill_functional_groups();
+    compound.fill_formula_annotations();
+    compound
 }
 
 fn demo_entries() -> Vec<(Compound, Vec<String>)> {
@@ -275,6 +283,7 @@ pub fn demo_catalog() -> Catalog {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::FunctionalGroupKind;
     use rand::rngs::StdRng;
 
     #[test]
@@ -364,4 +373,22 @@ mod tests {
                 .any(|compound| compound.iupac_name == "propane-1,2,3-triol")
         );
     }
+
+    #[test]
+    fn demo_compounds_carry_detected_functional_groups() {
+        let catalog = demo_catalog();
+        let hydroxyl = catalog.compounds_with_functional_group(FunctionalGroupKind::Hydroxyl);
+
+        assert!(
+            hydroxyl
+                .iter()
+                .any(|compound| compound.iupac_name == "ethanol")
+        );
+        assert!(hydroxyl.iter().all(|compound| {
+            compound
+                .functional_groups
+                .iter()
+                .any(|group| group.name_en == "Hydroxyl")
+        }));
+    }
 }
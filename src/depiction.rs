@@ -0,0 +1,366 @@
+mod full;
+pub mod layout;
+
+pub use full::full_structure_svg;
+pub use layout::{Layout, Point};
+
+use std::fmt::Write;
+
+use crate::smiles::{Atom, Bond, BondOrder, Molecule};
+
+/// Pixels per bond length.
+const BOND_LENGTH: f64 = 40.0;
+const FONT_SIZE: f64 = 14.0;
+const STROKE_WIDTH: f64 = 1.6;
+const PADDING: f64 = 20.0;
+/// Approximate glyph width relative to the font size, used to centre element symbols.
+const GLYPH_WIDTH: f64 = 0.6;
+/// Distance, in bond lengths, by which bonds stop short of a text label.
+const LABEL_CLEARANCE: f64 = 0.3;
+/// Separation between the strokes of a multiple bond, in bond lengths.
+const MULTIPLE_BOND_OFFSET: f64 = 0.16;
+
+/// Draws the skeletal formula of `molecule` as a standalone SVG document.
+///
+/// Carbon atoms are drawn as bare vertices unless they are isolated, charged, or carry an
+/// isotope label; other elements are written with their attached hydrogens. Aromatic rings
+/// are drawn with an inscribed circle. Strokes and text use `currentColor`, so the drawing
+/// follows the surrounding CSS colour (and thus light and dark themes).
+pub fn skeletal_svg(molecule: &Molecule) -> String {
+    let layout = Layout::compute(molecule);
+    let labels: Vec<Option<AtomLabel>> = molecule
+        .atoms()
+        .iter()
+        .enumerate()
+        .map(|(index, atom)| {
+            let shown = atom.element != "C"
+                || molecule.degree(index) == 0
+                || atom.charge != 0
+                || atom.isotope.is_some();
+            shown.then(|| AtomLabel::for_atom(atom))
+        })
+        .collect();
+
+    let mut canvas = SvgCanvas::new(layout.bounds());
+    let rings = molecule.rings();
+
+    for bond in molecule.bonds() {
+        draw_bond(
+            &mut canvas,
+            (layout.position(bond.from), labels[bond.from].is_some()),
+            (layout.position(bond.to), labels[bond.to].is_some()),
+            bond.order,
+            ring_centre_for(&rings, layout.positions(), bond),
+        );
+    }
+    draw_aromatic_rings(&mut canvas, molecule, &rings, layout.positions());
+
+    for (index, label) in labels.iter().enumerate() {
+        if let Some(label) = label {
+            canvas.label(layout.position(index), label);
+        }
+    }
+
+    canvas.finish()
+}
+
+/// Text drawn at an atom position: element symbol, attached hydrogens, and charge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AtomLabel {
+    isotope: Option<u16>,
+    element: String,
+    hydrogens: u32,
+    charge: i32,
+}
+
+impl AtomLabel {
+    fn for_atom(atom: &Atom) -> Self {
+        Self {
+            isotope: atom.isotope,
+            element: atom.element.clone(),
+            hydrogens: atom.hydrogens,
+            charge: atom.charge,
+        }
+    }
+
+    fn charge_text(&self) -> Option<String> {
+        let sign = if self.charge > 0 { "+" } else { "−" };
+        match self.charge.unsigned_abs() {
+            0 => None,
+            1 => Some(sign.to_string()),
+            magnitude => Some(format!("{}{}", magnitude, sign)),
+        }
+    }
+}
+
+fn ring_centre(points: &[Point]) -> Point {
+    let sum = points.iter().fold(Point::ORIGIN, |sum, point| sum + *point);
+    sum * (1.0 / points.len() as f64)
+}
+
+/// Centre of the first ring containing `bond`, used to draw ring double bonds inwards.
+fn ring_centre_for(rings: &[Vec<usize>], positions: &[Point], bond: &Bond) -> Option<Point> {
+    rings
+        .iter()
+        .find(|ring| ring.contains(&bond.from) && ring.contains(&bond.to))
+        .map(|ring| {
+            let points: Vec<Point> = ring.iter().map(|&atom| positions[atom]).collect();
+            ring_centre(&points)
+        })
+}
+
+/// Draws an inscribed circle in every ring whose bonds are all aromatic.
+fn draw_aromatic_rings(
+    canvas: &mut SvgCanvas,
+    molecule: &Molecule,
+    rings: &[Vec<usize>],
+    positions: &[Point],
+) {
+    for ring in rings {
+        let aromatic = ring.iter().enumerate().all(|(index, &atom)| {
+            let next = ring[(index + 1) % ring.len()];
+            molecule
+                .bond_between(atom, next)
+                .is_some_and(|bond| bond.order == BondOrder::Aromatic)
+        });
+        if aromatic {
+            let points: Vec<Point> = ring.iter().map(|&atom| positions[atom]).collect();
+            let centre = ring_centre(&points);
+            let inner = points
+                .iter()
+                .map(|point| point.distance(centre))
+                .fold(f64::INFINITY, f64::min);
+            canvas.circle(centre, inner * 0.6);
+        }
+    }
+}
+
+/// Draws a bond between two points. Ends flagged as labelled stop short of the label text;
+/// double bonds inside a ring keep one stroke on the ring outline and put a shorter second
+/// stroke towards `ring_centre`.
+fn draw_bond(
+    canvas: &mut SvgCanvas,
+    (mut start, start_labelled): (Point, bool),
+    (mut end, end_labelled): (Point, bool),
+    order: BondOrder,
+    ring_centre: Option<Point>,
+) {
+    let Some(direction) = (end - start).normalized() else {
+        return;
+    };
+    if start_labelled {
+        start = start + direction * LABEL_CLEARANCE;
+    }
+    if end_labelled {
+        end = end - direction * LABEL_CLEARANCE;
+    }
+    let normal = direction.perpendicular();
+
+    match order {
+        BondOrder::Single | BondOrder::Aromatic => canvas.line(start, end),
+        BondOrder::Double => match ring_centre {
+            Some(centre) => {
+                let midpoint = (start + end) * 0.5;
+                let inward = if normal.dot(centre - midpoint) >= 0.0 {
+                    normal
+                } else {
+                    normal * -1.0
+                };
+                let shift = inward * MULTIPLE_BOND_OFFSET;
+                let trim = direction * MULTIPLE_BOND_OFFSET;
+                canvas.line(start, end);
+                canvas.line(start + shift + trim, end + shift - trim);
+            }
+            None => {
+                let shift = normal * (MULTIPLE_BOND_OFFSET / 2.0);
+                canvas.line(start + shift, end + shift);
+                canvas.line(start - shift, end - shift);
+            }
+        },
+        BondOrder::Triple | BondOrder::Quadruple => {
+            let strokes = if order == BondOrder::Triple { 3 } else { 4 };
+            for stroke in 0..strokes {
+                let offset = (stroke as f64 - (strokes - 1) as f64 / 2.0) * MULTIPLE_BOND_OFFSET;
+                let shift = normal * offset;
+                canvas.line(start + shift, end + shift);
+            }
+        }
+    }
+}
+
+/// Accumulates SVG elements in layout coordinates and writes them out in pixels.
+struct SvgCanvas {
+    min: Point,
+    width: f64,
+    height: f64,
+    strokes: String,
+    texts: String,
+}
+
+impl SvgCanvas {
+    fn new((min, max): (Point, Point)) -> Self {
+        Self {
+            min,
+            width: (max.x - min.x) * BOND_LENGTH + 2.0 * PADDING,
+            height: (max.y - min.y) * BOND_LENGTH + 2.0 * PADDING,
+            strokes: String::new(),
+            texts: String::new(),
+        }
+    }
+
+    /// Converts a layout point into SVG pixels, flipping the y axis.
+    fn project(&self, point: Point) -> (f64, f64) {
+        (
+            (point.x - self.min.x) * BOND_LENGTH + PADDING,
+            self.height - ((point.y - self.min.y) * BOND_LENGTH + PADDING),
+        )
+    }
+
+    fn line(&mut self, from: Point, to: Point) {
+        let (x1, y1) = self.project(from);
+        let (x2, y2) = self.project(to);
+        let _ = writeln!(
+            self.strokes,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+            number(x1),
+            number(y1),
+            number(x2),
+            number(y2)
+        );
+    }
+
+    fn circle(&mut self, centre: Point, radius: f64) {
+        let (cx, cy) = self.project(centre);
+        let _ = writeln!(
+            self.strokes,
+            r#"<circle cx="{}" cy="{}" r="{}"/>"#,
+            number(cx),
+            number(cy),
+            number(radius * BOND_LENGTH)
+        );
+    }
+
+    fn label(&mut self, at: Point, label: &AtomLabel) {
+        let (x, y) = self.project(at);
+        // Centre the element symbol on the atom; hydrogens and charge trail to the right.
+        let x = x - label.element.chars().count() as f64 * FONT_SIZE * GLYPH_WIDTH / 2.0;
+
+        let mut content = String::new();
+        if let Some(isotope) = label.isotope {
+            let _ = write!(
+                content,
+                r#"<tspan baseline-shift="super" font-size="70%">{}</tspan>"#,
+                isotope
+            );
+        }
+        content.push_str(&label.element);
+        if label.hydrogens > 0 {
+            content.push('H');
+            if label.hydrogens > 1 {
+                let _ = write!(
+                    content,
+                    r#"<tspan baseline-shift="sub" font-size="70%">{}</tspan>"#,
+                    label.hydrogens
+                );
+            }
+        }
+        if let Some(charge) = label.charge_text() {
+            let _ = write!(
+                content,
+                r#"<tspan baseline-shift="super" font-size="70%">{}</tspan>"#,
+                charge
+            );
+        }
+
+        let _ = writeln!(
+            self.texts,
+            r#"<text x="{}" y="{}">{}</text>"#,
+            number(x),
+            number(y),
+            content
+        );
+    }
+
+    fn finish(self) -> String {
+        let width = number(self.width);
+        let height = number(self.height);
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}" height="{height}">"#,
+                "\n",
+                r#"<g stroke="currentColor" stroke-width="{stroke}" stroke-linecap="round" fill="none">"#,
+                "\n{strokes}</g>\n",
+                r#"<g fill="currentColor" font-family="sans-serif" font-size="{font}" dominant-baseline="central">"#,
+                "\n{texts}</g>\n</svg>\n"
+            ),
+            width = width,
+            height = height,
+            stroke = STROKE_WIDTH,
+            font = FONT_SIZE,
+            strokes = self.strokes,
+            texts = self.texts,
+        )
+    }
+}
+
+/// Formats a coordinate with two decimals, avoiding `-0.00`.
+fn number(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn svg(smiles: &str) -> String {
+        skeletal_svg(&Molecule::parse(smiles).expect("SMILES should parse"))
+    }
+
+    #[test]
+    fn draws_one_stroke_per_bond_order() {
+        let ethanol = svg("CCO");
+        assert_eq!(ethanol.matches("<line").count(), 2);
+        assert_eq!(ethanol.matches("<text").count(), 1);
+        assert!(ethanol.contains(">OH</text>"));
+
+        let acetic_acid = svg("CC(=O)O");
+        assert_eq!(acetic_acid.matches("<line").count(), 4);
+
+        let propyne = svg("CC#C");
+        assert_eq!(propyne.matches("<line").count(), 4);
+    }
+
+    #[test]
+    fn marks_aromatic_rings_with_a_circle() {
+        let benzene = svg("c1ccccc1");
+        assert_eq!(benzene.matches("<line").count(), 6);
+        assert_eq!(benzene.matches("<circle").count(), 1);
+        assert!(!benzene.contains("<text"));
+
+        let cyclohexene = svg("C1=CCCCC1");
+        assert_eq!(cyclohexene.matches("<circle").count(), 0);
+        assert_eq!(cyclohexene.matches("<line").count(), 7);
+    }
+
+    #[test]
+    fn labels_ions_with_charges() {
+        let salt = svg("[Na+].[Cl-]");
+        assert!(salt.contains(r#"Na<tspan baseline-shift="super" font-size="70%">+</tspan>"#));
+        assert!(salt.contains(r#"Cl<tspan baseline-shift="super" font-size="70%">−</tspan>"#));
+
+        let methane = svg("C");
+        assert!(methane.contains(r#"CH<tspan baseline-shift="sub" font-size="70%">4</tspan>"#));
+    }
+
+    #[test]
+    fn formats_numbers_compactly() {
+        assert_eq!(number(-0.001), "0");
+        assert_eq!(number(12.0), "12");
+        assert_eq!(number(7.126), "7.13");
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::layout::{COMPONENT_GAP, Layout, Point, bounds_of, spread_in_widest_gap};
+use super::{AtomLabel, SvgCanvas, draw_aromatic_rings, draw_bond, ring_centre_for};
+use crate::smiles::{BondOrder, Molecule};
+
+/// Longest bond, in grid cells, tried when a branch would collide with earlier atoms.
+const MAX_STRETCH: i32 = 8;
+
+/// Draws the expanded structural formula of `molecule` as a standalone SVG document, with
+/// every atom (hydrogens included) written out and every bond drawn as a line.
+///
+/// Acyclic parts follow the right-angled style of Japanese high-school materials: the main
+/// chain runs left to right, branches and hydrogens sit above and below it, and bonds are
+/// stretched where branches would otherwise collide. Components containing rings keep the
+/// ring polygons of [`Layout`] and receive their hydrogens radially.
+pub fn full_structure_svg(molecule: &Molecule) -> String {
+    let drawing = FullLayout::compute(molecule);
+    let rings = molecule.rings();
+
+    let all_points = drawing
+        .atoms
+        .iter()
+        .copied()
+        .chain(drawing.hydrogens.iter().map(|(_, point)| *point));
+    let mut canvas = SvgCanvas::new(bounds_of(all_points));
+
+    for bond in molecule.bonds() {
+        draw_bond(
+            &mut canvas,
+            (drawing.atoms[bond.from], true),
+            (drawing.atoms[bond.to], true),
+            bond.order,
+            ring_centre_for(&rings, &drawing.atoms, bond),
+        );
+    }
+    for &(parent, point) in &drawing.hydrogens {
+        draw_bond(
+            &mut canvas,
+            (drawing.atoms[parent], true),
+            (point, true),
+            BondOrder::Single,
+            None,
+        );
+    }
+    draw_aromatic_rings(&mut canvas, molecule, &rings, &drawing.atoms);
+
+    for (index, atom) in molecule.atoms().iter().enumerate() {
+        let label = AtomLabel {
+            hydrogens: 0,
+            ..AtomLabel::for_atom(atom)
+        };
+        canvas.label(drawing.atoms[index], &label);
+    }
+    let hydrogen = AtomLabel {
+        isotope: None,
+        element: "H".to_string(),
+        hydrogens: 0,
+        charge: 0,
+    };
+    for &(_, point) in &drawing.hydrogens {
+        canvas.label(point, &hydrogen);
+    }
+
+    canvas.finish()
+}
+
+/// Positions of heavy atoms (indexed like [`Molecule::atoms`]) and of the hydrogens expanded
+/// from their hydrogen counts, each paired with the atom it is bonded to.
+struct FullLayout {
+    atoms: Vec<Point>,
+    hydrogens: Vec<(usize, Point)>,
+}
+
+impl FullLayout {
+    fn compute(molecule: &Molecule) -> Self {
+        let ring_atoms: HashSet<usize> = molecule.rings().into_iter().flatten().collect();
+        let mut ring_layout = None;
+        let mut atoms = vec![Point::ORIGIN; molecule.atoms().len()];
+        let mut hydrogens = Vec::new();
+        let mut cursor = 0.0;
+
+        for component in molecule.components() {
+            let mut local_hydrogens = Vec::new();
+
+            if component.iter().any(|atom| ring_atoms.contains(atom)) {
+                let layout = ring_layout.get_or_insert_with(|| Layout::compute(molecule));
+                for &atom in &component {
+                    atoms[atom] = layout.position(atom);
+                }
+                for &atom in &component {
+                    let placed: Vec<f64> = molecule
+                        .neighbors(atom)
+                        .filter_map(|(neighbor, _)| (atoms[neighbor] - atoms[atom]).normalized())
+                        .map(Point::angle)
+                        .collect();
+                    let count = molecule.atoms()[atom].hydrogens as usize;
+                    for angle in spread_in_widest_gap(&placed, count) {
+                        local_hydrogens.push((atom, atoms[atom] + Point::from_angle(angle)));
+                    }
+                }
+            } else {
+                let grid = GridTree { molecule };
+                let placement = grid.place(grid.root(&component), None, None);
+                for (atom, cell) in placement.atoms {
+                    atoms[atom] = cell.point();
+                }
+                for (parent, cell) in placement.hydrogens {
+                    local_hydrogens.push((parent, cell.point()));
+                }
+            }
+
+            let points = component
+                .iter()
+                .map(|&atom| atoms[atom])
+                .chain(local_hydrogens.iter().map(|(_, point)| *point));
+            let (min, max) = bounds_of(points);
+            let offset = Point::new(cursor - min.x, -(min.y + max.y) / 2.0);
+            for &atom in &component {
+                atoms[atom] = atoms[atom] + offset;
+            }
+            hydrogens.extend(
+                local_hydrogens
+                    .into_iter()
+                    .map(|(parent, point)| (parent, point + offset)),
+            );
+            cursor += max.x - min.x + COMPONENT_GAP;
+        }
+
+        Self { atoms, hydrogens }
+    }
+}
+
+/// Integer grid offset; `y` grows upwards like [`Point`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Cell {
+    x: i32,
+    y: i32,
+}
+
+impl Cell {
+    const RIGHT: Cell = Cell { x: 1, y: 0 };
+    const UP: Cell = Cell { x: 0, y: 1 };
+    const DOWN: Cell = Cell { x: 0, y: -1 };
+    const LEFT: Cell = Cell { x: -1, y: 0 };
+
+    fn offset(self, by: Cell, times: i32) -> Cell {
+        Cell {
+            x: self.x + by.x * times,
+            y: self.y + by.y * times,
+        }
+    }
+
+    fn turned_left(self) -> Cell {
+        Cell {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    fn turned_right(self) -> Cell {
+        Cell {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    fn point(self) -> Point {
+        Point::new(f64::from(self.x), f64::from(self.y))
+    }
+
+    /// Bond directions in order of preference: straight on, then the two right-angle
+    /// turns, then diagonals for atoms with more than four substituents.
+    fn directions(incoming: Option<Cell>) -> Vec<Cell> {
+        match incoming {
+            None => vec![
+                Cell::RIGHT,
+                Cell::UP,
+                Cell::DOWN,
+                Cell::LEFT,
+                Cell { x: 1, y: 1 },
+                Cell { x: 1, y: -1 },
+                Cell { x: -1, y: 1 },
+                Cell { x: -1, y: -1 },
+            ],
+            Some(straight) => {
+                let (left, right) = (straight.turned_left(), straight.turned_right());
+                vec![
+                    straight,
+                    left,
+                    right,
+                    straight.offset(left, 1),
+                    straight.offset(right, 1),
+                    left.offset(straight, -1),
+                    right.offset(straight, -1),
+                ]
+            }
+        }
+    }
+}
+
+/// Grid placement of a subtree relative to its root at the origin.
+#[derive(Default)]
+struct GridPlacement {
+    atoms: Vec<(usize, Cell)>,
+    hydrogens: Vec<(usize, Cell)>,
+    /// Cells covered by atoms or by the middle of stretched bonds.
+    occupied: HashSet<Cell>,
+}
+
+struct GridTree<'a> {
+    molecule: &'a Molecule,
+}
+
+impl GridTree<'_> {
+    /// Lower-indexed end of the component's longest chain, so the main chain is drawn
+    /// straight and in SMILES order.
+    fn root(&self, component: &[usize]) -> usize {
+        let first_end = self.farthest_from(component[0]);
+        let second_end = self.farthest_from(first_end);
+        first_end.min(second_end)
+    }
+
+    fn farthest_from(&self, start: usize) -> usize {
+        let mut distance = vec![usize::MAX; self.molecule.atoms().len()];
+        distance[start] = 0;
+        let mut queue = VecDeque::from([start]);
+        let mut farthest = start;
+
+        while let Some(atom) = queue.pop_front() {
+            let further = distance[atom] > distance[farthest];
+            if further || (distance[atom] == distance[farthest] && atom < farthest) {
+                farthest = atom;
+            }
+            for (neighbor, _) in self.molecule.neighbors(atom) {
+                if distance[neighbor] == usize::MAX {
+                    distance[neighbor] = distance[atom] + 1;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        farthest
+    }
+
+    /// Height and size (hydrogens included) of the subtree below `atom`.
+    fn extent(&self, atom: usize, parent: Option<usize>) -> (usize, u32) {
+        let mut height = 0;
+        let mut size = 1 + self.molecule.atoms()[atom].hydrogens;
+        for (child, _) in self.molecule.neighbors(atom) {
+            if Some(child) != parent {
+                let (child_height, child_size) = self.extent(child, Some(atom));
+                height = height.max(child_height + 1);
+                size += child_size;
+            }
+        }
+        (height, size)
+    }
+
+    fn place(&self, atom: usize, parent: Option<usize>, incoming: Option<Cell>) -> GridPlacement {
+        let mut placement = GridPlacement::default();
+        let origin = Cell { x: 0, y: 0 };
+        placement.atoms.push((atom, origin));
+        placement.occupied.insert(origin);
+
+        // The tallest branch continues straight on so the longest chain stays on one line.
+        let mut children: Vec<(usize, (usize, u32))> = self
+            .molecule
+            .neighbors(atom)
+            .map(|(child, _)| child)
+            .filter(|&child| Some(child) != parent)
+            .map(|child| (child, self.extent(child, Some(atom))))
+            .collect();
+        children.sort_by(|(left, left_extent), (right, right_extent)| {
+            right_extent.cmp(left_extent).then(left.cmp(right))
+        });
+
+        let directions = Cell::directions(incoming);
+        let mut slots = directions.iter().copied();
+        let child_directions: Vec<(usize, Cell)> = children
+            .iter()
+            .map(|&(child, _)| (child, slots.next().unwrap_or(directions[0])))
+            .collect();
+
+        for _ in 0..self.molecule.atoms()[atom].hydrogens {
+            let direction = slots.next().unwrap_or(directions[0]);
+            let cell = origin.offset(direction, 1);
+            placement.hydrogens.push((atom, cell));
+            placement.occupied.insert(cell);
+        }
+
+        for (child, direction) in child_directions {
+            let subtree = self.place(child, Some(atom), Some(direction));
+            let stretch = (1..MAX_STRETCH)
+                .find(|&length| {
+                    let path_clear = (1..length)
+                        .all(|step| !placement.occupied.contains(&origin.offset(direction, step)));
+                    path_clear
+                        && subtree.occupied.iter().all(|cell| {
+                            let shifted = Cell {
+                                x: cell.x + direction.x * length,
+                                y: cell.y + direction.y * length,
+                            };
+                            !placement.occupied.contains(&shifted)
+                        })
+                })
+                .unwrap_or(MAX_STRETCH);
+
+            for step in 1..stretch {
+                placement.occupied.insert(origin.offset(direction, step));
+            }
+            let shift = |cell: Cell| cell.offset(direction, stretch);
+            placement.atoms.extend(
+                subtree
+                    .atoms
+                    .into_iter()
+                    .map(|(atom, cell)| (atom, shift(cell))),
+            );
+            placement.hydrogens.extend(
+                subtree
+                    .hydrogens
+                    .into_iter()
+                    .map(|(atom, cell)| (atom, shift(cell))),
+            );
+            placement
+                .occupied
+                .extend(subtree.occupied.into_iter().map(shift));
+        }
+
+        placement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(smiles: &str) -> (Molecule, FullLayout) {
+        let molecule = Molecule::parse(smiles).expect("SMILES should parse");
+        let layout = FullLayout::compute(&molecule);
+        (molecule, layout)
+    }
+
+    fn all_points(layout: &FullLayout) -> Vec<Point> {
+        layout
+            .atoms
+            .iter()
+            .copied()
+            .chain(layout.hydrogens.iter().map(|(_, point)| *point))
+            .collect()
+    }
+
+    #[test]
+    fn expands_every_hydrogen() {
+        let (molecule, ethanol) = layout("CCO");
+        let total: u32 = molecule.atoms().iter().map(|atom| atom.hydrogens).sum();
+        assert_eq!(ethanol.hydrogens.len(), total as usize);
+    }
+
+    #[test]
+    fn draws_chains_on_a_right_angled_grid() {
+        let (_, ethanol) = layout("CCO");
+        let [c1, c2, o] = [0, 1, 2].map(|atom| ethanol.atoms[atom]);
+        assert_eq!(c2 - c1, Point::new(1.0, 0.0));
+        assert_eq!(o - c2, Point::new(1.0, 0.0));
+
+        for (parent, point) in &ethanol.hydrogens {
+            let bond = *point - ethanol.atoms[*parent];
+            assert!(
+                (bond.x == 0.0 || bond.y == 0.0) && (bond.length() - 1.0).abs() < 1e-9,
+                "hydrogen bonds follow the grid"
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_atoms_apart() {
+        for smiles in [
+            "CCO",
+            "CC(C)C",
+            "CC(C)(C)C",
+            "CC(C)C(C)C",
+            "CC(C)CC(C)(C)C",
+            "CC(=O)O",
+            "OCC(O)CO",
+            "C#C",
+            "c1ccccc1",
+            "Cc1ccccc1",
+            "C1CCCCC1",
+            "[NH4+].[Cl-]",
+            "OP(=O)(O)O",
+        ] {
+            let (_, drawing) = layout(smiles);
+            let points = all_points(&drawing);
+            for (index, left) in points.iter().enumerate() {
+                for right in &points[index + 1..] {
+                    assert!(left.distance(*right) > 0.5, "{smiles}: atoms overlap");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,630 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+use crate::smiles::{BondOrder, Molecule};
+
+/// Gap left between disconnected components such as the ions of a salt, in bond lengths.
+pub(crate) const COMPONENT_GAP: f64 = 1.5;
+
+/// Point in layout space. One unit is one bond length and `y` grows upwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Unit vector pointing at `angle` radians counter-clockwise from the positive x axis.
+    pub fn from_angle(angle: f64) -> Self {
+        Self::new(angle.cos(), angle.sin())
+    }
+
+    pub fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    pub fn angle(self) -> f64 {
+        self.y.atan2(self.x)
+    }
+
+    pub fn distance(self, other: Point) -> f64 {
+        (self - other).length()
+    }
+
+    /// Returns the vector scaled to unit length, or `None` for the zero vector.
+    pub fn normalized(self) -> Option<Point> {
+        let length = self.length();
+        (length > 1e-9).then(|| self * (1.0 / length))
+    }
+
+    /// Rotates the vector by a quarter turn counter-clockwise.
+    pub fn perpendicular(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    pub fn dot(self, other: Point) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn rotated(self, angle: f64) -> Point {
+        let (sin, cos) = angle.sin_cos();
+        Point::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: f64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// Deterministic 2D coordinates for every atom of a [`Molecule`].
+///
+/// Ring systems are drawn as regular polygons fused along shared bonds, chains zig-zag at
+/// 120°, and disconnected components are placed left to right in SMILES order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    positions: Vec<Point>,
+}
+
+impl Layout {
+    pub fn compute(molecule: &Molecule) -> Self {
+        LayoutBuilder::new(molecule).build()
+    }
+
+    pub fn positions(&self) -> &[Point] {
+        &self.positions
+    }
+
+    pub fn position(&self, atom: usize) -> Point {
+        self.positions[atom]
+    }
+
+    /// Lower-left and upper-right corners of the box enclosing every atom.
+    pub fn bounds(&self) -> (Point, Point) {
+        bounds_of(self.positions.iter().copied())
+    }
+}
+
+pub(crate) fn bounds_of(points: impl Iterator<Item = Point>) -> (Point, Point) {
+    let mut min = Point::new(f64::INFINITY, f64::INFINITY);
+    let mut max = Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+    for point in points {
+        min = Point::new(min.x.min(point.x), min.y.min(point.y));
+        max = Point::new(max.x.max(point.x), max.y.max(point.y));
+    }
+
+    if min.x > max.x {
+        (Point::ORIGIN, Point::ORIGIN)
+    } else {
+        (min, max)
+    }
+}
+
+fn centroid(points: &[Point]) -> Point {
+    let sum = points.iter().fold(Point::ORIGIN, |sum, point| sum + *point);
+    sum * (1.0 / points.len().max(1) as f64)
+}
+
+/// Wraps an angle into `(-π, π]`.
+fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+struct LayoutBuilder<'a> {
+    molecule: &'a Molecule,
+    rings: Vec<Vec<usize>>,
+    /// Ring indices grouped into fused or spiro-linked systems.
+    systems: Vec<Vec<usize>>,
+    system_of: Vec<Option<usize>>,
+    positions: Vec<Option<Point>>,
+    /// Side (+1 or -1) to which the chain bent when reaching each atom, used to zig-zag.
+    turns: Vec<f64>,
+}
+
+impl<'a> LayoutBuilder<'a> {
+    fn new(molecule: &'a Molecule) -> Self {
+        let atom_count = molecule.atoms().len();
+        let rings = molecule.rings();
+        let systems = ring_systems(&rings);
+
+        let mut system_of = vec![None; atom_count];
+        for (system_index, system) in systems.iter().enumerate() {
+            for &ring_index in system {
+                for &atom in &rings[ring_index] {
+                    system_of[atom] = Some(system_index);
+                }
+            }
+        }
+
+        Self {
+            molecule,
+            rings,
+            systems,
+            system_of,
+            positions: vec![None; atom_count],
+            turns: vec![0.0; atom_count],
+        }
+    }
+
+    fn build(mut self) -> Layout {
+        let mut cursor = 0.0;
+
+        for component in self.molecule.components() {
+            self.place_component(component[0]);
+
+            let (min, max) = bounds_of(component.iter().filter_map(|&atom| self.positions[atom]));
+            let offset = Point::new(cursor - min.x, -(min.y + max.y) / 2.0);
+            for &atom in &component {
+                if let Some(position) = self.positions[atom] {
+                    self.positions[atom] = Some(position + offset);
+                }
+            }
+            cursor += max.x - min.x + COMPONENT_GAP;
+        }
+
+        Layout {
+            positions: self
+                .positions
+                .into_iter()
+                .map(|position| position.unwrap_or(Point::ORIGIN))
+                .collect(),
+        }
+    }
+
+    fn place_component(&mut self, root: usize) {
+        let mut queue = VecDeque::new();
+
+        match self.system_of[root] {
+            Some(system) => {
+                let local = self.layout_system(system);
+                let anchor = local[&root];
+                for (&atom, &point) in &local {
+                    self.positions[atom] = Some(point - anchor);
+                    queue.push_back(atom);
+                }
+            }
+            None => {
+                self.positions[root] = Some(Point::ORIGIN);
+                queue.push_back(root);
+            }
+        }
+
+        while let Some(atom) = queue.pop_front() {
+            let mut children: Vec<usize> = self
+                .molecule
+                .neighbors(atom)
+                .map(|(neighbor, _)| neighbor)
+                .filter(|&neighbor| self.positions[neighbor].is_none())
+                .collect();
+            children.sort_unstable();
+            children.dedup();
+            if children.is_empty() {
+                continue;
+            }
+
+            let origin = self.positions[atom].expect("queued atoms are placed");
+            for (child, (angle, turn)) in children
+                .iter()
+                .copied()
+                .zip(self.child_directions(atom, children.len()))
+            {
+                if self.positions[child].is_some() {
+                    continue;
+                }
+                let position = origin + Point::from_angle(angle);
+
+                match self.system_of[child] {
+                    Some(system) => {
+                        let local = self.layout_system(system);
+                        let anchor = local[&child];
+                        let local_centroid = centroid(&local.values().copied().collect::<Vec<_>>());
+                        let rotation = match (local_centroid - anchor).normalized() {
+                            Some(direction) => angle - direction.angle(),
+                            None => 0.0,
+                        };
+                        for (&member, &point) in &local {
+                            if self.positions[member].is_none() {
+                                self.positions[member] =
+                                    Some(position + (point - anchor).rotated(rotation));
+                                queue.push_back(member);
+                            }
+                        }
+                    }
+                    None => {
+                        self.positions[child] = Some(position);
+                        self.turns[child] = turn;
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Directions (angle, turn) for `count` new neighbours of `atom`, spread through the
+    /// widest gap between the neighbours already placed.
+    fn child_directions(&self, atom: usize, count: usize) -> Vec<(f64, f64)> {
+        let origin = self.positions[atom].expect("atom is placed");
+        let placed: Vec<f64> = self
+            .molecule
+            .neighbors(atom)
+            .filter_map(|(neighbor, _)| self.positions[neighbor])
+            .filter_map(|position| (position - origin).normalized())
+            .map(|direction| direction.angle().rem_euclid(2.0 * PI))
+            .collect();
+
+        if placed.is_empty() {
+            // Start chains rising to the right; further neighbours spread evenly.
+            return (0..count)
+                .map(|index| (PI / 6.0 - 2.0 * PI * index as f64 / count as f64, 1.0))
+                .collect();
+        }
+
+        if placed.len() == 1 && count == 1 {
+            let incoming = placed[0] + PI;
+            if self.is_linear(atom) {
+                return vec![(incoming, self.turns[atom])];
+            }
+            let bends_back = self.turns[atom] > 0.0;
+            let turn = if bends_back != self.continues_cis(atom) {
+                -1.0
+            } else {
+                1.0
+            };
+            return vec![(incoming + turn * PI / 3.0, turn)];
+        }
+
+        let incoming = placed[0] + PI;
+        spread_in_widest_gap(&placed, count)
+            .into_iter()
+            .map(|angle| {
+                let turn = if wrap_angle(angle - incoming) >= 0.0 {
+                    1.0
+                } else {
+                    -1.0
+                };
+                (angle, turn)
+            })
+            .collect()
+    }
+
+    /// Returns `true` when `atom` ends a double bond whose written configuration puts the
+    /// chain atoms on both sides of it cis, so the chain must bend the same way twice.
+    fn continues_cis(&self, atom: usize) -> bool {
+        let placed = |neighbor: &usize| self.positions[*neighbor].is_some();
+        let Some((previous, bond)) = self
+            .molecule
+            .neighbors(atom)
+            .find(|(neighbor, _)| placed(neighbor))
+        else {
+            return false;
+        };
+        if bond.order != BondOrder::Double {
+            return false;
+        }
+
+        let before = self
+            .molecule
+            .neighbors(previous)
+            .map(|(neighbor, _)| neighbor)
+            .find(|neighbor| *neighbor != atom && placed(neighbor));
+        let after = self
+            .molecule
+            .neighbors(atom)
+            .map(|(neighbor, _)| neighbor)
+            .find(|neighbor| !placed(neighbor));
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                crate::stereo::same_side(self.molecule, (previous, before), (atom, after))
+                    == Some(true)
+            }
+            _ => false,
+        }
+    }
+
+    /// Atoms with a triple bond or two double bonds (allenes, CO2) keep their chain straight.
+    fn is_linear(&self, atom: usize) -> bool {
+        let orders: Vec<BondOrder> = self
+            .molecule
+            .neighbors(atom)
+            .map(|(_, bond)| bond.order)
+            .collect();
+
+        orders.contains(&BondOrder::Triple)
+            || orders
+                .iter()
+                .filter(|order| **order == BondOrder::Double)
+                .count()
+                >= 2
+    }
+
+    /// Places the rings of one system as regular polygons in a local frame.
+    fn layout_system(&self, system: usize) -> BTreeMap<usize, Point> {
+        let ring_indices = &self.systems[system];
+        let mut local = BTreeMap::new();
+
+        let first = &self.rings[ring_indices[0]];
+        let radius = circumradius(first.len());
+        for (index, &atom) in first.iter().enumerate() {
+            let angle = PI / 2.0 + 2.0 * PI * index as f64 / first.len() as f64;
+            local.insert(atom, Point::from_angle(angle) * radius);
+        }
+
+        let mut pending: Vec<usize> = ring_indices[1..].to_vec();
+        while !pending.is_empty() {
+            let fused = pending
+                .iter()
+                .position(|&ring| shared_edge(&self.rings[ring], &local).is_some());
+            let spiro = || {
+                pending
+                    .iter()
+                    .position(|&ring| self.rings[ring].iter().any(|atom| local.contains_key(atom)))
+            };
+
+            let Some(position) = fused.or_else(spiro) else {
+                break;
+            };
+            let ring = &self.rings[pending.remove(position)];
+            let placed_centroid = centroid(&local.values().copied().collect::<Vec<_>>());
+
+            match shared_edge(ring, &local) {
+                Some(first_index) => {
+                    place_fused_ring(ring, first_index, placed_centroid, &mut local);
+                }
+                None => place_spiro_ring(ring, placed_centroid, &mut local),
+            }
+        }
+
+        local
+    }
+}
+
+/// Spreads `count` directions evenly through the widest angular gap between the `placed`
+/// directions (radians). Without placed directions the whole circle is used.
+pub(crate) fn spread_in_widest_gap(placed: &[f64], count: usize) -> Vec<f64> {
+    let mut sorted: Vec<f64> = placed
+        .iter()
+        .map(|angle| angle.rem_euclid(2.0 * PI))
+        .collect();
+    sorted.sort_by(f64::total_cmp);
+
+    let (mut gap_start, mut gap) = match (sorted.first(), sorted.last()) {
+        (Some(first), Some(last)) => (*last, first + 2.0 * PI - last),
+        _ => (0.0, 2.0 * PI),
+    };
+    for pair in sorted.windows(2) {
+        if pair[1] - pair[0] > gap + 1e-9 {
+            gap_start = pair[0];
+            gap = pair[1] - pair[0];
+        }
+    }
+
+    (0..count)
+        .map(|index| gap_start + gap * (index + 1) as f64 / (count + 1) as f64)
+        .collect()
+}
+
+fn circumradius(size: usize) -> f64 {
+    1.0 / (2.0 * (PI / size as f64).sin())
+}
+
+fn apothem(size: usize) -> f64 {
+    1.0 / (2.0 * (PI / size as f64).tan())
+}
+
+/// Returns the ring atoms in ring order, starting at index `start`.
+fn ring_order(ring: &[usize], start: usize) -> Vec<usize> {
+    (0..ring.len())
+        .map(|step| ring[(start + step) % ring.len()])
+        .collect()
+}
+
+/// Finds two consecutive ring atoms that are already placed and returns the index of the
+/// first of them.
+fn shared_edge(ring: &[usize], local: &BTreeMap<usize, Point>) -> Option<usize> {
+    (0..ring.len()).find(|&index| {
+        local.contains_key(&ring[index]) && local.contains_key(&ring[(index + 1) % ring.len()])
+    })
+}
+
+fn place_fused_ring(
+    ring: &[usize],
+    first_index: usize,
+    placed_centroid: Point,
+    local: &mut BTreeMap<usize, Point>,
+) {
+    let order = ring_order(ring, first_index);
+    let (first, second) = (local[&order[0]], local[&order[1]]);
+    let midpoint = (first + second) * 0.5;
+    let mut normal = (second - first)
+        .perpendicular()
+        .normalized()
+        .unwrap_or(Point::new(0.0, 1.0));
+    if normal.dot(midpoint - placed_centroid) < 0.0 {
+        normal = normal * -1.0;
+    }
+
+    let centre = midpoint + normal * apothem(ring.len());
+    let start = (first - centre).angle();
+    let step = wrap_angle((second - centre).angle() - start);
+
+    for (index, &atom) in order.iter().enumerate() {
+        local.entry(atom).or_insert_with(|| {
+            centre + Point::from_angle(start + step * index as f64) * circumradius(ring.len())
+        });
+    }
+}
+
+fn place_spiro_ring(ring: &[usize], placed_centroid: Point, local: &mut BTreeMap<usize, Point>) {
+    let Some(start_index) = ring.iter().position(|atom| local.contains_key(atom)) else {
+        return;
+    };
+    let order = ring_order(ring, start_index);
+    let shared = local[&order[0]];
+    let radius = circumradius(ring.len());
+    let direction = (shared - placed_centroid)
+        .normalized()
+        .unwrap_or(Point::new(1.0, 0.0));
+    let centre = shared + direction * radius;
+    let start = (shared - centre).angle();
+
+    for (index, &atom) in order.iter().enumerate() {
+        local.entry(atom).or_insert_with(|| {
+            centre + Point::from_angle(start + 2.0 * PI * index as f64 / ring.len() as f64) * radius
+        });
+    }
+}
+
+/// Groups rings that share at least one atom.
+fn ring_systems(rings: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut systems: Vec<Vec<usize>> = Vec::new();
+    let mut system_of_ring: Vec<Option<usize>> = vec![None; rings.len()];
+
+    for index in 0..rings.len() {
+        if system_of_ring[index].is_some() {
+            continue;
+        }
+
+        let system_index = systems.len();
+        let mut members = vec![index];
+        system_of_ring[index] = Some(system_index);
+        let mut cursor = 0;
+        while cursor < members.len() {
+            let current = members[cursor];
+            cursor += 1;
+            for (other, ring) in rings.iter().enumerate() {
+                if system_of_ring[other].is_none()
+                    && ring.iter().any(|atom| rings[current].contains(atom))
+                {
+                    system_of_ring[other] = Some(system_index);
+                    members.push(other);
+                }
+            }
+        }
+        systems.push(members);
+    }
+
+    systems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(smiles: &str) -> (Molecule, Layout) {
+        let molecule = Molecule::parse(smiles).expect("SMILES should parse");
+        let layout = Layout::compute(&molecule);
+        (molecule, layout)
+    }
+
+    fn assert_clean(smiles: &str) {
+        let (molecule, layout) = layout(smiles);
+
+        for bond in molecule.bonds() {
+            let length = layout
+                .position(bond.from)
+                .distance(layout.position(bond.to));
+            assert!(
+                (length - 1.0).abs() < 1e-6,
+                "{smiles}: bond {}-{} has length {length}",
+                bond.from,
+                bond.to
+            );
+        }
+
+        let positions = layout.positions();
+        for (index, left) in positions.iter().enumerate() {
+            for right in &positions[index + 1..] {
+                assert!(left.distance(*right) > 0.5, "{smiles}: atoms overlap");
+            }
+        }
+    }
+
+    #[test]
+    fn lays_out_common_structures_without_overlaps() {
+        for smiles in [
+            "CCO",
+            "CC(C)C",
+            "CC(C)(C)C",
+            "CCCCCCCC",
+            "CC(=O)O",
+            "C#CC",
+            "c1ccccc1",
+            "Cc1ccccc1",
+            "c1ccc2ccccc2c1",
+            "c1ccc2cc3ccccc3cc2c1",
+            "C1CCC2(CC1)CCCC2",
+            "OC(=O)c1ccccc1C(=O)O",
+            "[Na+].[Cl-]",
+        ] {
+            assert_clean(smiles);
+        }
+    }
+
+    #[test]
+    fn chains_zig_zag_and_triple_bonds_stay_straight() {
+        let (_, butane) = layout("CCCC");
+        let [a, b, c, d] = [0, 1, 2, 3].map(|atom| butane.position(atom));
+        assert!((a.distance(c) - 3f64.sqrt()).abs() < 1e-6);
+        assert!((b.distance(d) - 3f64.sqrt()).abs() < 1e-6);
+        assert!((a.y - c.y).abs() < 1e-6);
+
+        let (_, propyne) = layout("CC#C");
+        let ends = propyne.position(0).distance(propyne.position(2));
+        assert!((ends - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cis_double_bonds_bend_the_chain_back() {
+        let (_, cis) = layout("C/C=C\\C");
+        assert!((cis.position(0).distance(cis.position(3)) - 2.0).abs() < 1e-6);
+
+        let (_, trans) = layout("C/C=C/C");
+        assert!((trans.position(0).distance(trans.position(3)) - 7f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn separates_disconnected_components() {
+        let (_, salt) = layout("[Na+].[Cl-]");
+        assert!((salt.position(1).x - salt.position(0).x - COMPONENT_GAP).abs() < 1e-6);
+        assert!((salt.position(0).y - salt.position(1).y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn layout_is_deterministic() {
+        let (_, first) = layout("CC(C)c1ccc(O)cc1");
+        let (_, second) = layout("CC(C)c1ccc(O)cc1");
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,663 @@
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+use crate::compound::FunctionalGroup;
+use crate::smiles::{BondOrder, Molecule};
+
+/// Functional groups from `dataset/organic_functional_groups.json` that can be recognised
+/// in a SMILES graph. Alkanes are the absence of any of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FunctionalGroupKind {
+    Alkene,
+    Alkyne,
+    AromaticRing,
+    Halide,
+    Hydroxyl,
+    PhenolicHydroxyl,
+    Ether,
+    Aldehyde,
+    Ketone,
+    Carboxyl,
+    Ester,
+    Amine,
+    Amide,
+    Nitrile,
+    Nitro,
+    Thiol,
+}
+
+#[derive(serde::Deserialize)]
+struct FunctionalGroupDataset {
+    functional_groups: Vec<DatasetGroup>,
+}
+
+#[derive(serde::Deserialize)]
+struct DatasetGroup {
+    label_en: String,
+    group_en: String,
+    group_ja: String,
+    pattern: String,
+}
+
+/// Entries of `dataset/organic_functional_groups.json`, in its order.
+static FUNCTIONAL_GROUP_DATASET: LazyLock<Vec<DatasetGroup>> = LazyLock::new(|| {
+    let dataset: FunctionalGroupDataset =
+        serde_json::from_str(include_str!("../dataset/organic_functional_groups.json"))
+            .expect("the functional group dataset parses");
+    dataset.functional_groups
+});
+
+impl FunctionalGroupKind {
+    /// Every kind, in the order of the functional group dataset.
+    pub const ALL: [Self; 16] = [
+        Self::Alkene,
+        Self::Alkyne,
+        Self::AromaticRing,
+        Self::Halide,
+        Self::Hydroxyl,
+        Self::PhenolicHydroxyl,
+        Self::Ether,
+        Self::Aldehyde,
+        Self::Ketone,
+        Self::Carboxyl,
+        Self::Ester,
+        Self::Amine,
+        Self::Amide,
+        Self::Nitrile,
+        Self::Nitro,
+        Self::Thiol,
+    ];
+
+    /// The dataset entry's `label_en`, which names the compound class the group defines.
+    fn dataset_label(self) -> &'static str {
+        match self {
+            Self::Alkene => "Alkenes",
+            Self::Alkyne => "Alkynes",
+            Self::AromaticRing => "Arenes",
+            Self::Halide => "Halogenoalkanes",
+            Self::Hydroxyl => "Alcohols",
+            Self::PhenolicHydroxyl => "Phenols",
+            Self::Ether => "Ethers",
+            Self::Aldehyde => "Aldehydes",
+            Self::Ketone => "Ketones",
+            Self::Carboxyl => "Carboxylic acids",
+            Self::Ester => "Esters",
+            Self::Amine => "Amines",
+            Self::Amide => "Amides",
+            Self::Nitrile => "Nitriles",
+            Self::Nitro => "Nitro compounds",
+            Self::Thiol => "Thiols",
+        }
+    }
+
+    fn dataset_entry(self) -> &'static DatasetGroup {
+        let label = self.dataset_label();
+        FUNCTIONAL_GROUP_DATASET
+            .iter()
+            .find(|group| group.label_en == label)
+            .unwrap_or_else(|| panic!("the functional group dataset lists {label}"))
+    }
+
+    /// English group name as written in the dataset's `group_en`.
+    pub fn name_en(self) -> &'static str {
+        &self.dataset_entry().group_en
+    }
+
+    /// Japanese group name as written in the dataset's `group_ja`.
+    pub fn name_ja(self) -> &'static str {
+        &self.dataset_entry().group_ja
+    }
+
+    /// Condensed pattern as written in the dataset's `pattern`.
+    pub fn pattern(self) -> &'static str {
+        &self.dataset_entry().pattern
+    }
+
+    /// Catalog-style metadata for this group.
+    pub fn to_functional_group(self) -> FunctionalGroup {
+        FunctionalGroup {
+            name_en: self.name_en().to_string(),
+            name_ja: self.name_ja().to_string(),
+            pattern: self.pattern().to_string(),
+        }
+    }
+
+    /// Recognises hand-entered catalog labels such as `"Ester linkage"`, `"Ketone (carbonyl)"`
+    /// or `"Peptide bonds"`. Labels for inorganic ions and structural notes return `None`.
+    pub fn from_label(label: &str) -> Option<Self> {
+        if let Some(kind) = Self::ALL.into_iter().find(|kind| kind.name_en() == label) {
+            return Some(kind);
+        }
+
+        let mut base = String::new();
+        let mut depth = 0usize;
+        for character in label.chars() {
+            match character {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ if depth == 0 => base.extend(character.to_lowercase()),
+                _ => {}
+            }
+        }
+        let base = base.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let kind = match base.as_str() {
+            "alkene" | "alkene double bond" | "alkene units" | "carbon–carbon double bond" => {
+                Self::Alkene
+            }
+            "alkyne" | "alkyne triple bond" | "carbon–carbon triple bond" => Self::Alkyne,
+            "aromatic ring" | "phenyl" | "phenyl group" => Self::AromaticRing,
+            "halo" | "c–f bonds" | "c–cl bonds" | "c–br bonds" | "c–i bonds" => {
+                Self::Halide
+            }
+            "hydroxyl" => Self::Hydroxyl,
+            "phenolic hydroxyl" => Self::PhenolicHydroxyl,
+            "ether" | "ether linkage" | "acetal" | "acetal bonds" | "glycosidic bond" => {
+                Self::Ether
+            }
+            "aldehyde" => Self::Aldehyde,
+            "ketone" | "carbonyl" => Self::Ketone,
+            "carboxyl" => Self::Carboxyl,
+            "ester" | "ester linkage" => Self::Ester,
+            "amino" | "amine" => Self::Amine,
+            "amide" | "amide linkage" | "peptide bond" | "peptide bonds" => Self::Amide,
+            "nitrile" | "cyano" => Self::Nitrile,
+            "nitro" => Self::Nitro,
+            "thiol" => Self::Thiol,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Structural patterns for the group; a match of any one counts.
+    fn patterns(self) -> &'static [Pattern] {
+        match self {
+            Self::Alkene => &[ALKENE],
+            Self::Alkyne => &[ALKYNE],
+            Self::AromaticRing => &[AROMATIC_SIX_RING, AROMATIC_FIVE_RING, KEKULE_BENZENE],
+            Self::Halide => &[HALIDE],
+            Self::Hydroxyl => &[HYDROXYL],
+            Self::PhenolicHydroxyl => &[PHENOLIC_HYDROXYL],
+            Self::Ether => &[ETHER],
+            Self::Aldehyde => &[ALDEHYDE],
+            Self::Ketone => &[KETONE],
+            Self::Carboxyl => &[CARBOXYL],
+            Self::Ester => &[ESTER],
+            Self::Amine => &[AMINE],
+            Self::Amide => &[AMIDE],
+            Self::Nitrile => &[NITRILE],
+            Self::Nitro => &[NITRO],
+            Self::Thiol => &[THIOL],
+        }
+    }
+
+    /// Groups whose matches hide a match of this group on the same atoms, so the OH of a
+    /// carboxyl is not also reported as an alcohol.
+    fn suppressed_by(self) -> &'static [Self] {
+        match self {
+            Self::Alkene => &[Self::AromaticRing],
+            Self::Hydroxyl => &[Self::Carboxyl],
+            Self::Ether => &[Self::Ester],
+            Self::Aldehyde => &[Self::Carboxyl, Self::Ester, Self::Amide],
+            Self::Amine => &[Self::Amide, Self::Nitro],
+            _ => &[],
+        }
+    }
+}
+
+/// One occurrence of a functional group. `atoms` holds the sorted indices of the atoms
+/// that make up the group itself (e.g. the carbonyl carbon and both oxygens of a carboxyl).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupMatch {
+    pub kind: FunctionalGroupKind,
+    pub atoms: Vec<usize>,
+}
+
+/// Finds every functional group occurrence in `molecule`, ordered by kind and then by atom
+/// indices. Symmetric matches of the same atoms are reported once.
+///
+/// Only organic components are searched, i.e. those with a carbon that is neither bonded
+/// solely to oxygen nor a bare cyanide carbon, so carbonate, hydrogencarbonate and cyanide
+/// ligands are not reported as carboxyl or cyano groups.
+pub fn find_functional_groups(molecule: &Molecule) -> Vec<GroupMatch> {
+    let organic = organic_atoms(molecule);
+    let raw: Vec<(FunctionalGroupKind, BTreeSet<Vec<usize>>)> = FunctionalGroupKind::ALL
+        .into_iter()
+        .map(|kind| {
+            let cores = kind
+                .patterns()
+                .iter()
+                .flat_map(|pattern| pattern.core_matches(molecule))
+                .filter(|core| core.iter().all(|&atom| organic[atom]))
+                .collect();
+            (kind, cores)
+        })
+        .collect();
+
+    let cores_of = |kind: FunctionalGroupKind| {
+        raw.iter()
+            .find(|(candidate, _)| *candidate == kind)
+            .map(|(_, cores)| cores)
+    };
+
+    raw.iter()
+        .flat_map(|(kind, cores)| {
+            cores
+                .iter()
+                .filter(|core| {
+                    !kind.suppressed_by().iter().any(|suppressor| {
+                        cores_of(*suppressor).is_some_and(|others| {
+                            others
+                                .iter()
+                                .any(|other| core.iter().all(|atom| other.contains(atom)))
+                        })
+                    })
+                })
+                .map(|core| GroupMatch {
+                    kind: *kind,
+                    atoms: core.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Distinct functional group kinds present in `molecule`, in [`FunctionalGroupKind::ALL`]
+/// order.
+pub fn detect_functional_groups(molecule: &Molecule) -> Vec<FunctionalGroupKind> {
+    let kinds: BTreeSet<FunctionalGroupKind> = find_functional_groups(molecule)
+        .into_iter()
+        .map(|found| found.kind)
+        .collect();
+    kinds.into_iter().collect()
+}
+
+fn organic_atoms(molecule: &Molecule) -> Vec<bool> {
+    let mut organic = vec![false; molecule.atoms().len()];
+
+    for component in molecule.components() {
+        let has_organic_carbon = component.iter().any(|&atom| {
+            let carbon = &molecule.atoms()[atom];
+            if carbon.element != "C" {
+                return false;
+            }
+            let oxygen_only = carbon.hydrogens == 0
+                && molecule
+                    .neighbors(atom)
+                    .all(|(neighbor, _)| molecule.atoms()[neighbor].element == "O");
+            let cyanide = carbon.hydrogens == 0
+                && molecule.neighbors(atom).any(|(neighbor, bond)| {
+                    bond.order == BondOrder::Triple && molecule.atoms()[neighbor].element == "N"
+                })
+                && molecule
+                    .neighbors(atom)
+                    .all(|(neighbor, _)| molecule.atoms()[neighbor].element != "C");
+            !oxygen_only && !cyanide
+        });
+        if has_organic_carbon {
+            for atom in component {
+                organic[atom] = true;
+            }
+        }
+    }
+
+    organic
+}
+
+/// Constraints on one pattern atom. An empty `elements` list accepts any element.
+#[derive(Debug, Clone, Copy)]
+struct AtomPattern {
+    elements: &'static [&'static str],
+    aromatic: Option<bool>,
+    min_hydrogens: u32,
+    max_hydrogens: u32,
+    charge: Option<i32>,
+}
+
+impl AtomPattern {
+    const fn new(elements: &'static [&'static str]) -> Self {
+        Self {
+            elements,
+            aromatic: None,
+            min_hydrogens: 0,
+            max_hydrogens: u32::MAX,
+            charge: None,
+        }
+    }
+
+    const fn aromatic(mut self, aromatic: bool) -> Self {
+        self.aromatic = Some(aromatic);
+        self
+    }
+
+    const fn hydrogens(mut self, min: u32, max: u32) -> Self {
+        self.min_hydrogens = min;
+        self.max_hydrogens = max;
+        self
+    }
+
+    const fn charge(mut self, charge: i32) -> Self {
+        self.charge = Some(charge);
+        self
+    }
+
+    fn accepts(&self, molecule: &Molecule, index: usize) -> bool {
+        let atom = &molecule.atoms()[index];
+        (self.elements.is_empty() || self.elements.contains(&atom.element.as_str()))
+            && self
+                .aromatic
+                .is_none_or(|aromatic| atom.aromatic == aromatic)
+            && (self.min_hydrogens..=self.max_hydrogens).contains(&atom.hydrogens)
+            && self.charge.is_none_or(|charge| atom.charge == charge)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BondPattern {
+    Exactly(BondOrder),
+    Any,
+}
+
+impl BondPattern {
+    fn accepts(self, order: BondOrder) -> bool {
+        match self {
+            Self::Exactly(expected) => expected == order,
+            Self::Any => true,
+        }
+    }
+}
+
+const SINGLE: BondPattern = BondPattern::Exactly(BondOrder::Single);
+const DOUBLE: BondPattern = BondPattern::Exactly(BondOrder::Double);
+const TRIPLE: BondPattern = BondPattern::Exactly(BondOrder::Triple);
+const AROMATIC: BondPattern = BondPattern::Exactly(BondOrder::Aromatic);
+
+/// Small connected graph matched against a molecule. Every pattern atom after the first
+/// must be bonded to an earlier one, which keeps the backtracking search anchored.
+#[derive(Debug, Clone, Copy)]
+struct Pattern {
+    atoms: &'static [AtomPattern],
+    bonds: &'static [(usize, usize, BondPattern)],
+    /// Pattern atoms that belong to the group itself rather than its surroundings.
+    core: &'static [usize],
+}
+
+impl Pattern {
+    /// Sorted core atom indices of every match.
+    fn core_matches(&self, molecule: &Molecule) -> BTreeSet<Vec<usize>> {
+        let mut found = BTreeSet::new();
+        let mut mapping = Vec::with_capacity(self.atoms.len());
+        self.extend(molecule, &mut mapping, &mut found);
+        found
+    }
+
+    fn extend(
+        &self,
+        molecule: &Molecule,
+        mapping: &mut Vec<usize>,
+        found: &mut BTreeSet<Vec<usize>>,
+    ) {
+        let next = mapping.len();
+        if next == self.atoms.len() {
+            let mut core: Vec<usize> = self.core.iter().map(|&atom| mapping[atom]).collect();
+            core.sort_unstable();
+            found.insert(core);
+            return;
+        }
+
+        for candidate in 0..molecule.atoms().len() {
+            if mapping.contains(&candidate) || !self.atoms[next].accepts(molecule, candidate) {
+                continue;
+            }
+
+            let bonded = self.bonds.iter().all(|&(from, to, bond)| {
+                let other = match (from == next, to == next) {
+                    (true, false) if to < next => mapping[to],
+                    (false, true) if from < next => mapping[from],
+                    _ => return true,
+                };
+                molecule
+                    .bond_between(candidate, other)
+                    .is_some_and(|found| bond.accepts(found.order))
+            });
+
+            if bonded {
+                mapping.push(candidate);
+                self.extend(molecule, mapping, found);
+                mapping.pop();
+            }
+        }
+    }
+}
+
+const CARBON: AtomPattern = AtomPattern::new(&["C"]);
+const ALIPHATIC_CARBON: AtomPattern = CARBON.aromatic(false);
+const OXYGEN: AtomPattern = AtomPattern::new(&["O"]);
+const ANY_AROMATIC: AtomPattern = AtomPattern::new(&[]).aromatic(true);
+
+const ALKENE: Pattern = Pattern {
+    atoms: &[ALIPHATIC_CARBON, ALIPHATIC_CARBON],
+    bonds: &[(0, 1, DOUBLE)],
+    core: &[0, 1],
+};
+
+const ALKYNE: Pattern = Pattern {
+    atoms: &[CARBON, CARBON],
+    bonds: &[(0, 1, TRIPLE)],
+    core: &[0, 1],
+};
+
+const AROMATIC_SIX_RING: Pattern = Pattern {
+    atoms: &[ANY_AROMATIC; 6],
+    bonds: &[
+        (0, 1, AROMATIC),
+        (1, 2, AROMATIC),
+        (2, 3, AROMATIC),
+        (3, 4, AROMATIC),
+        (4, 5, AROMATIC),
+        (5, 0, AROMATIC),
+    ],
+    core: &[0, 1, 2, 3, 4, 5],
+};
+
+const AROMATIC_FIVE_RING: Pattern = Pattern {
+    atoms: &[ANY_AROMATIC; 5],
+    bonds: &[
+        (0, 1, AROMATIC),
+        (1, 2, AROMATIC),
+        (2, 3, AROMATIC),
+        (3, 4, AROMATIC),
+        (4, 0, AROMATIC),
+    ],
+    core: &[0, 1, 2, 3, 4],
+};
+
+/// Benzene written with alternating bonds, as in `C1=CC=CC=C1`.
+const KEKULE_BENZENE: Pattern = Pattern {
+    atoms: &[ALIPHATIC_CARBON; 6],
+    bonds: &[
+        (0, 1, DOUBLE),
+        (1, 2, SINGLE),
+        (2, 3, DOUBLE),
+        (3, 4, SINGLE),
+        (4, 5, DOUBLE),
+        (5, 0, SINGLE),
+    ],
+    core: &[0, 1, 2, 3, 4, 5],
+};
+
+const HALIDE: Pattern = Pattern {
+    atoms: &[CARBON, AtomPattern::new(&["F", "Cl", "Br", "I"])],
+    bonds: &[(0, 1, SINGLE)],
+    core: &[1],
+};
+
+const HYDROXYL: Pattern = Pattern {
+    atoms: &[ALIPHATIC_CARBON, OXYGEN.hydrogens(1, 1).charge(0)],
+    bonds: &[(0, 1, SINGLE)],
+    core: &[1],
+};
+
+const PHENOLIC_HYDROXYL: Pattern = Pattern {
+    atoms: &[CARBON.aromatic(true), OXYGEN.hydrogens(1, 1).charge(0)],
+    bonds: &[(0, 1, SINGLE)],
+    core: &[1],
+};
+
+const ETHER: Pattern = Pattern {
+    atoms: &[
+        OXYGEN.aromatic(false).hydrogens(0, 0).charge(0),
+        CARBON,
+        CARBON,
+    ],
+    bonds: &[(0, 1, SINGLE), (0, 2, SINGLE)],
+    core: &[0],
+};
+
+const ALDEHYDE: Pattern = Pattern {
+    atoms: &[ALIPHATIC_CARBON.hydrogens(1, 2), OXYGEN],
+    bonds: &[(0, 1, DOUBLE)],
+    core: &[0, 1],
+};
+
+const KETONE: Pattern = Pattern {
+    atoms: &[ALIPHATIC_CARBON, OXYGEN, CARBON, CARBON],
+    bonds: &[(0, 1, DOUBLE), (0, 2, SINGLE), (0, 3, SINGLE)],
+    core: &[0, 1],
+};
+
+const CARBOXYL: Pattern = Pattern {
+    atoms: &[ALIPHATIC_CARBON, OXYGEN, OXYGEN.hydrogens(1, 1).charge(0)],
+    bonds: &[(0, 1, DOUBLE), (0, 2, SINGLE)],
+    core: &[0, 1, 2],
+};
+
+const ESTER: Pattern = Pattern {
+    atoms: &[
+        ALIPHATIC_CARBON,
+        OXYGEN,
+        OXYGEN.hydrogens(0, 0).charge(0),
+        CARBON,
+    ],
+    bonds: &[(0, 1, DOUBLE), (0, 2, SINGLE), (2, 3, SINGLE)],
+    core: &[0, 1, 2],
+};
+
+const AMINE: Pattern = Pattern {
+    atoms: &[AtomPattern::new(&["N"]).aromatic(false).charge(0), CARBON],
+    bonds: &[(0, 1, SINGLE)],
+    core: &[0],
+};
+
+const AMIDE: Pattern = Pattern {
+    atoms: &[
+        ALIPHATIC_CARBON,
+        OXYGEN,
+        AtomPattern::new(&["N"]).aromatic(false),
+    ],
+    bonds: &[(0, 1, DOUBLE), (0, 2, SINGLE)],
+    core: &[0, 1, 2],
+};
+
+const NITRILE: Pattern = Pattern {
+    atoms: &[CARBON, AtomPattern::new(&["N"])],
+    bonds: &[(0, 1, TRIPLE)],
+    core: &[0, 1],
+};
+
+/// Matches both `N(=O)=O` and the charge-separated `[N+](=O)[O-]`.
+const NITRO: Pattern = Pattern {
+    atoms: &[AtomPattern::new(&["N"]), OXYGEN, OXYGEN, CARBON],
+    bonds: &[(0, 1, DOUBLE), (0, 2, BondPattern::Any), (0, 3, SINGLE)],
+    core: &[0, 1, 2],
+};
+
+const THIOL: Pattern = Pattern {
+    atoms: &[AtomPattern::new(&["S"]).hydrogens(1, 1), CARBON],
+    bonds: &[(0, 1, SINGLE)],
+    core: &[0],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FunctionalGroupKind::*;
+
+    fn detect(smiles: &str) -> Vec<FunctionalGroupKind> {
+        detect_functional_groups(&Molecule::parse(smiles).expect("SMILES should parse"))
+    }
+
+    #[test]
+    fn detects_oxygen_groups_without_double_counting() {
+        assert_eq!(detect("CCO"), vec![Hydroxyl]);
+        assert_eq!(detect("CC(=O)O"), vec![Carboxyl]);
+        assert_eq!(detect("CC(=O)OC"), vec![Ester]);
+        assert_eq!(detect("CCOCC"), vec![Ether]);
+        assert_eq!(detect("CC=O"), vec![Aldehyde]);
+        assert_eq!(detect("CC(C)=O"), vec![Ketone]);
+        assert_eq!(detect("Oc1ccccc1"), vec![AromaticRing, PhenolicHydroxyl]);
+        assert_eq!(
+            detect("OC(=O)c1ccccc1OC(C)=O"),
+            vec![AromaticRing, Carboxyl, Ester]
+        );
+    }
+
+    #[test]
+    fn detects_nitrogen_sulfur_and_hydrocarbon_groups() {
+        assert_eq!(detect("CC(N)C(=O)O"), vec![Carboxyl, Amine]);
+        assert_eq!(detect("CC(N)=O"), vec![Amide]);
+        assert_eq!(detect("C[N+](=O)[O-]"), vec![Nitro]);
+        assert_eq!(detect("CC#N"), vec![Nitrile]);
+        assert_eq!(detect("CCS"), vec![Thiol]);
+        assert_eq!(detect("C=CCl"), vec![Alkene, Halide]);
+        assert_eq!(detect("C#C"), vec![Alkyne]);
+        assert_eq!(detect("C1=CC=CC=C1"), vec![AromaticRing]);
+        assert!(detect("CCCC").is_empty());
+        assert!(detect("[Na+].[Cl-]").is_empty());
+        assert!(detect("OC(=O)O").is_empty());
+        assert!(detect("[K+].[C-]#N").is_empty());
+        assert_eq!(detect("NC(N)=O"), vec![Amide]);
+    }
+
+    #[test]
+    fn reports_each_occurrence_once() {
+        let oxalic_acid = Molecule::parse("OC(=O)C(=O)O").expect("SMILES should parse");
+        let found = find_functional_groups(&oxalic_acid);
+
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|group| group.kind == Carboxyl));
+
+        let naphthalene = Molecule::parse("c1ccc2ccccc2c1").expect("SMILES should parse");
+        assert_eq!(find_functional_groups(&naphthalene).len(), 2);
+    }
+
+    #[test]
+    fn recognises_catalog_labels() {
+        assert_eq!(
+            FunctionalGroupKind::from_label("Ester linkage"),
+            Some(Ester)
+        );
+        assert_eq!(
+            FunctionalGroupKind::from_label("Ketone (carbonyl)"),
+            Some(Ketone)
+        );
+        assert_eq!(
+            FunctionalGroupKind::from_label("Peptide (amide) bond"),
+            Some(Amide)
+        );
+        assert_eq!(
+            FunctionalGroupKind::from_label("Carbon–carbon double bond"),
+            Some(Alkene)
+        );
+        assert_eq!(FunctionalGroupKind::from_label("Sulfate"), None);
+    }
+
+    #[test]
+    fn names_come_from_the_dataset() {
+        for kind in FunctionalGroupKind::ALL {
+            assert!(!kind.name_en().is_empty() && !kind.pattern().is_empty());
+        }
+        assert_eq!(Ketone.name_en(), "Carbonyl (ketone)");
+        assert_eq!(Ketone.name_ja(), "カルボニル基（ケトン）");
+        assert_eq!(Nitrile.pattern(), "–C≡N");
+    }
+}
@@ -0,0 +1,228 @@
+use std::sync::LazyLock;
+
+use crate::compound::Formula;
+
+/// Halogens that a general formula's `X` stands for.
+const HALOGENS: [&str; 4] = ["F", "Cl", "Br", "I"];
+
+/// Homologous series from `dataset/organic_homologous_series.json`, with its general
+/// formula broken down so molecular formulas can be checked against it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomologousSeries {
+    pub name_en: String,
+    pub name_ja: String,
+    /// General formula without the dataset's remarks, e.g. `"CnH2n-2"`.
+    pub general_formula: String,
+    pub katex_general_formula: String,
+    /// Carbons written after the `CnH…` part, as in the CONH2 of `CnH2n+1CONH2`.
+    extra_carbons: u32,
+    /// Hydrogens beyond 2n, including those written after the `CnH…` part.
+    hydrogen_offset: i32,
+    /// Other atoms per molecule; `"X"` is any one halogen.
+    heteroatoms: Vec<(String, u32)>,
+    /// Smallest n of the series, e.g. 3 for ketones.
+    min_n: u32,
+}
+
+impl HomologousSeries {
+    /// The `n` of the general formula that gives `formula`, or `None` when `formula` is not
+    /// a neutral member of the series (`C3H4` is the alkyne with n = 3).
+    pub fn carbon_number(&self, formula: &Formula) -> Option<u32> {
+        if formula.charge() != 0 {
+            return None;
+        }
+
+        let n = formula.count("C").checked_sub(self.extra_carbons)?;
+        if n < self.min_n
+            || i64::from(formula.count("H")) != 2 * i64::from(n) + i64::from(self.hydrogen_offset)
+        {
+            return None;
+        }
+
+        let mut rest = formula.elements().clone();
+        rest.remove("C");
+        rest.remove("H");
+        for (symbol, count) in &self.heteroatoms {
+            let symbol = if symbol == "X" {
+                HALOGENS
+                    .into_iter()
+                    .find(|halogen| rest.contains_key(*halogen))?
+            } else {
+                symbol.as_str()
+            };
+            if rest.remove(symbol) != Some(*count) {
+                return None;
+            }
+        }
+
+        rest.is_empty().then_some(n)
+    }
+
+    /// Whether `formula` fits the general formula with an `n` the series allows.
+    pub fn fits(&self, formula: &Formula) -> bool {
+        self.carbon_number(formula).is_some()
+    }
+
+    /// Lowercase singular name looked for in `Compound::series_general_formula`, e.g.
+    /// `"carboxylic acid"`.
+    pub(crate) fn keyword(&self) -> String {
+        let name = self.name_en.split(" (").next().unwrap_or(&self.name_en);
+        name.strip_suffix('s').unwrap_or(name).to_lowercase()
+    }
+}
+
+impl HomologousSeries {
+    /// Reads the parts of the general formula around `CnH2n`: a hydrogen offset such as
+    /// the `+1` of `CnH2n+1OH`, then the atoms written after it. The dataset lists the
+    /// first members of each series, so the smallest example gives `min_n`; `None` when a
+    /// formula does not parse or an example does not fit the general formula.
+    fn from_dataset(entry: DatasetSeries) -> Option<Self> {
+        let general_formula = entry.general_formula.rsplit(": ").next()?;
+        let general_formula = general_formula.split(" (").next()?.to_string();
+        let katex_general_formula = entry.katex_general_formula.split(r" \text").next()?;
+
+        let tail = general_formula.strip_prefix("CnH2n")?;
+        let digits = tail
+            .char_indices()
+            .skip(1)
+            .find(|(_, character)| !character.is_ascii_digit())
+            .map_or(tail.len(), |(index, _)| index);
+        let (offset, written) = match tail.chars().next() {
+            Some('+' | '-') => (tail[..digits].parse::<i32>().ok()?, &tail[digits..]),
+            _ => (0, tail),
+        };
+
+        let mut heteroatoms: Vec<(String, u32)> = Vec::new();
+        let halogens = written.matches('X').count() as u32;
+        let written = written.replace('X', "");
+        let (extra_carbons, extra_hydrogens) = if written.is_empty() {
+            (0, 0)
+        } else {
+            let formula = Formula::parse(&written).ok()?;
+            heteroatoms.extend(
+                formula
+                    .elements()
+                    .iter()
+                    .filter(|(symbol, _)| !matches!(symbol.as_str(), "C" | "H"))
+                    .map(|(symbol, count)| (symbol.clone(), *count)),
+            );
+            (formula.count("C"), formula.count("H"))
+        };
+        if halogens > 0 {
+            heteroatoms.push(("X".to_string(), halogens));
+        }
+
+        let mut series = Self {
+            name_en: entry.name_en,
+            name_ja: entry.name_ja,
+            general_formula,
+            katex_general_formula: katex_general_formula.to_string(),
+            extra_carbons,
+            hydrogen_offset: offset + i32::try_from(extra_hydrogens).ok()?,
+            heteroatoms,
+            min_n: 0,
+        };
+        series.min_n = entry
+            .example_members
+            .iter()
+            .map(|member| {
+                let formula = Formula::parse(&member.formula).ok()?;
+                series.carbon_number(&formula)
+            })
+            .min()
+            .flatten()?;
+        Some(series)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct SeriesDataset {
+    series: Vec<DatasetSeries>,
+}
+
+#[derive(serde::Deserialize)]
+struct DatasetSeries {
+    name_en: String,
+    name_ja: String,
+    general_formula: String,
+    katex_general_formula: String,
+    example_members: Vec<ExampleMember>,
+}
+
+#[derive(serde::Deserialize)]
+struct ExampleMember {
+    formula: String,
+}
+
+/// Series of `dataset/organic_homologous_series.json`, in its order. Several share a
+/// general formula (alkenes and cycloalkanes, aldehydes and ketones, carboxylic acids and
+/// esters), so a formula alone does not pick the series.
+pub static HOMOLOGOUS_SERIES: LazyLock<Vec<HomologousSeries>> = LazyLock::new(|| {
+    let dataset: SeriesDataset =
+        serde_json::from_str(include_str!("../dataset/organic_homologous_series.json"))
+            .expect("the homologous series dataset parses");
+    dataset
+        .series
+        .into_iter()
+        .map(|entry| {
+            let name = entry.name_en.clone();
+            HomologousSeries::from_dataset(entry)
+                .unwrap_or_else(|| panic!("{name}: general formula and members should parse"))
+        })
+        .collect()
+});
+
+/// Series with the general formula `general_formula` as written in [`HOMOLOGOUS_SERIES`],
+/// e.g. both alkenes and cycloalkanes for `"CnH2n"`.
+pub fn series_with_general_formula(general_formula: &str) -> Vec<&'static HomologousSeries> {
+    HOMOLOGOUS_SERIES
+        .iter()
+        .filter(|series| series.general_formula == general_formula)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formula(text: &str) -> Formula {
+        Formula::parse(text).expect("formula should parse")
+    }
+
+    fn named(name: &str) -> &'static HomologousSeries {
+        HOMOLOGOUS_SERIES
+            .iter()
+            .find(|series| series.name_en == name)
+            .expect("series exists")
+    }
+
+    #[test]
+    fn matches_formulas_to_general_formulas() {
+        assert_eq!(named("Alkynes").carbon_number(&formula("C3H4")), Some(3));
+        assert_eq!(
+            named("Alkanes").carbon_number(&formula("CH3CH2CH3")),
+            Some(3)
+        );
+        assert_eq!(named("Amides").carbon_number(&formula("HCONH2")), Some(0));
+        assert!(
+            named("Haloalkanes (halogenoalkanes)").fits(&formula("C2H5Br")),
+            "X stands for any halogen"
+        );
+
+        assert!(!named("Alkanes").fits(&formula("C3H4")));
+        assert!(!named("Ketones").fits(&formula("CH3CHO")), "n starts at 3");
+        assert!(!named("Alcohols").fits(&formula("C2H6O2")));
+        assert!(!named("Carboxylic acids").fits(&formula("HCOO^-")));
+        assert!(
+            named("Alkenes").fits(&formula("C6H12"))
+                && named("Cycloalkanes").fits(&formula("C6H12"))
+        );
+
+        assert_eq!(named("Carboxylic acids").keyword(), "carboxylic acid");
+        assert_eq!(
+            named("Haloalkanes (halogenoalkanes)").keyword(),
+            "haloalkane"
+        );
+        assert_eq!(series_with_general_formula("CnH2nO").len(), 2);
+    }
+}
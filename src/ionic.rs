@@ -0,0 +1,648 @@
+use std::sync::LazyLock;
+
+use crate::compound::Formula;
+use crate::compound::formula::{self, Unit};
+use crate::periodic_table::{self, Block};
+
+/// Polyatomic ion with its English and Japanese names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolyatomicIon {
+    /// Formula body without the charge, e.g. `"SO4"`.
+    pub formula: String,
+    pub charge: i32,
+    pub name_en: String,
+    /// Japanese name without the trailing イオン, e.g. `"硫酸"`.
+    pub name_ja: String,
+}
+
+impl From<DatasetIon> for PolyatomicIon {
+    /// Drops the charge written after the formula (`"SO4^2-"`), the remarks in brackets
+    /// after the names (`"hydrogencarbonate (bicarbonate)"`) and the Japanese イオン.
+    fn from(ion: DatasetIon) -> Self {
+        let formula = ion.formula.split('^').next().unwrap_or_default();
+        let name_en = ion.name_en.split(" (").next().unwrap_or_default();
+        let name_ja = ion.name_ja.split('（').next().unwrap_or_default();
+        Self {
+            formula: formula.trim_end_matches(['+', '-']).to_string(),
+            charge: ion.charge,
+            name_en: name_en.to_string(),
+            name_ja: name_ja
+                .strip_suffix("イオン")
+                .unwrap_or(name_ja)
+                .to_string(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct PolyatomicIonDataset {
+    polyatomic_ions: Vec<DatasetIon>,
+}
+
+#[derive(serde::Deserialize)]
+struct DatasetIon {
+    formula: String,
+    charge: i32,
+    name_en: String,
+    name_ja: String,
+}
+
+/// Ions the dataset leaves out: thiosulfate, for the catalog's sodium thiosulfate.
+const EXTRA_POLYATOMIC_IONS: [(&str, i32, &str, &str); 1] =
+    [("S2O3", -2, "thiosulfate", "チオ硫酸")];
+
+/// Ions from `dataset/inorganic_polyatomic_ions.json`, in its order, followed by
+/// [`EXTRA_POLYATOMIC_IONS`].
+pub static POLYATOMIC_IONS: LazyLock<Vec<PolyatomicIon>> = LazyLock::new(|| {
+    let dataset: PolyatomicIonDataset =
+        serde_json::from_str(include_str!("../dataset/inorganic_polyatomic_ions.json"))
+            .expect("the polyatomic ion dataset parses");
+    let extra = EXTRA_POLYATOMIC_IONS
+        .iter()
+        .map(|(formula, charge, name_en, name_ja)| PolyatomicIon {
+            formula: formula.to_string(),
+            charge: *charge,
+            name_en: name_en.to_string(),
+            name_ja: name_ja.to_string(),
+        });
+    dataset
+        .polyatomic_ions
+        .into_iter()
+        .map(PolyatomicIon::from)
+        .chain(extra)
+        .collect()
+});
+
+/// Monatomic anions: symbol, charge, English and Japanese names.
+const MONATOMIC_ANIONS: [(&str, i32, &str, &str); 8] = [
+    ("F", -1, "fluoride", "フッ化物"),
+    ("Cl", -1, "chloride", "塩化物"),
+    ("Br", -1, "bromide", "臭化物"),
+    ("I", -1, "iodide", "ヨウ化物"),
+    ("O", -2, "oxide", "酸化物"),
+    ("S", -2, "sulfide", "硫化物"),
+    ("N", -3, "nitride", "窒化物"),
+    ("H", -1, "hydride", "水素化物"),
+];
+
+/// Metals outside groups 1 and 2 that only form one common cation, so their names carry no
+/// Roman numeral ("zinc chloride", "silver nitrate").
+const SINGLE_CHARGE_METALS: [(&str, i32); 4] = [("Al", 3), ("Zn", 2), ("Ag", 1), ("Cd", 2)];
+
+/// Metallic p-block elements; every s-block element other than H and He and every d- and
+/// f-block element is a metal as well.
+const P_BLOCK_METALS: [&str; 7] = ["Al", "Ga", "In", "Sn", "Tl", "Pb", "Bi"];
+
+/// Cation or anion that can be combined into an ionic compound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ion {
+    /// Formula body without the charge, e.g. `"Fe"` or `"SO4"`.
+    pub formula: String,
+    pub charge: i32,
+    /// English name without the word "ion", e.g. `"iron(III)"` or `"sulfate"`.
+    pub name_en: String,
+    /// Japanese name without the trailing イオン, e.g. `"鉄(III)"` or `"硫酸"`.
+    pub name_ja: String,
+}
+
+impl Ion {
+    /// Metal cation (`"Fe"`, 3 → iron(III)) or polyatomic cation (`"NH4"`, 1 → ammonium).
+    ///
+    /// Returns `None` for non-metals, non-positive charges, charges that do not match a
+    /// polyatomic cation, and charges other than the only common one of metals such as
+    /// sodium or aluminium.
+    pub fn cation(formula: &str, charge: i32) -> Option<Self> {
+        if charge <= 0 {
+            return None;
+        }
+        if let Some(ion) = POLYATOMIC_IONS
+            .iter()
+            .find(|ion| ion.formula == formula && ion.charge > 0)
+        {
+            return (ion.charge == charge).then(|| Self::from_polyatomic(ion));
+        }
+
+        let element = periodic_table::element(formula).filter(|element| is_metal(element))?;
+        let (name_en, name_ja) = match single_charge(formula) {
+            Some(only) if only == charge => {
+                (element.name_en.to_string(), element.name_ja.to_string())
+            }
+            Some(_) => return None,
+            None => {
+                let numeral = roman_numeral(charge)?;
+                (
+                    format!("{}({numeral})", element.name_en),
+                    format!("{}({numeral})", element.name_ja),
+                )
+            }
+        };
+
+        Some(Self {
+            formula: formula.to_string(),
+            charge,
+            name_en,
+            name_ja,
+        })
+    }
+
+    /// Polyatomic or monatomic anion by its formula body, e.g. `"SO4"` or `"Cl"`.
+    pub fn anion(formula: &str) -> Option<Self> {
+        if let Some(ion) = POLYATOMIC_IONS
+            .iter()
+            .find(|ion| ion.formula == formula && ion.charge < 0)
+        {
+            return Some(Self::from_polyatomic(ion));
+        }
+
+        MONATOMIC_ANIONS
+            .iter()
+            .find(|(symbol, ..)| *symbol == formula)
+            .map(|(symbol, charge, name_en, name_ja)| Self {
+                formula: symbol.to_string(),
+                charge: *charge,
+                name_en: name_en.to_string(),
+                name_ja: name_ja.to_string(),
+            })
+    }
+
+    fn from_polyatomic(ion: &PolyatomicIon) -> Self {
+        Self {
+            formula: ion.formula.clone(),
+            charge: ion.charge,
+            name_en: ion.name_en.clone(),
+            name_ja: ion.name_ja.clone(),
+        }
+    }
+
+    /// Ion written with its charge in the catalog's notation: `Na^+`, `SO4^{2-}`.
+    pub fn notation(&self) -> String {
+        let sign = if self.charge > 0 { '+' } else { '-' };
+        match self.charge.unsigned_abs() {
+            1 => format!("{}^{sign}", self.formula),
+            magnitude => format!("{}^{{{magnitude}{sign}}}", self.formula),
+        }
+    }
+
+    /// `true` for ions of more than one atom, which need brackets when multiplied.
+    fn is_polyatomic(&self) -> bool {
+        !matches!(
+            formula::written_units(&self.formula).as_slice(),
+            [Unit::Atom(_, 1)]
+        )
+    }
+
+    /// `true` for metal cations whose name carries a Roman numeral.
+    fn has_numeral(&self) -> bool {
+        self.name_en.ends_with(')')
+    }
+
+    /// Name with the charge as a Roman numeral even where it is usually left out
+    /// ("silver(I)").
+    fn name_with_numeral(&self) -> String {
+        match (self.has_numeral(), periodic_table::element(&self.formula)) {
+            (false, Some(element)) if self.charge > 0 => match roman_numeral(self.charge) {
+                Some(numeral) => format!("{}({numeral})", element.name_en),
+                None => self.name_en.clone(),
+            },
+            _ => self.name_en.clone(),
+        }
+    }
+}
+
+/// Charge-balanced combination of cations with one anion, optionally hydrated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IonicCompound {
+    cations: Vec<(Ion, u32)>,
+    anion: (Ion, u32),
+    hydrate_water: u32,
+}
+
+impl IonicCompound {
+    /// Combines one cation with one anion in the smallest charge-balanced ratio, e.g.
+    /// Fe³⁺ and SO₄²⁻ into `Fe2(SO4)3`. Returns `None` unless `cation` is positive and
+    /// `anion` negative.
+    pub fn from_ions(cation: Ion, anion: Ion) -> Option<Self> {
+        if cation.charge <= 0 || anion.charge >= 0 {
+            return None;
+        }
+        let positive = cation.charge.unsigned_abs();
+        let negative = anion.charge.unsigned_abs();
+        let divisor = gcd(positive, negative);
+
+        Some(Self {
+            cations: vec![(cation, negative / divisor)],
+            anion: (anion, positive / divisor),
+            hydrate_water: 0,
+        })
+    }
+
+    /// Splits a neutral formula such as `Fe2(SO4)3`, `NH4NO3`, `KAl(SO4)2·12H2O` or
+    /// `Cu2O` into its ions. A metal without a single common charge has its charge inferred
+    /// from the balance, but only as the sole cation: `K4Fe(CN)6` is a complex, not a double
+    /// salt. Formulas that are not a metal or ammonium salt of a known anion give `None`.
+    pub fn decompose(input: &str) -> Option<Self> {
+        Formula::parse(input).ok()?;
+        let (body, charge) = formula::split_body(input).ok()?;
+        if charge != 0 {
+            return None;
+        }
+
+        let mut parts = formula::written_parts(&body).into_iter();
+        let (_, units) = parts.next()?;
+        let mut hydrate_water = 0;
+        for (multiplier, part) in parts {
+            if Unit::atom_run(&part)? != [("H".to_string(), 2), ("O".to_string(), 1)] {
+                return None;
+            }
+            hydrate_water += multiplier;
+        }
+
+        // Prefer the fewest cations: KMnO4 is K⁺ with MnO₄⁻, not K⁺ and Mn⁷⁺ with O²⁻.
+        let (cations, rest) = leading_cations(&units);
+        (1..=cations.len().min(2)).find_map(|taken| {
+            let anion = anion_of(&units[rest[taken - 1]..])?;
+            let cations = balance(&cations[..taken], &anion)?;
+            Some(Self {
+                cations,
+                anion,
+                hydrate_water,
+            })
+        })
+    }
+
+    pub fn cations(&self) -> &[(Ion, u32)] {
+        &self.cations
+    }
+
+    pub fn anion(&self) -> &(Ion, u32) {
+        &self.anion
+    }
+
+    pub fn hydrate_water(&self) -> u32 {
+        self.hydrate_water
+    }
+
+    /// Same compound with `count` waters of crystallisation.
+    pub fn with_hydrate_water(mut self, count: u32) -> Self {
+        self.hydrate_water = count;
+        self
+    }
+
+    /// English name: "iron(III) sulfate", "potassium aluminium sulfate dodecahydrate".
+    pub fn name(&self) -> String {
+        self.compose_name(false, false)
+    }
+
+    /// Japanese name: 硫酸鉄(III), 塩化ナトリウム, 硫酸銅(II)五水和物.
+    pub fn name_ja(&self) -> String {
+        let anion = self.anion.0.name_ja.trim_end_matches('物');
+        let cations: String = self
+            .cations
+            .iter()
+            .map(|(ion, _)| ion.name_ja.as_str())
+            .collect();
+        let hydrate = match self.hydrate_water {
+            0 => String::new(),
+            count => format!("{}水和物", kanji_number(count)),
+        };
+        format!("{anion}{cations}{hydrate}")
+    }
+
+    /// Formula with polyatomic ions bracketed when multiplied: `Fe2(SO4)3`, `CuSO4·5H2O`.
+    pub fn formula(&self) -> String {
+        let mut written: String = self
+            .cations
+            .iter()
+            .map(|(ion, count)| write_ion(ion, *count))
+            .collect();
+        written.push_str(&write_ion(&self.anion.0, self.anion.1));
+        match self.hydrate_water {
+            0 => {}
+            1 => written.push_str("·H2O"),
+            count => written.push_str(&format!("·{count}H2O")),
+        }
+        written
+    }
+
+    /// Whether `name` is an accepted English name for the compound. Spacing and case are
+    /// ignored, and besides [`name`](Self::name) the forms with multiplicative prefixes on
+    /// the cations ("disodium hydrogen phosphate") and with Roman numerals on single-charge
+    /// metals ("silver(I) chloride") are accepted.
+    pub fn matches_name(&self, name: &str) -> bool {
+        let wanted = normalize_name(name);
+        [(false, false), (true, false), (false, true), (true, true)]
+            .into_iter()
+            .any(|(prefixed, numerals)| {
+                normalize_name(&self.compose_name(prefixed, numerals)) == wanted
+            })
+    }
+
+    fn compose_name(&self, prefixed: bool, numerals: bool) -> String {
+        let mut words: Vec<String> = self
+            .cations
+            .iter()
+            .map(|(ion, count)| {
+                let name = if numerals {
+                    ion.name_with_numeral()
+                } else {
+                    ion.name_en.clone()
+                };
+                match multiplicative_prefix(*count).filter(|_| prefixed && *count > 1) {
+                    Some(prefix) => format!("{prefix}{name}"),
+                    None => name,
+                }
+            })
+            .collect();
+        words.push(self.anion.0.name_en.clone());
+        if self.hydrate_water > 0 {
+            let prefix = multiplicative_prefix(self.hydrate_water)
+                .map(|prefix| if prefix.is_empty() { "mono" } else { prefix })
+                .unwrap_or_default();
+            words.push(format!("{prefix}hydrate"));
+        }
+        words.join(" ")
+    }
+}
+
+/// Cations at the start of `units` and, for each, the index where the units after it begin.
+fn leading_cations(units: &[Unit]) -> (Vec<(Unit, u32)>, Vec<usize>) {
+    let mut cations = Vec::new();
+    let mut ends = Vec::new();
+    let mut index = 0;
+
+    while index < units.len() {
+        match &units[index] {
+            Unit::Group(inner, count) => {
+                let Some(run) = Unit::atom_run(inner) else {
+                    break;
+                };
+                if !polyatomic_cation(&run) {
+                    break;
+                }
+                cations.push((Unit::Group(inner.clone(), 1), *count));
+                index += 1;
+            }
+            Unit::Atom(symbol, count) => {
+                let run = Unit::atom_run(&units[index..]).unwrap_or_default();
+                let ion = POLYATOMIC_IONS
+                    .iter()
+                    .filter(|ion| ion.charge > 0)
+                    .find_map(|ion| {
+                        let tokens = ion_tokens(&ion.formula);
+                        run.starts_with(&tokens).then_some(tokens.len())
+                    });
+                if let Some(length) = ion {
+                    cations.push((Unit::Group(units[index..index + length].to_vec(), 1), 1));
+                    index += length;
+                } else if periodic_table::element(symbol).is_some_and(is_metal) {
+                    cations.push((Unit::Atom(symbol.clone(), 1), *count));
+                    index += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        ends.push(index);
+    }
+
+    (cations, ends)
+}
+
+fn polyatomic_cation(run: &[(String, u32)]) -> bool {
+    POLYATOMIC_IONS
+        .iter()
+        .any(|ion| ion.charge > 0 && ion_tokens(&ion.formula) == run)
+}
+
+/// The anion making up all of `units`: one bracketed or bare polyatomic ion, or a single
+/// element with its count.
+fn anion_of(units: &[Unit]) -> Option<(Ion, u32)> {
+    let (inner, count) = match units {
+        [Unit::Group(inner, count)] => (inner.as_slice(), *count),
+        [Unit::Atom(symbol, count)] => {
+            return Ion::anion(symbol).map(|ion| (ion, *count));
+        }
+        atoms => (atoms, 1),
+    };
+    let run = Unit::atom_run(inner)?;
+    POLYATOMIC_IONS
+        .iter()
+        .find(|ion| ion.charge < 0 && ion_tokens(&ion.formula) == run)
+        .map(|ion| (Ion::from_polyatomic(ion), count))
+}
+
+/// Gives each cation its charge. Polyatomic and single-charge cations are fixed; a lone
+/// other metal takes whatever balances the anion.
+fn balance(cations: &[(Unit, u32)], anion: &(Ion, u32)) -> Option<Vec<(Ion, u32)>> {
+    let needed = -anion.0.charge * anion.1 as i32;
+    let mut fixed = 0;
+    let mut variable = None;
+
+    for (index, (unit, count)) in cations.iter().enumerate() {
+        match unit {
+            Unit::Group(inner, _) => {
+                let run = Unit::atom_run(inner)?;
+                let ion = POLYATOMIC_IONS
+                    .iter()
+                    .find(|ion| ion.charge > 0 && ion_tokens(&ion.formula) == run)?;
+                fixed += ion.charge * *count as i32;
+            }
+            Unit::Atom(symbol, _) => match single_charge(symbol) {
+                Some(charge) => fixed += charge * *count as i32,
+                None if cations.len() == 1 => variable = Some(index),
+                None => return None,
+            },
+        }
+    }
+
+    let variable_charge = match variable {
+        Some(index) => {
+            let count = cations[index].1 as i32;
+            let remaining = needed - fixed;
+            (remaining > 0 && remaining % count == 0).then_some(remaining / count)?
+        }
+        None if fixed == needed => 0,
+        None => return None,
+    };
+
+    cations
+        .iter()
+        .enumerate()
+        .map(|(index, (unit, count))| {
+            let ion = match unit {
+                Unit::Group(inner, _) => {
+                    let run = Unit::atom_run(inner)?;
+                    let ion = POLYATOMIC_IONS
+                        .iter()
+                        .find(|ion| ion.charge > 0 && ion_tokens(&ion.formula) == run)?;
+                    Ion::from_polyatomic(ion)
+                }
+                Unit::Atom(symbol, _) => {
+                    let charge = if Some(index) == variable {
+                        variable_charge
+                    } else {
+                        single_charge(symbol)?
+                    };
+                    Ion::cation(symbol, charge)?
+                }
+            };
+            Some((ion, *count))
+        })
+        .collect()
+}
+
+fn ion_tokens(formula: &str) -> Vec<(String, u32)> {
+    Unit::atom_run(&formula::written_units(formula)).unwrap_or_default()
+}
+
+fn is_metal(element: &periodic_table::Element) -> bool {
+    match element.block {
+        Block::S => !matches!(element.symbol, "H" | "He"),
+        Block::P => P_BLOCK_METALS.contains(&element.symbol),
+        Block::D | Block::F => true,
+    }
+}
+
+/// The only common charge of alkali, alkaline-earth and [`SINGLE_CHARGE_METALS`] cations.
+fn single_charge(symbol: &str) -> Option<i32> {
+    let element = periodic_table::element(symbol)?;
+    match (element.block, element.group) {
+        (Block::S, Some(1)) if symbol != "H" => Some(1),
+        (Block::S, Some(2)) if symbol != "He" => Some(2),
+        _ => SINGLE_CHARGE_METALS
+            .iter()
+            .find(|(metal, _)| *metal == symbol)
+            .map(|(_, charge)| *charge),
+    }
+}
+
+/// Writes `cations` of `cation` and `anions` of `anion` as a formula without checking the
+/// charge balance, for distractors such as `Fe(SO4)2`.
+pub(crate) fn unbalanced_formula(cation: &Ion, cations: u32, anion: &Ion, anions: u32) -> String {
+    format!("{}{}", write_ion(cation, cations), write_ion(anion, anions))
+}
+
+fn write_ion(ion: &Ion, count: u32) -> String {
+    match count {
+        1 => ion.formula.clone(),
+        _ if ion.is_polyatomic() => format!("({}){count}", ion.formula),
+        _ => format!("{}{count}", ion.formula),
+    }
+}
+
+fn roman_numeral(value: i32) -> Option<&'static str> {
+    ["I", "II", "III", "IV", "V", "VI", "VII", "VIII"]
+        .get(usize::try_from(value).ok()?.checked_sub(1)?)
+        .copied()
+}
+
+/// Prefix for a count of 1–12; the prefix for 1 is empty.
+fn multiplicative_prefix(count: u32) -> Option<&'static str> {
+    [
+        "", "di", "tri", "tetra", "penta", "hexa", "hepta", "octa", "nona", "deca", "undeca",
+        "dodeca",
+    ]
+    .get(usize::try_from(count).ok()?.checked_sub(1)?)
+    .copied()
+}
+
+fn kanji_number(count: u32) -> String {
+    const DIGITS: [&str; 10] = ["", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    match count {
+        1..=9 => DIGITS[count as usize].to_string(),
+        10 => "十".to_string(),
+        11..=19 => format!("十{}", DIGITS[(count - 10) as usize]),
+        _ => count.to_string(),
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|character| !character.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn gcd(mut left: u32, mut right: u32) -> u32 {
+    while right != 0 {
+        (left, right) = (right, left % right);
+    }
+    left.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ion_pair(cation: &str, charge: i32, anion: &str) -> IonicCompound {
+        IonicCompound::from_ions(
+            Ion::cation(cation, charge).expect("known cation"),
+            Ion::anion(anion).expect("known anion"),
+        )
+        .expect("opposite charges")
+    }
+
+    #[test]
+    fn composes_charge_balanced_names_and_formulas() {
+        let iron_sulfate = ion_pair("Fe", 3, "SO4");
+        assert_eq!(iron_sulfate.formula(), "Fe2(SO4)3");
+        assert_eq!(iron_sulfate.name(), "iron(III) sulfate");
+        assert_eq!(iron_sulfate.name_ja(), "硫酸鉄(III)");
+
+        let calcium_chloride = ion_pair("Ca", 2, "Cl");
+        assert_eq!(calcium_chloride.formula(), "CaCl2");
+        assert_eq!(calcium_chloride.name_ja(), "塩化カルシウム");
+
+        let ammonium_sulfate = ion_pair("NH4", 1, "SO4");
+        assert_eq!(ammonium_sulfate.formula(), "(NH4)2SO4");
+        assert_eq!(ammonium_sulfate.name(), "ammonium sulfate");
+        assert_eq!(ion_pair("Na", 1, "CH3COO").name_ja(), "酢酸ナトリウム");
+
+        let hydrate = ion_pair("Cu", 2, "SO4").with_hydrate_water(5);
+        assert_eq!(hydrate.formula(), "CuSO4·5H2O");
+        assert_eq!(hydrate.name(), "copper(II) sulfate pentahydrate");
+        assert_eq!(hydrate.name_ja(), "硫酸銅(II)五水和物");
+
+        assert_eq!(Ion::cation("Na", 2), None);
+        assert_eq!(Ion::cation("S", 2), None);
+        assert_eq!(
+            Ion::anion("SO4").map(|ion| ion.notation()).as_deref(),
+            Some("SO4^{2-}")
+        );
+        assert_eq!(
+            Ion::cation("Ag", 1).map(|ion| ion.notation()).as_deref(),
+            Some("Ag^+")
+        );
+    }
+
+    #[test]
+    fn decomposes_salts_into_ions() {
+        let name = |formula: &str| IonicCompound::decompose(formula).map(|salt| salt.name());
+
+        assert_eq!(name("Fe2(SO4)3").as_deref(), Some("iron(III) sulfate"));
+        assert_eq!(name("KMnO4").as_deref(), Some("potassium permanganate"));
+        assert_eq!(name("NH4NO3").as_deref(), Some("ammonium nitrate"));
+        assert_eq!(name("NaHCO3").as_deref(), Some("sodium hydrogencarbonate"));
+        assert_eq!(name("Na2S2O3").as_deref(), Some("sodium thiosulfate"));
+        assert_eq!(name("Cu2O").as_deref(), Some("copper(I) oxide"));
+        assert_eq!(
+            name("KAl(SO4)2·12H2O").as_deref(),
+            Some("potassium aluminium sulfate dodecahydrate")
+        );
+        assert_eq!(name("Fe3O4"), None);
+        assert_eq!(name("H2SO4"), None);
+        assert_eq!(name("NH3"), None);
+        assert_eq!(name("Cu(NH3)4^{2+}"), None);
+        assert_eq!(name("K4Fe(CN)6"), None);
+
+        let phosphate = IonicCompound::decompose("Ca3(PO4)2").expect("salt");
+        assert_eq!(phosphate.formula(), "Ca3(PO4)2");
+        assert!(phosphate.matches_name("tricalcium phosphate"));
+        assert!(phosphate.matches_name("calcium phosphate"));
+        assert!(!phosphate.matches_name("calcium phosphite"));
+
+        let silver = IonicCompound::decompose("AgCl").expect("salt");
+        assert!(silver.matches_name("silver chloride"));
+        assert!(silver.matches_name("silver(I) chloride"));
+    }
+}
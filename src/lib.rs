@@ -1,13 +1,54 @@
 pub mod catalog;
 pub mod catalog_manifest;
+pub mod composition;
 pub mod compound;
 pub mod demo;
+pub mod depiction;
+pub mod functional_groups;
+pub mod homologous_series;
+pub mod ionic;
+pub mod nomenclature;
+pub mod oxidation;
+pub mod periodic_table;
 pub mod quiz;
+pub mod reaction;
+pub mod smiles;
+pub mod stereo;
+pub mod validation;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
-pub use catalog::{Catalog, CatalogEntry, CatalogError, CatalogLoadError};
+pub use catalog::{Catalog, CatalogEntry, CatalogError, CatalogLoadError, fill_loaded_compound};
 pub use catalog_manifest::{CatalogLeaf, CatalogManifest, CatalogNode};
-pub use compound::Compound;
+pub use composition::{
+    CompositionError, ElementShare, MassComposition, empirical_formula,
+    empirical_formula_from_percentages, mass_composition, molecular_formula_from_empirical,
+};
+pub use compound::{Compound, Formula, FormulaError, PhysicalState, StructuralDescriptors};
 pub use demo::{DEMO_OPTION_COUNT, demo_catalog, demo_compounds};
+pub use depiction::{full_structure_svg, skeletal_svg};
+pub use functional_groups::{
+    FunctionalGroupKind, GroupMatch, detect_functional_groups, find_functional_groups,
+};
+pub use homologous_series::{HOMOLOGOUS_SERIES, HomologousSeries, series_with_general_formula};
+pub use ionic::{Ion, IonicCompound, PolyatomicIon};
+pub use nomenclature::{
+    NameParseError, NamingError, name_molecule, parse_name, random_structure, smiles_from_name,
+    systematic_name,
+};
+pub use oxidation::{
+    OxidationAssignment, OxidationError, OxidationNumber, OxidationStates, oxidation_states,
+};
+pub use periodic_table::{Block, Element};
 pub use quiz::{QuizError, QuizItem, QuizMode, generate_quiz};
+pub use reaction::{
+    Equation, PRECIPITATION_REACTIONS, PrecipitationReaction, ReactionError, Species,
+};
+pub use smiles::{BondDirection, Chirality, Molecule, SmilesError};
+pub use stereo::{
+    Configuration, Geometry, StereoBond, StereoDescriptors, Stereocentre, stereo_descriptors,
+};
+pub use validation::{
+    EntryIssue, EntryProblem, check_catalog_functional_groups, check_catalog_katex,
+    check_catalog_salt_names, check_catalog_structures, check_catalog_systematic_names,
+};
@@ -0,0 +1,865 @@
+mod parse;
+mod sketch;
+
+pub use parse::{NameParseError, parse_name, smiles_from_name};
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::smiles::{BondOrder, Molecule, SmilesError};
+use sketch::Sketch;
+
+/// Chain stems for parents of one to twenty carbons.
+const STEMS: [&str; 20] = [
+    "meth", "eth", "prop", "but", "pent", "hex", "hept", "oct", "non", "dec", "undec", "dodec",
+    "tridec", "tetradec", "pentadec", "hexadec", "heptadec", "octadec", "nonadec", "icos",
+];
+
+/// Multiplying prefixes for simple substituents and suffixes, indexed by count − 1.
+const MULTIPLIERS: [&str; 6] = ["", "di", "tri", "tetra", "penta", "hexa"];
+
+/// Multiplying prefixes for substituents that are themselves substituted.
+const COMPLEX_MULTIPLIERS: [&str; 6] = ["", "bis", "tris", "tetrakis", "pentakis", "hexakis"];
+
+/// Substituents whose alkoxy name drops the "yl" ("methoxy" rather than "methyloxy").
+const SHORT_ALKOXY_LENGTH: usize = 4;
+
+/// Errors raised when a structure falls outside what [`systematic_name`] can name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NamingError {
+    #[error("SMILES could not be parsed: {0}")]
+    Smiles(#[from] SmilesError),
+    #[error("element {0} is not supported; only carbon, hydrogen and oxygen are named")]
+    UnsupportedElement(String),
+    #[error("charged structures are not supported")]
+    Charged,
+    #[error("aromatic structures are not supported")]
+    Aromatic,
+    #[error("stereo configurations are not named")]
+    Stereo,
+    #[error("structure has several disconnected parts")]
+    Disconnected,
+    #[error("only acyclic structures and single carbocycles are supported")]
+    UnsupportedRing,
+    #[error("unsupported structure: {0}")]
+    UnsupportedStructure(&'static str),
+    #[error("parent chains longer than {} carbons are not supported", STEMS.len())]
+    ChainTooLong,
+}
+
+/// Characteristic groups that can be cited as the suffix, in increasing seniority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CharacteristicGroup {
+    Alcohol,
+    Ketone,
+    Aldehyde,
+    Ester,
+    CarboxylicAcid,
+}
+
+impl CharacteristicGroup {
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Alcohol => "ol",
+            Self::Ketone => "one",
+            Self::Aldehyde => "al",
+            Self::Ester => "oate",
+            Self::CarboxylicAcid => "oic acid",
+        }
+    }
+
+    /// Prefix used when a more senior group takes the suffix.
+    fn prefix(self) -> Result<&'static str, NamingError> {
+        match self {
+            Self::Alcohol => Ok("hydroxy"),
+            Self::Ketone | Self::Aldehyde => Ok("oxo"),
+            Self::Ester | Self::CarboxylicAcid => Err(NamingError::UnsupportedStructure(
+                "ester or acid group cited as a prefix",
+            )),
+        }
+    }
+
+    /// Aldehydes, acids and esters sit at the end of the chain and never need a locant.
+    fn takes_locants(self) -> bool {
+        matches!(self, Self::Alcohol | Self::Ketone)
+    }
+}
+
+/// Returns the substitutive IUPAC name of the structure written as `smiles`.
+///
+/// Covers alkanes, alkenes, alkynes, single carbocycles, alcohols, ethers (as alkoxy
+/// prefixes), aldehydes, ketones, carboxylic acids and esters. See [`name_molecule`].
+///
+/// # Errors
+/// Returns [`NamingError`] when the SMILES is invalid or the structure is out of scope.
+pub fn systematic_name(smiles: &str) -> Result<String, NamingError> {
+    name_molecule(&Molecule::parse(smiles)?)
+}
+
+/// Names a parsed molecule following the 1979/1993 IUPAC substitutive rules.
+///
+/// The parent chain has the most principal characteristic groups, then the most multiple
+/// bonds, then the most carbons, then the most double bonds and the most substituents. It is
+/// numbered to give the lowest locants to the principal groups, then to multiple bonds
+/// (double bonds before triple bonds on a tie), then to the prefixes, and finally to the
+/// prefix cited first in alphabetical order. Locants that cannot vary are left out, as in
+/// "propanone", "ethanol" and "methylcyclohexane".
+///
+/// # Errors
+/// Returns [`NamingError`] for anything but uncharged, non-aromatic C/H/O structures that are
+/// acyclic or contain a single carbocycle, for structures with written stereo marks, and for
+/// groups the rules above do not cover (anhydrides, peroxides, unsaturated or oxygenated
+/// side chains, …).
+pub fn name_molecule(molecule: &Molecule) -> Result<String, NamingError> {
+    let structure = Structure::analyse(molecule)?;
+    let parent = structure.best_parent()?;
+    structure.name(&parent)
+}
+
+/// Carbon framework with its characteristic groups worked out.
+struct Structure<'a> {
+    molecule: &'a Molecule,
+    /// Carbon atoms the parent may use; the alcohol side of an ester is excluded.
+    skeleton: Vec<bool>,
+    /// Characteristic group carried by each carbon atom.
+    groups: Vec<Option<CharacteristicGroup>>,
+    principal: Option<CharacteristicGroup>,
+    /// Ester oxygen and the first carbon of the alkyl group named in front of the parent.
+    ester: Option<(usize, usize)>,
+    /// Ring atoms in bond order, when the structure has a ring.
+    ring: Option<Vec<usize>>,
+}
+
+/// One way of choosing and numbering the parent.
+struct Parent {
+    atoms: Vec<usize>,
+    cyclic: bool,
+    principal_locants: Vec<u32>,
+    double_locants: Vec<u32>,
+    triple_locants: Vec<u32>,
+    prefixes: Vec<Prefix>,
+}
+
+/// Substituent cited as a prefix, such as `methyl` at locant 2.
+struct Prefix {
+    name: String,
+    /// Substituted substituents are parenthesised and multiplied with "bis", "tris", ….
+    complex: bool,
+    locant: u32,
+}
+
+impl<'a> Structure<'a> {
+    fn analyse(molecule: &'a Molecule) -> Result<Self, NamingError> {
+        let atoms = molecule.atoms();
+        if atoms.is_empty() {
+            return Err(NamingError::UnsupportedStructure("empty structure"));
+        }
+        for atom in atoms {
+            if atom.element != "C" && atom.element != "O" {
+                return Err(NamingError::UnsupportedElement(atom.element.clone()));
+            }
+            if atom.charge != 0 {
+                return Err(NamingError::Charged);
+            }
+            if atom.aromatic {
+                return Err(NamingError::Aromatic);
+            }
+            if atom.chirality.is_some() {
+                return Err(NamingError::Stereo);
+            }
+        }
+        if molecule.bonds().iter().any(|bond| bond.direction.is_some()) {
+            return Err(NamingError::Stereo);
+        }
+        if molecule.components().len() > 1 {
+            return Err(NamingError::Disconnected);
+        }
+
+        let ring = match molecule.ring_count() {
+            0 => None,
+            1 => {
+                let ring = molecule.rings().remove(0);
+                if ring.iter().any(|&atom| atoms[atom].element != "C") {
+                    return Err(NamingError::UnsupportedRing);
+                }
+                Some(ring)
+            }
+            _ => return Err(NamingError::UnsupportedRing),
+        };
+
+        let mut carbonyls = vec![0u32; atoms.len()];
+        let mut hydroxyls = vec![0u32; atoms.len()];
+        let mut bridges = Vec::new();
+        for (oxygen, atom) in atoms.iter().enumerate() {
+            if atom.element != "O" {
+                continue;
+            }
+            let neighbors: Vec<(usize, BondOrder)> = molecule
+                .neighbors(oxygen)
+                .map(|(neighbor, bond)| (neighbor, bond.order))
+                .collect();
+            match neighbors.as_slice() {
+                [(carbon, BondOrder::Double)] => carbonyls[*carbon] += 1,
+                [(carbon, BondOrder::Single)] => hydroxyls[*carbon] += 1,
+                [(left, BondOrder::Single), (right, BondOrder::Single)]
+                    if atoms[*left].element == "C" && atoms[*right].element == "C" =>
+                {
+                    bridges.push((oxygen, *left, *right));
+                }
+                [] => return Err(NamingError::UnsupportedStructure("lone oxygen atom")),
+                _ => return Err(NamingError::UnsupportedStructure("oxygen bonded to oxygen")),
+            }
+        }
+
+        let mut ester_bridges = Vec::new();
+        for &(oxygen, left, right) in &bridges {
+            match (carbonyls[left] > 0, carbonyls[right] > 0) {
+                (true, true) => return Err(NamingError::UnsupportedStructure("acid anhydride")),
+                (true, false) => ester_bridges.push((oxygen, left, right)),
+                (false, true) => ester_bridges.push((oxygen, right, left)),
+                (false, false) => {}
+            }
+        }
+
+        let mut groups = vec![None; atoms.len()];
+        for (carbon, group) in groups.iter_mut().enumerate() {
+            let is_ester_carbon = ester_bridges.iter().any(|&(_, acyl, _)| acyl == carbon);
+            *group = match (carbonyls[carbon], hydroxyls[carbon]) {
+                (0, 0) => None,
+                (0, 1) => Some(CharacteristicGroup::Alcohol),
+                (1, 0) if is_ester_carbon => Some(CharacteristicGroup::Ester),
+                (1, 1) if !is_ester_carbon => Some(CharacteristicGroup::CarboxylicAcid),
+                (1, 0) => {
+                    let carbons = molecule
+                        .neighbors(carbon)
+                        .filter(|(neighbor, _)| atoms[*neighbor].element == "C")
+                        .count();
+                    if carbons <= 1 {
+                        Some(CharacteristicGroup::Aldehyde)
+                    } else {
+                        Some(CharacteristicGroup::Ketone)
+                    }
+                }
+                _ => {
+                    return Err(NamingError::UnsupportedStructure(
+                        "several oxygen groups on one carbon",
+                    ));
+                }
+            };
+        }
+
+        let principal = groups.iter().flatten().copied().max();
+        let mut skeleton: Vec<bool> = atoms.iter().map(|atom| atom.element == "C").collect();
+        let ester = match ester_bridges.as_slice() {
+            [] => None,
+            [(oxygen, _, alkyl)] if principal == Some(CharacteristicGroup::Ester) => {
+                for atom in subtree(molecule, *alkyl, *oxygen) {
+                    skeleton[atom] = false;
+                }
+                Some((*oxygen, *alkyl))
+            }
+            [_] => {
+                return Err(NamingError::UnsupportedStructure(
+                    "ester alongside a carboxylic acid",
+                ));
+            }
+            _ => return Err(NamingError::UnsupportedStructure("several ester groups")),
+        };
+
+        if let Some(ring) = &ring {
+            if ring.iter().any(|&atom| !skeleton[atom]) {
+                return Err(NamingError::UnsupportedStructure(
+                    "ring in the alkyl part of an ester",
+                ));
+            }
+            if principal.is_some_and(|group| !group.takes_locants()) {
+                return Err(NamingError::UnsupportedStructure(
+                    "acid, ester or aldehyde group on a ring",
+                ));
+            }
+        }
+
+        Ok(Self {
+            molecule,
+            skeleton,
+            groups,
+            principal,
+            ester,
+            ring,
+        })
+    }
+
+    /// Enumerates every candidate parent and numbering and keeps the one the rules prefer.
+    fn best_parent(&self) -> Result<Parent, NamingError> {
+        let mut orderings = Vec::new();
+        match &self.ring {
+            Some(ring) => {
+                for start in 0..ring.len() {
+                    let forward: Vec<usize> = (0..ring.len())
+                        .map(|i| ring[(start + i) % ring.len()])
+                        .collect();
+                    let mut backward = forward.clone();
+                    backward[1..].reverse();
+                    orderings.push(forward);
+                    orderings.push(backward);
+                }
+            }
+            None => {
+                for start in 0..self.skeleton.len() {
+                    if self.skeleton[start] {
+                        orderings.extend(self.paths_from(start));
+                    }
+                }
+            }
+        }
+
+        let cyclic = self.ring.is_some();
+        let mut best: Option<(ParentKey, Parent)> = None;
+        let mut first_error = None;
+        for atoms in orderings {
+            match self.parent(atoms, cyclic) {
+                Ok(parent) => {
+                    let key = parent.key();
+                    if best.as_ref().is_none_or(|(best_key, _)| key < *best_key) {
+                        best = Some((key, parent));
+                    }
+                }
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+
+        match (best, first_error) {
+            (Some((_, parent)), _) => Ok(parent),
+            (None, Some(error)) => Err(error),
+            (None, None) => Err(NamingError::UnsupportedStructure("no carbon chain")),
+        }
+    }
+
+    /// Every simple path through the skeleton's carbon-carbon bonds that starts at `start`.
+    fn paths_from(&self, start: usize) -> Vec<Vec<usize>> {
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![start]];
+        while let Some(path) = stack.pop() {
+            let last = *path.last().expect("paths are never empty");
+            for (neighbor, _) in self.molecule.neighbors(last) {
+                if self.skeleton[neighbor] && !path.contains(&neighbor) {
+                    let mut longer = path.clone();
+                    longer.push(neighbor);
+                    stack.push(longer);
+                }
+            }
+            paths.push(path);
+        }
+        paths
+    }
+
+    /// Describes the parent made of `atoms`, numbered in the given order.
+    fn parent(&self, atoms: Vec<usize>, cyclic: bool) -> Result<Parent, NamingError> {
+        let mut principal_locants = Vec::new();
+        let mut double_locants = Vec::new();
+        let mut triple_locants = Vec::new();
+        let mut prefixes = Vec::new();
+
+        let bond_count = if cyclic { atoms.len() } else { atoms.len() - 1 };
+        for index in 0..bond_count {
+            let (from, to) = (atoms[index], atoms[(index + 1) % atoms.len()]);
+            let locant = index as u32 + 1;
+            match self.molecule.bond_between(from, to).map(|bond| bond.order) {
+                Some(BondOrder::Double) => double_locants.push(locant),
+                Some(BondOrder::Triple) => triple_locants.push(locant),
+                _ => {}
+            }
+        }
+
+        for (index, &atom) in atoms.iter().enumerate() {
+            let locant = index as u32 + 1;
+            if let Some(group) = self.groups[atom] {
+                if Some(group) == self.principal {
+                    principal_locants.push(locant);
+                } else {
+                    prefixes.push(Prefix {
+                        name: group.prefix()?.to_string(),
+                        complex: false,
+                        locant,
+                    });
+                }
+            }
+
+            for (neighbor, bond) in self.molecule.neighbors(atom) {
+                if atoms.contains(&neighbor) {
+                    continue;
+                }
+                if self.molecule.atoms()[neighbor].element == "C" {
+                    if bond.order != BondOrder::Single {
+                        return Err(NamingError::UnsupportedStructure("unsaturated side chain"));
+                    }
+                    let alkyl = self.alkyl(neighbor, atom)?;
+                    prefixes.push(Prefix {
+                        name: alkyl.name,
+                        complex: alkyl.complex,
+                        locant,
+                    });
+                } else if self.molecule.degree(neighbor) == 2
+                    && self.ester.is_none_or(|(oxygen, _)| oxygen != neighbor)
+                {
+                    let carbon = self
+                        .molecule
+                        .neighbors(neighbor)
+                        .map(|(other, _)| other)
+                        .find(|&other| other != atom)
+                        .expect("ether oxygens have two neighbours");
+                    let alkyl = self.alkyl(carbon, neighbor)?;
+                    let name = if alkyl.length <= SHORT_ALKOXY_LENGTH {
+                        format!("{}oxy", alkyl.name.trim_end_matches("yl"))
+                    } else {
+                        format!("{}oxy", alkyl.name)
+                    };
+                    prefixes.push(Prefix {
+                        name,
+                        complex: alkyl.complex,
+                        locant,
+                    });
+                }
+            }
+        }
+
+        Ok(Parent {
+            atoms,
+            cyclic,
+            principal_locants,
+            double_locants,
+            triple_locants,
+            prefixes,
+        })
+    }
+
+    /// Names the saturated, oxygen-free side chain that starts at `start` and hangs off `from`.
+    fn alkyl(&self, start: usize, from: usize) -> Result<Alkyl, NamingError> {
+        let members = subtree(self.molecule, start, from);
+        for &atom in &members {
+            if self.molecule.atoms()[atom].element != "C" {
+                return Err(NamingError::UnsupportedStructure("oxygen in a side chain"));
+            }
+            for (neighbor, bond) in self.molecule.neighbors(atom) {
+                if neighbor != from && bond.order != BondOrder::Single {
+                    return Err(NamingError::UnsupportedStructure("unsaturated side chain"));
+                }
+            }
+        }
+
+        // The substituent chain is numbered from the attachment point; pick the longest,
+        // then the most substituted, then the lowest locants.
+        let mut best: Option<(AlkylKey, Alkyl)> = None;
+        let mut stack = vec![vec![start]];
+        while let Some(path) = stack.pop() {
+            let last = *path.last().expect("paths are never empty");
+            let extensions: Vec<usize> = self
+                .molecule
+                .neighbors(last)
+                .map(|(neighbor, _)| neighbor)
+                .filter(|&neighbor| neighbor != from && !path.contains(&neighbor))
+                .collect();
+            if !extensions.is_empty() {
+                for neighbor in extensions {
+                    let mut longer = path.clone();
+                    longer.push(neighbor);
+                    stack.push(longer);
+                }
+                continue;
+            }
+
+            let mut prefixes = Vec::new();
+            for (index, &atom) in path.iter().enumerate() {
+                for (neighbor, _) in self.molecule.neighbors(atom) {
+                    if neighbor != from && !path.contains(&neighbor) {
+                        let branch = self.alkyl(neighbor, atom)?;
+                        prefixes.push(Prefix {
+                            name: branch.name,
+                            complex: branch.complex,
+                            locant: index as u32 + 1,
+                        });
+                    }
+                }
+            }
+            let stem = STEMS.get(path.len() - 1).ok_or(NamingError::ChainTooLong)?;
+            let alkyl = Alkyl {
+                name: format!("{}{stem}yl", prefix_text(&prefixes, false)),
+                complex: !prefixes.is_empty(),
+                length: path.len(),
+            };
+            let key = (
+                Reverse(path.len()),
+                Reverse(prefixes.len()),
+                sorted_locants(&prefixes),
+                alphabetical_locants(&prefixes),
+            );
+            if best.as_ref().is_none_or(|(best_key, _)| key < *best_key) {
+                best = Some((key, alkyl));
+            }
+        }
+
+        Ok(best.expect("a side chain has at least one path").1)
+    }
+
+    fn name(&self, parent: &Parent) -> Result<String, NamingError> {
+        let length = parent.atoms.len();
+        let stem = STEMS.get(length - 1).ok_or(NamingError::ChainTooLong)?;
+
+        let suffix_locants: &[u32] = match self.principal {
+            Some(group) if group.takes_locants() => &parent.principal_locants,
+            _ => &[],
+        };
+        let numbered =
+            parent.double_locants.len() + parent.triple_locants.len() + suffix_locants.len();
+        let omit_locants = numbered == 1
+            && (parent.cyclic
+                || match self.principal {
+                    Some(CharacteristicGroup::Alcohol) if !suffix_locants.is_empty() => length <= 2,
+                    Some(CharacteristicGroup::Ketone) if !suffix_locants.is_empty() => length == 3,
+                    _ => length <= 3,
+                });
+        let omit_prefix_locants =
+            numbered == 0 && parent.prefixes.len() == 1 && (parent.cyclic || length <= 2);
+
+        let mut name = prefix_text(&parent.prefixes, omit_prefix_locants);
+        if parent.cyclic {
+            name.push_str("cyclo");
+        }
+        name.push_str(stem);
+
+        let unsaturations: Vec<(&[u32], &str)> = [
+            (parent.double_locants.as_slice(), "en"),
+            (parent.triple_locants.as_slice(), "yn"),
+        ]
+        .into_iter()
+        .filter(|(locants, _)| !locants.is_empty())
+        .collect();
+        match unsaturations.first() {
+            None => name.push_str("an"),
+            Some((locants, _)) if locants.len() > 1 => name.push('a'),
+            Some(_) => {}
+        }
+        for (locants, ending) in unsaturations {
+            if !omit_locants {
+                name.push_str(&format!("-{}-", join_locants(locants)));
+            }
+            name.push_str(multiplier(locants.len(), false));
+            name.push_str(ending);
+        }
+
+        match self.principal {
+            Some(group) => {
+                let suffix = format!(
+                    "{}{}",
+                    multiplier(parent.principal_locants.len(), false),
+                    group.suffix()
+                );
+                if !suffix.starts_with(['a', 'e', 'i', 'o', 'u', 'y']) {
+                    name.push('e');
+                }
+                if !suffix_locants.is_empty() && !omit_locants {
+                    name.push_str(&format!("-{}-", join_locants(suffix_locants)));
+                }
+                name.push_str(&suffix);
+            }
+            None => name.push('e'),
+        }
+
+        if let Some((oxygen, carbon)) = self.ester {
+            let alkyl = self.alkyl(carbon, oxygen)?;
+            let alkyl = if alkyl.complex {
+                format!("({})", alkyl.name)
+            } else {
+                alkyl.name
+            };
+            name = format!("{alkyl} {name}");
+        }
+
+        Ok(name)
+    }
+}
+
+/// Parent comparison key; smaller is preferred.
+type ParentKey = (
+    Reverse<usize>,
+    Reverse<usize>,
+    Reverse<usize>,
+    Reverse<usize>,
+    Reverse<usize>,
+    Vec<u32>,
+    Vec<u32>,
+    Vec<u32>,
+    Vec<u32>,
+    Vec<u32>,
+);
+
+impl Parent {
+    fn key(&self) -> ParentKey {
+        let mut multiple: Vec<u32> = self
+            .double_locants
+            .iter()
+            .chain(&self.triple_locants)
+            .copied()
+            .collect();
+        multiple.sort_unstable();
+        (
+            Reverse(self.principal_locants.len()),
+            Reverse(multiple.len()),
+            Reverse(self.atoms.len()),
+            Reverse(self.double_locants.len()),
+            Reverse(self.prefixes.len()),
+            self.principal_locants.clone(),
+            multiple,
+            self.double_locants.clone(),
+            sorted_locants(&self.prefixes),
+            alphabetical_locants(&self.prefixes),
+        )
+    }
+}
+
+/// Side-chain comparison key; smaller is preferred.
+type AlkylKey = (Reverse<usize>, Reverse<usize>, Vec<u32>, Vec<u32>);
+
+/// Side chain name and the length of its own main chain.
+struct Alkyl {
+    name: String,
+    complex: bool,
+    length: usize,
+}
+
+/// Atoms reachable from `start` without passing through `from`, including `start`.
+fn subtree(molecule: &Molecule, start: usize, from: usize) -> Vec<usize> {
+    let mut members = vec![start];
+    let mut cursor = 0;
+    while cursor < members.len() {
+        let atom = members[cursor];
+        cursor += 1;
+        for (neighbor, _) in molecule.neighbors(atom) {
+            if neighbor != from && !members.contains(&neighbor) {
+                members.push(neighbor);
+            }
+        }
+    }
+    members
+}
+
+fn multiplier(count: usize, complex: bool) -> &'static str {
+    let table = if complex {
+        &COMPLEX_MULTIPLIERS
+    } else {
+        &MULTIPLIERS
+    };
+    table[count.clamp(1, table.len()) - 1]
+}
+
+fn join_locants(locants: &[u32]) -> String {
+    locants
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Prefixes grouped by name in alphabetical order, ignoring multiplying prefixes.
+fn grouped_prefixes(prefixes: &[Prefix]) -> Vec<(&str, bool, Vec<u32>)> {
+    let mut groups: BTreeMap<String, (&str, bool, Vec<u32>)> = BTreeMap::new();
+    for prefix in prefixes {
+        let key: String = prefix
+            .name
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .collect();
+        let entry = groups
+            .entry(key)
+            .or_insert((prefix.name.as_str(), prefix.complex, Vec::new()));
+        entry.2.push(prefix.locant);
+    }
+    groups
+        .into_values()
+        .map(|(name, complex, mut locants)| {
+            locants.sort_unstable();
+            (name, complex, locants)
+        })
+        .collect()
+}
+
+fn prefix_text(prefixes: &[Prefix], omit_locants: bool) -> String {
+    grouped_prefixes(prefixes)
+        .into_iter()
+        .map(|(name, complex, locants)| {
+            let name = if complex {
+                format!("({name})")
+            } else {
+                name.to_string()
+            };
+            let multiplied = format!("{}{name}", multiplier(locants.len(), complex));
+            if omit_locants {
+                multiplied
+            } else {
+                format!("{}-{multiplied}", join_locants(&locants))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn sorted_locants(prefixes: &[Prefix]) -> Vec<u32> {
+    let mut locants: Vec<u32> = prefixes.iter().map(|prefix| prefix.locant).collect();
+    locants.sort_unstable();
+    locants
+}
+
+/// Prefix locants in order of citation, used to break the last numbering tie.
+fn alphabetical_locants(prefixes: &[Prefix]) -> Vec<u32> {
+    grouped_prefixes(prefixes)
+        .into_iter()
+        .flat_map(|(_, _, locants)| locants)
+        .collect()
+}
+
+/// Longest main chain drawn by [`random_structure`].
+const MAX_DRILL_CHAIN: usize = 7;
+
+/// Draws a random acyclic structure for nomenclature drills and returns its SMILES.
+///
+/// The structure is a chain of one to seven carbons with up to two methyl or ethyl branches
+/// and at most one double bond, triple bond, hydroxyl, ketone, aldehyde or carboxyl group.
+/// Every structure drawn can be named by [`systematic_name`].
+pub fn random_structure<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let length = rng.gen_range(1..=MAX_DRILL_CHAIN);
+    let mut sketch = Sketch::default();
+    for index in 0..length {
+        let atom = sketch.add('C');
+        if index > 0 {
+            sketch.bond(atom - 1, atom, 1);
+        }
+    }
+
+    if length >= 3 {
+        for _ in 0..rng.gen_range(0..=2) {
+            let at = rng.gen_range(1..length - 1);
+            if sketch.free_valence(at) == 0 {
+                continue;
+            }
+            let first = sketch.add('C');
+            sketch.bond(at, first, 1);
+            if rng.gen_bool(0.25) {
+                let second = sketch.add('C');
+                sketch.bond(first, second, 1);
+            }
+        }
+    }
+
+    match rng.gen_range(0..7) {
+        1 | 2 if length >= 2 => {
+            let order = if rng.gen_bool(0.5) { 2 } else { 3 };
+            let at = rng.gen_range(0..length - 1);
+            if sketch.free_valence(at) >= order - 1 && sketch.free_valence(at + 1) >= order - 1 {
+                sketch.bond(at, at + 1, order);
+            }
+        }
+        3 => {
+            let at = rng.gen_range(0..length);
+            if sketch.free_valence(at) >= 1 {
+                let oxygen = sketch.add('O');
+                sketch.bond(at, oxygen, 1);
+            }
+        }
+        4 if length >= 3 => {
+            let at = rng.gen_range(1..length - 1);
+            if sketch.free_valence(at) >= 2 {
+                let oxygen = sketch.add('O');
+                sketch.bond(at, oxygen, 2);
+            }
+        }
+        5 if sketch.free_valence(0) >= 2 => {
+            let oxygen = sketch.add('O');
+            sketch.bond(0, oxygen, 2);
+        }
+        6 if sketch.free_valence(0) >= 3 => {
+            let carbonyl = sketch.add('O');
+            sketch.bond(0, carbonyl, 2);
+            let hydroxyl = sketch.add('O');
+            sketch.bond(0, hydroxyl, 1);
+        }
+        _ => {}
+    }
+
+    sketch.to_smiles()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn name(smiles: &str) -> String {
+        systematic_name(smiles).unwrap_or_else(|error| panic!("{smiles}: {error}"))
+    }
+
+    #[test]
+    fn names_branched_and_unsaturated_hydrocarbons() {
+        assert_eq!(name("CC(C)(C)C"), "2,2-dimethylpropane");
+        assert_eq!(name("CCC(CC)C(C)C"), "3-ethyl-2-methylpentane");
+        assert_eq!(name("CC(C)CC(C)(C)C"), "2,2,4-trimethylpentane");
+        assert_eq!(name("C=CC=C"), "buta-1,3-diene");
+        assert_eq!(name("CC=C(C)C"), "2-methylbut-2-ene");
+        assert_eq!(name("C#CCC=C"), "pent-1-en-4-yne");
+        assert_eq!(name("CCCC(CCC)C(C)C"), "4-(1-methylethyl)heptane");
+        assert_eq!(name("CC1CCCCC1"), "methylcyclohexane");
+        assert_eq!(name("CC1CCCC(C)C1"), "1,3-dimethylcyclohexane");
+    }
+
+    #[test]
+    fn names_oxygen_compounds() {
+        assert_eq!(name("CO"), "methanol");
+        assert_eq!(name("CC(O)C"), "propan-2-ol");
+        assert_eq!(name("OCC(O)CO"), "propane-1,2,3-triol");
+        assert_eq!(name("CCOC"), "methoxyethane");
+        assert_eq!(name("CC(C)C=O"), "2-methylpropanal");
+        assert_eq!(name("CC(=O)C"), "propanone");
+        assert_eq!(name("CC(=O)CC(C)O"), "4-hydroxypentan-2-one");
+        assert_eq!(name("OC(=O)C(=O)O"), "ethanedioic acid");
+        assert_eq!(name("CC(=O)CC(=O)O"), "3-oxobutanoic acid");
+        assert_eq!(name("C=CCO"), "prop-2-en-1-ol");
+        assert_eq!(name("CCCC(=O)OC"), "methyl butanoate");
+        assert_eq!(name("COC=O"), "methyl methanoate");
+        assert_eq!(name("CC(=O)OC(C)C"), "(1-methylethyl) ethanoate");
+        assert_eq!(name("OC1CCCCC1"), "cyclohexanol");
+        assert_eq!(name("CC1CCCCC1=O"), "2-methylcyclohexanone");
+    }
+
+    #[test]
+    fn rejects_structures_out_of_scope() {
+        assert_eq!(
+            systematic_name("CCN"),
+            Err(NamingError::UnsupportedElement("N".to_string()))
+        );
+        assert_eq!(systematic_name("c1ccccc1"), Err(NamingError::Aromatic));
+        assert_eq!(
+            systematic_name("OC(=O)/C=C\\C(=O)O"),
+            Err(NamingError::Stereo)
+        );
+        assert_eq!(
+            systematic_name("C1CC2CCC1C2"),
+            Err(NamingError::UnsupportedRing)
+        );
+        assert!(matches!(
+            systematic_name("CC(=O)OC(C)=O"),
+            Err(NamingError::UnsupportedStructure(_))
+        ));
+    }
+
+    #[test]
+    fn random_structures_can_be_named() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for _ in 0..500 {
+            let smiles = random_structure(&mut rng);
+            assert!(systematic_name(&smiles).is_ok(), "{smiles}");
+        }
+    }
+}
@@ -0,0 +1,830 @@
+use super::sketch::Sketch;
+use super::{COMPLEX_MULTIPLIERS, MULTIPLIERS, STEMS};
+use crate::smiles::{Molecule, SmilesError};
+
+/// Errors raised while reading a systematic name with [`smiles_from_name`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NameParseError {
+    #[error("name is empty")]
+    Empty,
+    #[error("no parent chain or ring found in \"{0}\"")]
+    UnknownParent(String),
+    #[error("unknown substituent \"{0}\"")]
+    UnknownSubstituent(String),
+    #[error("\"{part}\" needs {expected} locant(s) but has {found}")]
+    LocantCount {
+        part: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("locant {locant} does not fit a chain of {length} carbon(s)")]
+    LocantOutOfRange { locant: u32, length: usize },
+    #[error("\"{0}\" needs a locant to say where it goes")]
+    MissingLocant(String),
+    #[error("\"{part}\" at {locant} would lengthen the chain instead of branching it")]
+    LocantOnChainEnd { part: String, locant: u32 },
+    #[error("the parent is not the chain the naming rules pick; the name is \"{0}\"")]
+    ParentNotPreferred(String),
+    #[error("the locants are not the lowest set; the name is \"{0}\"")]
+    LocantsNotLowest(String),
+    #[error("a ring needs at least three carbons")]
+    RingTooSmall,
+    #[error("rings cannot carry an acid, ester or aldehyde suffix")]
+    SuffixOnRing,
+    #[error("ester name \"{0}\" needs one alkyl group in front of it")]
+    MissingEsterAlkyl(String),
+    #[error("name gives an atom more bonds than its valence allows")]
+    Valence,
+    #[error("structure built from the name is not valid SMILES: {0}")]
+    Smiles(#[from] SmilesError),
+}
+
+/// Suffix cited after the parent, mirroring the groups the name generator knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Suffix {
+    Alcohol,
+    Ketone,
+    Aldehyde,
+    CarboxylicAcid,
+    Ester,
+}
+
+const SUFFIXES: [(&str, Suffix); 5] = [
+    ("ol", Suffix::Alcohol),
+    ("one", Suffix::Ketone),
+    ("al", Suffix::Aldehyde),
+    ("oic acid", Suffix::CarboxylicAcid),
+    ("oate", Suffix::Ester),
+];
+
+/// Part of a name with its locants (if written) and multiplicity, e.g. `2,2-dimethyl`.
+#[derive(Debug, Clone)]
+struct Cited<T> {
+    text: String,
+    locants: Option<Vec<u32>>,
+    count: usize,
+    item: T,
+}
+
+impl<T> Cited<T> {
+    /// Locants as written, or `default` for a single unnumbered item.
+    fn resolve_locants(&self, default: u32) -> Result<Vec<u32>, NameParseError> {
+        match &self.locants {
+            Some(locants) if locants.len() == self.count => Ok(locants.clone()),
+            None if self.count == 1 => Ok(vec![default]),
+            locants => Err(NameParseError::LocantCount {
+                part: self.text.clone(),
+                expected: self.count,
+                found: locants.as_ref().map_or(0, Vec::len),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Substituent {
+    Alkyl(Alkyl),
+    Alkoxy(Alkyl),
+    Hydroxy,
+    Oxo,
+}
+
+/// Alkyl group: a chain numbered from the attachment point with its own branches, kept
+/// sorted so the order they were written in does not matter.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Alkyl {
+    length: usize,
+    branches: Vec<(u32, Alkyl)>,
+}
+
+/// Parent hydride with its unsaturation endings and suffix as written.
+#[derive(Debug)]
+struct Parent {
+    length: usize,
+    cyclic: bool,
+    /// Bond orders (2 or 3) cited as "en" and "yn".
+    unsaturations: Vec<Cited<u32>>,
+    suffix: Option<Cited<Suffix>>,
+}
+
+/// Part of a name that carries a locant.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Numbered {
+    Prefix(Substituent),
+    /// Bond order of an "en" (2) or "yn" (3) ending.
+    Ending(u32),
+    Suffix(Suffix),
+}
+
+/// Numbered parts with their locants, one entry per locant.
+type NumberedParts = Vec<(Numbered, u32)>;
+
+/// What a name says about its structure with every locant resolved, so two names for the
+/// same structure can be compared regardless of spelling, order and omitted locants.
+#[derive(Debug, PartialEq, Eq)]
+struct Reading {
+    length: usize,
+    cyclic: bool,
+    ester: Option<Alkyl>,
+    /// Numbered parts with their locants, sorted.
+    parts: NumberedParts,
+}
+
+impl Reading {
+    fn kinds(&self) -> Vec<&Numbered> {
+        self.parts.iter().map(|(part, _)| part).collect()
+    }
+}
+
+/// Reads a systematic name (as produced by [`systematic_name`](super::systematic_name)) and
+/// returns the structure it describes.
+///
+/// # Errors
+/// See [`smiles_from_name`].
+pub fn parse_name(name: &str) -> Result<Molecule, NameParseError> {
+    let smiles = smiles_from_name(name)?;
+    Ok(Molecule::parse(&smiles)?)
+}
+
+/// Reads a systematic name such as "2-methylpropan-1-ol", "but-2-yne" or "methyl
+/// ethanoate" and writes the structure it describes as SMILES.
+///
+/// The name is split into substituent prefixes (alkyl, alkoxy, hydroxy, oxo, with
+/// parenthesised substituted alkyl groups), the parent chain or ring, its "en"/"yn" endings
+/// and a suffix (ol, one, al, oic acid, oate). Locants left out of a single ending take the
+/// positions the generator would have omitted them for: 1, or 2 for a ketone. A single
+/// prefix may leave out its locant only when every position it fits gives the same
+/// structure, as in "methylpropane" or "methylcyclohexane". The terminal "e" of the parent
+/// is elided before a vowel only ("butan-2-ol", "ethane-1,2-diol").
+///
+/// The name must also pick the parent and numbering that [`systematic_name`] would:
+/// "2-ethylbutane" and "3-methylbutane" are refused in favour of "3-methylpentane" and
+/// "2-methylbutane". Structures the generator cannot name are read without this check.
+///
+/// # Errors
+/// Returns [`NameParseError`] when the name does not follow this grammar, when a locant
+/// count or position does not fit, when a prefix needs a locant it does not have, when the
+/// structure would overload an atom, or when the parent or locants are not the preferred
+/// ones.
+///
+/// [`systematic_name`]: super::systematic_name
+pub fn smiles_from_name(name: &str) -> Result<String, NameParseError> {
+    let (smiles, reading) = read_name(name)?;
+    let Ok(preferred) = super::systematic_name(&smiles) else {
+        return Ok(smiles);
+    };
+    let Ok((_, expected)) = read_name(&preferred) else {
+        return Ok(smiles);
+    };
+
+    if (
+        reading.length,
+        reading.cyclic,
+        &reading.ester,
+        reading.kinds(),
+    ) != (
+        expected.length,
+        expected.cyclic,
+        &expected.ester,
+        expected.kinds(),
+    ) {
+        Err(NameParseError::ParentNotPreferred(preferred))
+    } else if reading != expected {
+        Err(NameParseError::LocantsNotLowest(preferred))
+    } else {
+        Ok(smiles)
+    }
+}
+
+/// Builds the structure a name describes, with no check that it is the preferred name.
+fn read_name(name: &str) -> Result<(String, Reading), NameParseError> {
+    let text = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if text.is_empty() {
+        return Err(NameParseError::Empty);
+    }
+
+    let (ester, body) = match text.split_once(' ') {
+        Some((alkyl, acid)) if acid.ends_with("oate") && !acid.contains(' ') => {
+            let inner = alkyl
+                .strip_prefix('(')
+                .and_then(|inner| inner.strip_suffix(')'))
+                .unwrap_or(alkyl);
+            let group = parse_alkyl(inner)
+                .ok_or_else(|| NameParseError::UnknownSubstituent(alkyl.to_string()))?;
+            (Some(group), acid)
+        }
+        _ => (None, text.as_str()),
+    };
+
+    let (prefixes, parent) = split_name(body)?;
+    let (smiles, mut parts) = build(&prefixes, &parent, ester.as_ref(), body)?;
+    parts.sort();
+    let reading = Reading {
+        length: parent.length,
+        cyclic: parent.cyclic,
+        ester,
+        parts,
+    };
+    Ok((smiles, reading))
+}
+
+/// Finds the split between the prefixes and the parent, preferring the longest parent.
+fn split_name(body: &str) -> Result<(Vec<Cited<Substituent>>, Parent), NameParseError> {
+    let mut first_error = None;
+    for (index, character) in body.char_indices() {
+        let previous = body[..index].chars().next_back();
+        if !character.is_ascii_alphabetic()
+            || previous.is_some_and(|previous| !previous.is_ascii_alphabetic() && previous != ')')
+        {
+            continue;
+        }
+        let Some(parent) = parse_parent(&body[index..]) else {
+            continue;
+        };
+        match parse_prefixes(&body[..index]) {
+            Ok(prefixes) => return Ok((prefixes, parent)),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| NameParseError::UnknownParent(body.to_string())))
+}
+
+fn parse_parent(text: &str) -> Option<Parent> {
+    let (cyclic, text) = match text.strip_prefix("cyclo") {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (length, mut rest) = stems_longest_first()
+        .find_map(|(length, stem)| text.strip_prefix(stem).map(|rest| (length, rest)))?;
+
+    let mut unsaturations = Vec::new();
+    if let Some(after) = rest.strip_prefix("an") {
+        rest = after;
+    } else {
+        // Euphonic "a" before a multiplied ending: "buta-1,3-diene".
+        if let Some(after) = rest.strip_prefix('a')
+            && (after.starts_with('-') || parse_multiplier(after).0 > 1)
+        {
+            rest = after;
+        }
+        while let Some((cited, after)) = parse_unsaturation(rest) {
+            unsaturations.push(cited);
+            rest = after;
+        }
+        if unsaturations.is_empty() {
+            return None;
+        }
+    }
+
+    let suffix = if rest == "e" {
+        None
+    } else {
+        // The terminal "e" stays before consonants only: "ethane-1,2-diol" and "ethanedioic
+        // acid", but "butan-2-ol".
+        let (kept, rest) = match rest.strip_prefix('e') {
+            Some(after) => (true, after),
+            None => (false, rest),
+        };
+        let suffix = parse_suffix(rest)?;
+        let (_, ending) = parse_hyphenated_locants(rest);
+        if ending.starts_with(['a', 'e', 'i', 'o', 'u']) == kept {
+            return None;
+        }
+        Some(suffix)
+    };
+
+    Some(Parent {
+        length,
+        cyclic,
+        unsaturations,
+        suffix,
+    })
+}
+
+fn parse_unsaturation(text: &str) -> Option<(Cited<u32>, &str)> {
+    let (locants, rest) = parse_hyphenated_locants(text);
+    let (count, rest) = parse_multiplier(rest);
+    let (order, ending, rest) = if let Some(rest) = rest.strip_prefix("en") {
+        (2, "en", rest)
+    } else if let Some(rest) = rest.strip_prefix("yn") {
+        (3, "yn", rest)
+    } else {
+        return None;
+    };
+    let cited = Cited {
+        text: ending.to_string(),
+        locants,
+        count,
+        item: order,
+    };
+    Some((cited, rest))
+}
+
+fn parse_suffix(text: &str) -> Option<Cited<Suffix>> {
+    let (locants, rest) = parse_hyphenated_locants(text);
+    let (count, rest) = parse_multiplier(rest);
+    let &(ending, suffix) = SUFFIXES.iter().find(|(ending, _)| *ending == rest)?;
+    Some(Cited {
+        text: ending.to_string(),
+        locants,
+        count,
+        item: suffix,
+    })
+}
+
+/// Reads `-1,3-` at the start of `text`.
+fn parse_hyphenated_locants(text: &str) -> (Option<Vec<u32>>, &str) {
+    let parsed = text.strip_prefix('-').and_then(|rest| {
+        let (locants, rest) = rest.split_once('-')?;
+        Some((parse_locants(locants)?, rest))
+    });
+    match parsed {
+        Some((locants, rest)) => (Some(locants), rest),
+        None => (None, text),
+    }
+}
+
+fn parse_locants(text: &str) -> Option<Vec<u32>> {
+    text.split(',')
+        .map(|locant| locant.parse::<u32>().ok().filter(|&locant| locant > 0))
+        .collect()
+}
+
+/// Reads a multiplying prefix ("di", "tri", …), also in its elided form before a vowel
+/// ("tetrol"). Returns a count of 1 when there is none.
+fn parse_multiplier(text: &str) -> (usize, &str) {
+    for (index, multiplier) in MULTIPLIERS.iter().enumerate().skip(1).rev() {
+        if let Some(rest) = text.strip_prefix(multiplier) {
+            return (index + 1, rest);
+        }
+        if let Some(elided) = multiplier.strip_suffix('a')
+            && let Some(rest) = text.strip_prefix(elided)
+            && rest.starts_with(['a', 'e', 'i', 'o', 'u'])
+        {
+            return (index + 1, rest);
+        }
+    }
+    (1, text)
+}
+
+fn parse_prefixes(text: &str) -> Result<Vec<Cited<Substituent>>, NameParseError> {
+    let mut prefixes = Vec::new();
+    let mut pending = None;
+    for piece in split_outside_parentheses(text) {
+        if let Some(locants) = parse_locants(piece) {
+            pending = Some(locants);
+            continue;
+        }
+        let locants = pending.take();
+        let (count, substituent) = parse_prefix(piece, locants.as_ref().map(Vec::len))
+            .ok_or_else(|| NameParseError::UnknownSubstituent(piece.to_string()))?;
+        prefixes.push(Cited {
+            text: piece.to_string(),
+            locants,
+            count,
+            item: substituent,
+        });
+    }
+    if pending.is_some() {
+        return Err(NameParseError::UnknownSubstituent(text.to_string()));
+    }
+    Ok(prefixes)
+}
+
+/// Splits `2,2-dimethyl-4-(1-methylethyl)` at the hyphens between prefixes.
+fn split_outside_parentheses(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, character) in text.char_indices() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '-' if depth == 0 => {
+                pieces.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+    pieces
+}
+
+/// Reads one prefix with an optional multiplier, which must agree with the locant count
+/// when locants were written.
+fn parse_prefix(text: &str, locant_count: Option<usize>) -> Option<(usize, Substituent)> {
+    if locant_count.is_none_or(|count| count == 1)
+        && let Some(substituent) = parse_substituent(text)
+    {
+        return Some((1, substituent));
+    }
+    for table in [&MULTIPLIERS, &COMPLEX_MULTIPLIERS] {
+        for (index, multiplier) in table.iter().enumerate().skip(1) {
+            let count = index + 1;
+            if locant_count.is_some_and(|expected| expected != count) {
+                continue;
+            }
+            if let Some(rest) = text.strip_prefix(multiplier)
+                && let Some(substituent) = parse_substituent(rest)
+            {
+                return Some((count, substituent));
+            }
+        }
+    }
+    None
+}
+
+fn parse_substituent(text: &str) -> Option<Substituent> {
+    match text {
+        "hydroxy" => return Some(Substituent::Hydroxy),
+        "oxo" => return Some(Substituent::Oxo),
+        _ => {}
+    }
+    if let Some(inner) = text
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+    {
+        return parse_substituent(inner);
+    }
+    if let Some(base) = text.strip_suffix("oxy") {
+        // "methoxy" drops the "yl" of methyl; longer groups keep it ("pentyloxy").
+        return parse_alkyl(&format!("{base}yl"))
+            .or_else(|| parse_alkyl(base))
+            .map(Substituent::Alkoxy);
+    }
+    parse_alkyl(text).map(Substituent::Alkyl)
+}
+
+fn parse_alkyl(text: &str) -> Option<Alkyl> {
+    let body = text.strip_suffix("yl")?;
+    stems_longest_first().find_map(|(length, stem)| {
+        let prefixes = parse_prefixes(body.strip_suffix(stem)?).ok()?;
+        let mut branches = Vec::new();
+        for prefix in prefixes {
+            let Substituent::Alkyl(branch) = &prefix.item else {
+                return None;
+            };
+            for locant in prefix.resolve_locants(1).ok()? {
+                branches.push((locant, branch.clone()));
+            }
+        }
+        branches.sort();
+        Some(Alkyl { length, branches })
+    })
+}
+
+/// Chain stems with their carbon counts, longest text first so "pentadec" wins over "pent".
+fn stems_longest_first() -> impl Iterator<Item = (usize, &'static str)> {
+    let mut stems: Vec<(usize, &str)> = STEMS
+        .iter()
+        .enumerate()
+        .map(|(index, stem)| (index + 1, *stem))
+        .collect();
+    stems.sort_by_key(|(_, stem)| std::cmp::Reverse(stem.len()));
+    stems.into_iter()
+}
+
+/// Builds the structure for every position the unnumbered prefixes could take and keeps the
+/// result only when all the positions that fit agree on it, together with the locants of
+/// the first such position.
+fn build(
+    prefixes: &[Cited<Substituent>],
+    parent: &Parent,
+    ester: Option<&Alkyl>,
+    body: &str,
+) -> Result<(String, NumberedParts), NameParseError> {
+    let choices = prefixes
+        .iter()
+        .map(|prefix| match (&prefix.locants, prefix.count) {
+            (None, 1) => Ok((1..=parent.length as u32)
+                .map(|locant| vec![locant])
+                .collect()),
+            _ => prefix.resolve_locants(1).map(|locants| vec![locants]),
+        })
+        .collect::<Result<Vec<Vec<Vec<u32>>>, _>>()?;
+
+    let mut built: Option<(String, String, NumberedParts)> = None;
+    let mut first_error = None;
+    let mut picks = vec![0; choices.len()];
+    loop {
+        let locants: Vec<&[u32]> = choices
+            .iter()
+            .zip(&picks)
+            .map(|(options, &pick)| options[pick].as_slice())
+            .collect();
+        match build_with_locants(prefixes, &locants, parent, ester, body) {
+            Ok((smiles, parts)) => {
+                let key = Molecule::parse(&smiles)?.canonical_key();
+                match &built {
+                    Some((seen, ..)) if *seen != key => {
+                        let ambiguous = choices
+                            .iter()
+                            .position(|options| options.len() > 1)
+                            .map_or(body, |index| prefixes[index].text.as_str());
+                        return Err(NameParseError::MissingLocant(ambiguous.to_string()));
+                    }
+                    Some(_) => {}
+                    None => built = Some((key, smiles, parts)),
+                }
+            }
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        // Next combination, counting through the choices like an odometer.
+        let Some(index) = (0..picks.len()).find(|&index| picks[index] + 1 < choices[index].len())
+        else {
+            break;
+        };
+        picks[index] += 1;
+        picks[..index].fill(0);
+    }
+
+    match (built, first_error) {
+        (Some((_, smiles, parts)), _) => Ok((smiles, parts)),
+        (None, Some(error)) => Err(error),
+        (None, None) => unreachable!("every prefix has at least one locant choice"),
+    }
+}
+
+fn build_with_locants(
+    prefixes: &[Cited<Substituent>],
+    prefix_locants: &[&[u32]],
+    parent: &Parent,
+    ester: Option<&Alkyl>,
+    body: &str,
+) -> Result<(String, NumberedParts), NameParseError> {
+    let length = parent.length;
+    let mut parts = Vec::new();
+    let mut sketch = Sketch::default();
+    for index in 0..length {
+        let atom = sketch.add('C');
+        if index > 0 {
+            sketch.bond(atom - 1, atom, 1);
+        }
+    }
+    if parent.cyclic {
+        if length < 3 {
+            return Err(NameParseError::RingTooSmall);
+        }
+        sketch.bond(length - 1, 0, 1);
+    }
+
+    let bond_count = if parent.cyclic { length } else { length - 1 };
+    for unsaturation in &parent.unsaturations {
+        for locant in unsaturation.resolve_locants(1)? {
+            if !(1..=bond_count as u32).contains(&locant) {
+                return Err(NameParseError::LocantOutOfRange { locant, length });
+            }
+            let from = locant as usize - 1;
+            sketch.bond(from, (from + 1) % length, unsaturation.item);
+            parts.push((Numbered::Ending(unsaturation.item), locant));
+        }
+    }
+
+    match &parent.suffix {
+        Some(suffix) => {
+            let locants = match suffix.item {
+                Suffix::Alcohol => suffix.resolve_locants(1)?,
+                Suffix::Ketone => suffix.resolve_locants(if parent.cyclic { 1 } else { 2 })?,
+                _ if parent.cyclic => return Err(NameParseError::SuffixOnRing),
+                _ => match (&suffix.locants, suffix.count) {
+                    (None, 2) => vec![1, length as u32],
+                    _ => suffix.resolve_locants(1)?,
+                },
+            };
+            if suffix.item == Suffix::Ester && (ester.is_none() || locants.len() != 1) {
+                return Err(NameParseError::MissingEsterAlkyl(body.to_string()));
+            }
+            for locant in locants {
+                check_locant(locant, length)?;
+                parts.push((Numbered::Suffix(suffix.item), locant));
+                let carbon = locant as usize - 1;
+                match suffix.item {
+                    Suffix::Alcohol => {
+                        attach_oxygen(&mut sketch, carbon, 1);
+                    }
+                    Suffix::Ketone | Suffix::Aldehyde => {
+                        attach_oxygen(&mut sketch, carbon, 2);
+                    }
+                    Suffix::CarboxylicAcid | Suffix::Ester => {
+                        attach_oxygen(&mut sketch, carbon, 2);
+                        let oxygen = attach_oxygen(&mut sketch, carbon, 1);
+                        if let Some(alkyl) = ester {
+                            attach_alkyl(&mut sketch, oxygen, alkyl)?;
+                        }
+                    }
+                }
+            }
+        }
+        None if ester.is_some() => return Err(NameParseError::UnknownParent(body.to_string())),
+        None => {}
+    }
+
+    for (prefix, locants) in prefixes.iter().zip(prefix_locants) {
+        for &locant in *locants {
+            check_locant(locant, length)?;
+            parts.push((Numbered::Prefix(prefix.item.clone()), locant));
+            let carbon = locant as usize - 1;
+            match &prefix.item {
+                Substituent::Alkyl(_)
+                    if !parent.cyclic && (locant == 1 || locant == length as u32) =>
+                {
+                    return Err(NameParseError::LocantOnChainEnd {
+                        part: prefix.text.clone(),
+                        locant,
+                    });
+                }
+                Substituent::Alkyl(alkyl) => attach_alkyl(&mut sketch, carbon, alkyl)?,
+                Substituent::Alkoxy(alkyl) => {
+                    let oxygen = attach_oxygen(&mut sketch, carbon, 1);
+                    attach_alkyl(&mut sketch, oxygen, alkyl)?;
+                }
+                Substituent::Hydroxy => {
+                    attach_oxygen(&mut sketch, carbon, 1);
+                }
+                Substituent::Oxo => {
+                    attach_oxygen(&mut sketch, carbon, 2);
+                }
+            }
+        }
+    }
+
+    if sketch.is_overloaded() {
+        return Err(NameParseError::Valence);
+    }
+    Ok((sketch.to_smiles(), parts))
+}
+
+fn check_locant(locant: u32, length: usize) -> Result<(), NameParseError> {
+    if (1..=length as u32).contains(&locant) {
+        Ok(())
+    } else {
+        Err(NameParseError::LocantOutOfRange { locant, length })
+    }
+}
+
+fn attach_oxygen(sketch: &mut Sketch, carbon: usize, order: u32) -> usize {
+    let oxygen = sketch.add('O');
+    sketch.bond(carbon, oxygen, order);
+    oxygen
+}
+
+fn attach_alkyl(sketch: &mut Sketch, at: usize, alkyl: &Alkyl) -> Result<(), NameParseError> {
+    let chain: Vec<usize> = (0..alkyl.length).map(|_| sketch.add('C')).collect();
+    sketch.bond(at, chain[0], 1);
+    for pair in chain.windows(2) {
+        sketch.bond(pair[0], pair[1], 1);
+    }
+    for (locant, branch) in &alkyl.branches {
+        check_locant(*locant, alkyl.length)?;
+        attach_alkyl(sketch, chain[*locant as usize - 1], branch)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nomenclature::{random_structure, systematic_name};
+    use rand::SeedableRng;
+
+    fn same_structure(name: &str, smiles: &str) -> bool {
+        let parsed = parse_name(name).unwrap_or_else(|error| panic!("{name}: {error}"));
+        parsed.canonical_key()
+            == Molecule::parse(smiles)
+                .expect("test SMILES should parse")
+                .canonical_key()
+    }
+
+    #[test]
+    fn reads_names_into_structures() {
+        assert!(same_structure("2-methylpropan-1-ol", "CC(C)CO"));
+        assert!(same_structure("but-2-yne", "CC#CC"));
+        assert!(same_structure("buta-1,3-diene", "C=CC=C"));
+        assert!(same_structure("propanone", "CC(C)=O"));
+        assert!(same_structure("ethane-1,2-diol", "OCCO"));
+        assert!(same_structure("ethanedioic acid", "OC(=O)C(=O)O"));
+        assert!(same_structure("methoxyethane", "COCC"));
+        assert!(same_structure("methyl ethanoate", "CC(=O)OC"));
+        assert!(same_structure("(1-methylethyl) ethanoate", "CC(=O)OC(C)C"));
+        assert!(same_structure("4-(1-methylethyl)heptane", "CCCC(CCC)C(C)C"));
+        assert!(same_structure("2-methylcyclohexanone", "CC1CCCCC1=O"));
+        assert!(same_structure("pent-1-en-4-yne", "C=CCC#C"));
+        assert!(same_structure("  Propan-2-OL ", "CC(C)O"));
+    }
+
+    #[test]
+    fn requires_the_preferred_parent_and_numbering() {
+        assert_eq!(
+            smiles_from_name("3-methylbutane"),
+            Err(NameParseError::LocantsNotLowest(
+                "2-methylbutane".to_string()
+            ))
+        );
+        assert_eq!(
+            smiles_from_name("2-methylbutan-3-ol"),
+            Err(NameParseError::LocantsNotLowest(
+                "3-methylbutan-2-ol".to_string()
+            ))
+        );
+        assert_eq!(
+            smiles_from_name("2-ethylbutane"),
+            Err(NameParseError::ParentNotPreferred(
+                "3-methylpentane".to_string()
+            ))
+        );
+        assert_eq!(
+            smiles_from_name("propan-1,2-diol"),
+            Err(NameParseError::UnknownParent("propan-1,2-diol".to_string()))
+        );
+        assert_eq!(
+            smiles_from_name("butane-2-ol"),
+            Err(NameParseError::UnknownParent("butane-2-ol".to_string()))
+        );
+
+        assert!(same_structure("propane-1,2-diol", "CC(O)CO"));
+        assert!(same_structure("1-methylcyclohexane", "CC1CCCCC1"));
+        assert!(same_structure("4-ethyl-2-methylhexane", "CCC(CC)CC(C)C"));
+    }
+
+    #[test]
+    fn leaves_out_only_locants_the_structure_forces() {
+        assert!(same_structure("methylpropane", "CC(C)C"));
+        assert!(same_structure("methylbutane", "CC(C)CC"));
+        assert!(same_structure("methylcyclohexane", "CC1CCCCC1"));
+        assert!(same_structure("methylpropanoic acid", "CC(C)C(=O)O"));
+        assert!(same_structure("hydroxyethanoic acid", "OCC(=O)O"));
+
+        assert_eq!(
+            smiles_from_name("methylpentane"),
+            Err(NameParseError::MissingLocant("methyl".to_string()))
+        );
+        assert_eq!(
+            smiles_from_name("methylcyclohexanol"),
+            Err(NameParseError::MissingLocant("methyl".to_string()))
+        );
+    }
+
+    #[test]
+    fn round_trips_generated_names() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        for _ in 0..300 {
+            let smiles = random_structure(&mut rng);
+            let name = systematic_name(&smiles).expect("random structures can be named");
+            assert!(same_structure(&name, &smiles), "{name} vs {smiles}");
+        }
+    }
+
+    #[test]
+    fn reports_names_it_cannot_read() {
+        assert_eq!(smiles_from_name(" "), Err(NameParseError::Empty));
+        assert_eq!(
+            smiles_from_name("benzene"),
+            Err(NameParseError::UnknownParent("benzene".to_string()))
+        );
+        assert_eq!(
+            smiles_from_name("2,3-methylbutane"),
+            Err(NameParseError::UnknownSubstituent("methyl".to_string()))
+        );
+        assert_eq!(
+            smiles_from_name("hex-7-ene"),
+            Err(NameParseError::LocantOutOfRange {
+                locant: 7,
+                length: 6
+            })
+        );
+        assert_eq!(
+            smiles_from_name("2,2,2-trimethylbutane"),
+            Err(NameParseError::Valence)
+        );
+        assert_eq!(
+            smiles_from_name("1-methylpropane"),
+            Err(NameParseError::LocantOnChainEnd {
+                part: "methyl".to_string(),
+                locant: 1
+            })
+        );
+        assert_eq!(
+            smiles_from_name("1-methylpropan-1-ol"),
+            Err(NameParseError::LocantOnChainEnd {
+                part: "methyl".to_string(),
+                locant: 1
+            })
+        );
+        assert_eq!(
+            smiles_from_name("ethanoate"),
+            Err(NameParseError::MissingEsterAlkyl("ethanoate".to_string()))
+        );
+    }
+}
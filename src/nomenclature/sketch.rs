@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+/// Heavy-atom graph built up by [`random_structure`](super::random_structure) and the name
+/// parser before it is written as SMILES. Hydrogens are left implicit.
+#[derive(Default)]
+pub(super) struct Sketch {
+    elements: Vec<char>,
+    bonds: Vec<Vec<(usize, u32)>>,
+}
+
+impl Sketch {
+    pub(super) fn add(&mut self, element: char) -> usize {
+        self.elements.push(element);
+        self.bonds.push(Vec::new());
+        self.elements.len() - 1
+    }
+
+    /// Adds a bond, or raises the order of an existing one.
+    pub(super) fn bond(&mut self, left: usize, right: usize, order: u32) {
+        for (from, to) in [(left, right), (right, left)] {
+            match self.bonds[from].iter_mut().find(|(atom, _)| *atom == to) {
+                Some(bond) => bond.1 = order,
+                None => self.bonds[from].push((to, order)),
+            }
+        }
+    }
+
+    fn valence(&self, atom: usize) -> u32 {
+        if self.elements[atom] == 'C' { 4 } else { 2 }
+    }
+
+    fn used_valence(&self, atom: usize) -> u32 {
+        self.bonds[atom].iter().map(|(_, order)| order).sum()
+    }
+
+    pub(super) fn free_valence(&self, atom: usize) -> u32 {
+        self.valence(atom).saturating_sub(self.used_valence(atom))
+    }
+
+    /// Returns `true` when some atom has more bonds than its valence allows.
+    pub(super) fn is_overloaded(&self) -> bool {
+        (0..self.elements.len()).any(|atom| self.used_valence(atom) > self.valence(atom))
+    }
+
+    /// Writes the graph as SMILES starting from atom 0, closing rings with digit labels.
+    pub(super) fn to_smiles(&self) -> String {
+        let mut visited = vec![false; self.elements.len()];
+        let mut children = vec![Vec::new(); self.elements.len()];
+        let mut closures: BTreeMap<(usize, usize), u32> = BTreeMap::new();
+        if !self.elements.is_empty() {
+            self.spanning_tree(0, None, &mut visited, &mut children, &mut closures);
+        }
+
+        let mut labels: Vec<Vec<(u32, u32)>> = vec![Vec::new(); self.elements.len()];
+        for (label, (&(left, right), &order)) in closures.iter().enumerate() {
+            labels[left].push((label as u32 + 1, order));
+            labels[right].push((label as u32 + 1, order));
+        }
+
+        let mut out = String::new();
+        if !self.elements.is_empty() {
+            self.write(0, &children, &labels, &mut out);
+        }
+        out
+    }
+
+    fn spanning_tree(
+        &self,
+        atom: usize,
+        parent: Option<usize>,
+        visited: &mut [bool],
+        children: &mut [Vec<(usize, u32)>],
+        closures: &mut BTreeMap<(usize, usize), u32>,
+    ) {
+        visited[atom] = true;
+        for &(neighbor, order) in &self.bonds[atom] {
+            if Some(neighbor) == parent {
+                continue;
+            }
+            if visited[neighbor] {
+                closures.insert((atom.min(neighbor), atom.max(neighbor)), order);
+            } else {
+                children[atom].push((neighbor, order));
+                self.spanning_tree(neighbor, Some(atom), visited, children, closures);
+            }
+        }
+    }
+
+    fn write(
+        &self,
+        atom: usize,
+        children: &[Vec<(usize, u32)>],
+        labels: &[Vec<(u32, u32)>],
+        out: &mut String,
+    ) {
+        out.push(self.elements[atom]);
+        for &(label, order) in &labels[atom] {
+            out.push_str(bond_symbol(order));
+            out.push_str(&label.to_string());
+        }
+        for (index, &(child, order)) in children[atom].iter().enumerate() {
+            let last = index + 1 == children[atom].len();
+            if !last {
+                out.push('(');
+            }
+            out.push_str(bond_symbol(order));
+            self.write(child, children, labels, out);
+            if !last {
+                out.push(')');
+            }
+        }
+    }
+}
+
+fn bond_symbol(order: u32) -> &'static str {
+    match order {
+        2 => "=",
+        3 => "#",
+        _ => "",
+    }
+}
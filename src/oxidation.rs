@@ -0,0 +1,493 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::compound::formula::{self, Unit};
+use crate::compound::{Formula, FormulaError};
+use crate::ionic::POLYATOMIC_IONS;
+use crate::periodic_table;
+
+/// Neutral ligands written inside complexes, such as the NH3 of `Cu(NH3)4^{2+}`.
+const NEUTRAL_LIGANDS: [&str; 2] = ["NH3", "H2O"];
+
+/// Polyatomic ions and neutral ligands with their charges.
+fn known_ions() -> impl Iterator<Item = (&'static str, i32)> {
+    POLYATOMIC_IONS
+        .iter()
+        .map(|ion| (ion.formula.as_str(), ion.charge))
+        .chain(NEUTRAL_LIGANDS.iter().map(|ligand| (*ligand, 0)))
+}
+
+/// Oxidation number, kept as a reduced fraction so mixed-valence compounds such as `Fe3O4`
+/// report their average (`+8/3`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OxidationNumber {
+    numerator: i32,
+    denominator: u32,
+}
+
+impl OxidationNumber {
+    /// Average oxidation number of `atoms` atoms whose states add up to `total`.
+    pub fn new(total: i32, atoms: u32) -> Self {
+        let divisor = gcd(total.unsigned_abs(), atoms.max(1));
+        Self {
+            numerator: total / divisor as i32,
+            denominator: atoms.max(1) / divisor,
+        }
+    }
+
+    /// The value as an integer, or `None` for fractional averages.
+    pub fn as_integer(self) -> Option<i32> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl From<i32> for OxidationNumber {
+    fn from(value: i32) -> Self {
+        Self::new(value, 1)
+    }
+}
+
+impl fmt::Display for OxidationNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.numerator > 0 {
+            f.write_str("+")?;
+        }
+        write!(f, "{}", self.numerator)?;
+        if self.denominator != 1 {
+            write!(f, "/{}", self.denominator)?;
+        }
+        Ok(())
+    }
+}
+
+/// Oxidation number shared by `atoms` atoms of `element` at one site of a formula.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OxidationAssignment {
+    pub element: String,
+    pub atoms: u32,
+    pub state: OxidationNumber,
+    /// `true` when the value comes from a fixed rule (F −1, alkali metals +1, O −2, …)
+    /// rather than from balancing the charge.
+    pub by_rule: bool,
+}
+
+/// Oxidation numbers assigned to every element of a formula.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OxidationStates {
+    assignments: Vec<OxidationAssignment>,
+}
+
+impl OxidationStates {
+    pub fn assignments(&self) -> &[OxidationAssignment] {
+        &self.assignments
+    }
+
+    /// Oxidation number of `symbol`, or `None` when it is absent or appears at sites with
+    /// different oxidation numbers (the two nitrogens of `NH4NO3`).
+    pub fn state_of(&self, symbol: &str) -> Option<OxidationNumber> {
+        let mut states = self
+            .assignments
+            .iter()
+            .filter(|assignment| assignment.element == symbol)
+            .map(|assignment| assignment.state);
+        let first = states.next()?;
+        states.all(|state| state == first).then_some(first)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OxidationError {
+    #[error("formula could not be parsed: {0}")]
+    Formula(#[from] FormulaError),
+    #[error("oxidation states of {} cannot be determined from the formula alone", .elements.join(", "))]
+    Undetermined { elements: Vec<String> },
+}
+
+/// Assigns oxidation numbers to the elements of `formula`.
+///
+/// Known polyatomic ions (`SO4^2-`, `NH4+`, `CN-`, …) and neutral ligands are split off
+/// first, so `NH4NO3` gives nitrogen −3 in the cation and +5 in the anion and `Fe2(SO4)3`
+/// gives iron +3. Within each part the usual rules apply in priority order: fluorine −1,
+/// alkali metals +1, alkaline-earth metals +2, aluminium +3 and zinc +2, hydrogen +1,
+/// oxygen −2, then the other halogens −1. The lowest-priority rule gives way when the
+/// charges would not balance, which covers peroxides, metal hydrides, oxyhalogen ions and
+/// free elements. A single remaining element takes whatever balances the charge.
+pub fn oxidation_states(input: &str) -> Result<OxidationStates, OxidationError> {
+    Formula::parse(input)?;
+    let (body, charge) = formula::split_body(input)?;
+
+    let mut assignments: Vec<OxidationAssignment> = Vec::new();
+    for (index, (multiplier, units)) in formula::written_parts(&body).into_iter().enumerate() {
+        let target = if index == 0 { charge } else { 0 };
+        let sites = solve_part(&units, multiplier, target)?;
+        for site in sites {
+            match assignments
+                .iter_mut()
+                .find(|existing| existing.element == site.element && existing.state == site.state)
+            {
+                Some(existing) => {
+                    existing.atoms += site.atoms;
+                    existing.by_rule &= site.by_rule;
+                }
+                None => assignments.push(site),
+            }
+        }
+    }
+
+    Ok(OxidationStates { assignments })
+}
+
+/// Fragment of a formula solved on its own: a known ion (with its charge) or the rest.
+struct Fragment {
+    counts: BTreeMap<String, u32>,
+    charge: Option<i32>,
+    multiplier: u32,
+}
+
+fn solve_part(
+    units: &[Unit],
+    multiplier: u32,
+    target: i32,
+) -> Result<Vec<OxidationAssignment>, OxidationError> {
+    let mut fragments = Vec::new();
+    let mut rest = BTreeMap::new();
+    collect_fragments(units, 1, true, &mut fragments, &mut rest);
+
+    let split = solve_fragments(fragments, rest, target);
+    let sites = match split {
+        Some(Ok(sites)) => sites,
+        // Fall back to the whole part when the ion split does not balance.
+        _ => {
+            let mut counts = BTreeMap::new();
+            flatten(units, 1, &mut counts);
+            solve_fragment(&counts, target)?
+        }
+    };
+
+    Ok(sites
+        .into_iter()
+        .map(|mut site| {
+            site.atoms *= multiplier;
+            site
+        })
+        .collect())
+}
+
+/// Solves known-ion fragments by their own charge and the rest by what remains. Returns
+/// `None` when the split cannot balance (an ion matched the whole part, for instance).
+fn solve_fragments(
+    fragments: Vec<Fragment>,
+    rest: BTreeMap<String, u32>,
+    target: i32,
+) -> Option<Result<Vec<OxidationAssignment>, OxidationError>> {
+    let ion_charge: i32 = fragments
+        .iter()
+        .map(|fragment| fragment.charge.unwrap_or(0) * fragment.multiplier as i32)
+        .sum();
+    let remaining = target - ion_charge;
+    if rest.is_empty() && remaining != 0 {
+        return None;
+    }
+
+    let mut sites = Vec::new();
+    for fragment in fragments.iter().chain(std::iter::once(&Fragment {
+        counts: rest,
+        charge: Some(remaining),
+        multiplier: 1,
+    })) {
+        if fragment.counts.is_empty() {
+            continue;
+        }
+        match solve_fragment(&fragment.counts, fragment.charge.unwrap_or(0)) {
+            Ok(solved) => sites.extend(solved.into_iter().map(|mut site| {
+                site.atoms *= fragment.multiplier;
+                site
+            })),
+            Err(error) => return Some(Err(error)),
+        }
+    }
+    Some(Ok(sites))
+}
+
+fn collect_fragments(
+    units: &[Unit],
+    multiplier: u32,
+    top_level: bool,
+    fragments: &mut Vec<Fragment>,
+    rest: &mut BTreeMap<String, u32>,
+) {
+    let mut atoms: Vec<(String, u32)> = units
+        .iter()
+        .filter_map(|unit| match unit {
+            Unit::Atom(symbol, count) => Some((symbol.clone(), *count)),
+            Unit::Group(..) => None,
+        })
+        .collect();
+
+    if top_level {
+        let leading = units
+            .iter()
+            .take_while(|unit| matches!(unit, Unit::Atom(..)))
+            .count();
+        let trailing = units
+            .iter()
+            .rev()
+            .take_while(|unit| matches!(unit, Unit::Atom(..)))
+            .count();
+        let total_atoms = atoms.len();
+        let grouped = units.len() > total_atoms;
+
+        // A cation at the start of the first run, an anion at the end of the last one. An ion
+        // that would consume the whole part is a molecule (NO2, SO3), not a salt.
+        if let Some((length, charge)) = match_ion(&atoms[..leading], true)
+            .filter(|(length, _)| grouped || *length < total_atoms)
+        {
+            let ion: Vec<(String, u32)> = atoms.drain(..length).collect();
+            fragments.push(ion_fragment(&ion, charge, multiplier));
+        }
+        let trailing = trailing.min(atoms.len());
+        let start = atoms.len() - trailing;
+        if let Some((length, charge)) =
+            match_ion(&atoms[start..], false).filter(|(length, _)| grouped || *length < atoms.len())
+        {
+            let ion: Vec<(String, u32)> = atoms.drain(atoms.len() - length..).collect();
+            fragments.push(ion_fragment(&ion, charge, multiplier));
+        }
+    }
+
+    for (symbol, count) in atoms {
+        *rest.entry(symbol).or_insert(0) += count * multiplier;
+    }
+
+    for unit in units {
+        let Unit::Group(inner, count) = unit else {
+            continue;
+        };
+        let flat = Unit::atom_run(inner);
+        let known = flat.as_ref().and_then(|flat| {
+            known_ions()
+                .find(|(ion, _)| ion_tokens(ion) == *flat)
+                .map(|(_, charge)| charge)
+        });
+
+        match (flat, known) {
+            (Some(flat), Some(charge)) => {
+                fragments.push(ion_fragment(&flat, charge, multiplier * count))
+            }
+            _ => collect_fragments(inner, multiplier * count, false, fragments, rest),
+        }
+    }
+}
+
+/// Longest known ion matching the start (`prefix`) or end of `atoms`, with its charge.
+/// Prefixes only match cations and suffixes only anions.
+fn match_ion(atoms: &[(String, u32)], prefix: bool) -> Option<(usize, i32)> {
+    known_ions()
+        .filter(|(_, charge)| if prefix { *charge > 0 } else { *charge < 0 })
+        .filter_map(|(ion, charge)| {
+            let tokens = ion_tokens(ion);
+            let length = tokens.len();
+            let candidate = if prefix {
+                atoms.get(..length)
+            } else {
+                atoms.len().checked_sub(length).map(|start| &atoms[start..])
+            };
+            (candidate == Some(&tokens[..])).then_some((length, charge))
+        })
+        .max_by_key(|(length, _)| *length)
+}
+
+fn ion_tokens(ion: &str) -> Vec<(String, u32)> {
+    Unit::atom_run(&formula::written_units(ion)).unwrap_or_default()
+}
+
+fn ion_fragment(tokens: &[(String, u32)], charge: i32, multiplier: u32) -> Fragment {
+    let mut counts = BTreeMap::new();
+    for (symbol, count) in tokens {
+        *counts.entry(symbol.clone()).or_insert(0) += count;
+    }
+    Fragment {
+        counts,
+        charge: Some(charge),
+        multiplier,
+    }
+}
+
+fn flatten(units: &[Unit], multiplier: u32, counts: &mut BTreeMap<String, u32>) {
+    for unit in units {
+        match unit {
+            Unit::Atom(symbol, count) => {
+                *counts.entry(symbol.clone()).or_insert(0) += count * multiplier
+            }
+            Unit::Group(inner, count) => flatten(inner, multiplier * count, counts),
+        }
+    }
+}
+
+/// Fixed oxidation number and priority (lower wins) for elements covered by the usual rules.
+fn rule(symbol: &str) -> Option<(u8, i32)> {
+    let rule = match symbol {
+        "F" => (0, -1),
+        "Li" | "Na" | "K" | "Rb" | "Cs" | "Fr" => (1, 1),
+        "Be" | "Mg" | "Ca" | "Sr" | "Ba" | "Ra" => (2, 2),
+        "Al" => (3, 3),
+        "Zn" => (3, 2),
+        "H" => (4, 1),
+        "O" => (5, -2),
+        "Cl" => (6, -1),
+        "Br" => (7, -1),
+        "I" => (8, -1),
+        _ => return None,
+    };
+    Some(rule)
+}
+
+/// Assigns oxidation numbers within one fragment whose charges must add up to `target`.
+fn solve_fragment(
+    counts: &BTreeMap<String, u32>,
+    target: i32,
+) -> Result<Vec<OxidationAssignment>, OxidationError> {
+    // (symbol, atoms, priority, value) for ruled elements; unknowns listed separately.
+    let mut ruled: Vec<(&str, u32, u8, i32)> = Vec::new();
+    let mut unknown: Vec<(&str, u32)> = Vec::new();
+    for (symbol, &atoms) in counts {
+        match rule(symbol) {
+            Some((priority, value)) => ruled.push((symbol, atoms, priority, value)),
+            None => unknown.push((symbol, atoms)),
+        }
+    }
+
+    // With several unknowns, the most electronegative main-group non-metal takes its
+    // lowest state (group − 18): C in SiC, N in CN⁻.
+    while unknown.len() > 1 {
+        let anion = unknown
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (symbol, _))| {
+                let element = periodic_table::element(symbol)?;
+                let group = element.group.filter(|group| (14..=17).contains(group))?;
+                Some((index, group, element.period))
+            })
+            .max_by_key(|(_, group, period)| (*group, std::cmp::Reverse(*period)));
+        let Some((index, group, _)) = anion else {
+            return Err(OxidationError::Undetermined {
+                elements: unknown
+                    .iter()
+                    .map(|(symbol, _)| symbol.to_string())
+                    .collect(),
+            });
+        };
+        let (symbol, atoms) = unknown.remove(index);
+        ruled.push((symbol, atoms, u8::MAX, i32::from(group) - 18));
+    }
+
+    ruled.sort_by_key(|(_, _, priority, _)| *priority);
+    let ruled_total = |ruled: &[(&str, u32, u8, i32)]| -> i32 {
+        ruled
+            .iter()
+            .map(|(_, atoms, _, value)| *atoms as i32 * value)
+            .sum()
+    };
+
+    if unknown.is_empty() && ruled_total(&ruled) != target {
+        // The lowest-priority rule gives way: O in H2O2, H in NaH, Cl in ClO3⁻.
+        if let Some((symbol, atoms, _, _)) = ruled.pop() {
+            unknown.push((symbol, atoms));
+        }
+    }
+
+    let mut sites: Vec<OxidationAssignment> = ruled
+        .iter()
+        .map(|(symbol, atoms, _, value)| OxidationAssignment {
+            element: symbol.to_string(),
+            atoms: *atoms,
+            state: OxidationNumber::from(*value),
+            by_rule: true,
+        })
+        .collect();
+
+    if let Some((symbol, atoms)) = unknown.first() {
+        sites.push(OxidationAssignment {
+            element: symbol.to_string(),
+            atoms: *atoms,
+            state: OxidationNumber::new(target - ruled_total(&ruled), *atoms),
+            by_rule: false,
+        });
+    }
+
+    Ok(sites)
+}
+
+fn gcd(mut left: u32, mut right: u32) -> u32 {
+    while right != 0 {
+        (left, right) = (right, left % right);
+    }
+    left.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(formula: &str, symbol: &str) -> String {
+        oxidation_states(formula)
+            .expect("formula should be solvable")
+            .state_of(symbol)
+            .map(|state| state.to_string())
+            .unwrap_or_else(|| "mixed".to_string())
+    }
+
+    #[test]
+    fn balances_the_central_atom_of_salts_and_acids() {
+        assert_eq!(state("KMnO4", "Mn"), "+7");
+        assert_eq!(state("K2Cr2O7", "Cr"), "+6");
+        assert_eq!(state("H2SO4", "S"), "+6");
+        assert_eq!(state("Na2S2O3·5H2O", "S"), "+2");
+        assert_eq!(state("HClO", "Cl"), "+1");
+        assert_eq!(state("NaCl", "Cl"), "-1");
+        assert_eq!(state("Cr2O3", "Cr"), "+3");
+        assert_eq!(state("Fe3O4", "Fe"), "+8/3");
+    }
+
+    #[test]
+    fn uses_polyatomic_ions_to_separate_unknowns() {
+        assert_eq!(state("Fe2(SO4)3", "Fe"), "+3");
+        assert_eq!(state("K4Fe(CN)6", "Fe"), "+2");
+        assert_eq!(state("K3Fe(CN)6", "Fe"), "+3");
+        assert_eq!(state("Cu(NH3)4^{2+}", "Cu"), "+2");
+        assert_eq!(state("MnSO4", "Mn"), "+2");
+
+        let ammonium_nitrate = oxidation_states("NH4NO3").expect("formula should be solvable");
+        assert_eq!(ammonium_nitrate.state_of("N"), None);
+        let nitrogen: Vec<String> = ammonium_nitrate
+            .assignments()
+            .iter()
+            .filter(|assignment| assignment.element == "N")
+            .map(|assignment| assignment.state.to_string())
+            .collect();
+        assert_eq!(nitrogen, vec!["-3", "+5"]);
+    }
+
+    #[test]
+    fn relaxes_rules_for_peroxides_hydrides_and_elements() {
+        assert_eq!(state("H2O2", "O"), "-1");
+        assert_eq!(state("NaH", "H"), "-1");
+        assert_eq!(state("OF2", "O"), "+2");
+        assert_eq!(state("O3", "O"), "0");
+        assert_eq!(state("Fe", "Fe"), "0");
+        assert_eq!(state("SiC", "Si"), "+4");
+        assert_eq!(state("NO2", "N"), "+4");
+    }
+
+    #[test]
+    fn reports_undetermined_formulas() {
+        assert!(matches!(
+            oxidation_states("FeCr2"),
+            Err(OxidationError::Undetermined { .. })
+        ));
+        assert!(matches!(
+            oxidation_states("Xx2"),
+            Err(OxidationError::Formula(_))
+        ));
+    }
+}
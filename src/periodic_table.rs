@@ -0,0 +1,225 @@
+/// Block of the periodic table an element belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Block {
+    S,
+    P,
+    D,
+    F,
+}
+
+/// Static data for a single element, embedded so lookups work without any dataset files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Element {
+    pub atomic_number: u8,
+    pub symbol: &'static str,
+    /// English name in lowercase, as used within compound names.
+    pub name_en: &'static str,
+    /// Japanese name.
+    pub name_ja: &'static str,
+    /// Standard atomic weight in g/mol. Elements without stable isotopes use the mass
+    /// number of their longest-lived isotope.
+    pub atomic_weight: f64,
+    /// Group number (1–18). Lanthanoids and actinoids other than Lu and Lr have none.
+    pub group: Option<u8>,
+    pub period: u8,
+    pub block: Block,
+}
+
+/// Looks up an element by its symbol, e.g. `"Fe"`.
+pub fn element(symbol: &str) -> Option<&'static Element> {
+    ELEMENTS.iter().find(|element| element.symbol == symbol)
+}
+
+/// Looks up an element by atomic number.
+pub fn element_by_number(atomic_number: u8) -> Option<&'static Element> {
+    ELEMENTS.get(usize::from(atomic_number).checked_sub(1)?)
+}
+
+/// Returns every element ordered by atomic number.
+pub fn elements() -> &'static [Element] {
+    &ELEMENTS
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn entry(
+    atomic_number: u8,
+    symbol: &'static str,
+    name_en: &'static str,
+    name_ja: &'static str,
+    atomic_weight: f64,
+    group: Option<u8>,
+    period: u8,
+    block: Block,
+) -> Element {
+    Element {
+        atomic_number,
+        symbol,
+        name_en,
+        name_ja,
+        atomic_weight,
+        group,
+        period,
+        block,
+    }
+}
+
+#[rustfmt::skip]
+static ELEMENTS: [Element; 118] = [
+    entry(1, "H", "hydrogen", "水素", 1.008, Some(1), 1, Block::S),
+    entry(2, "He", "helium", "ヘリウム", 4.0026, Some(18), 1, Block::S),
+    entry(3, "Li", "lithium", "リチウム", 6.94, Some(1), 2, Block::S),
+    entry(4, "Be", "beryllium", "ベリリウム", 9.0122, Some(2), 2, Block::S),
+    entry(5, "B", "boron", "ホウ素", 10.81, Some(13), 2, Block::P),
+    entry(6, "C", "carbon", "炭素", 12.011, Some(14), 2, Block::P),
+    entry(7, "N", "nitrogen", "窒素", 14.007, Some(15), 2, Block::P),
+    entry(8, "O", "oxygen", "酸素", 15.999, Some(16), 2, Block::P),
+    entry(9, "F", "fluorine", "フッ素", 18.998, Some(17), 2, Block::P),
+    entry(10, "Ne", "neon", "ネオン", 20.180, Some(18), 2, Block::P),
+    entry(11, "Na", "sodium", "ナトリウム", 22.990, Some(1), 3, Block::S),
+    entry(12, "Mg", "magnesium", "マグネシウム", 24.305, Some(2), 3, Block::S),
+    entry(13, "Al", "aluminium", "アルミニウム", 26.982, Some(13), 3, Block::P),
+    entry(14, "Si", "silicon", "ケイ素", 28.085, Some(14), 3, Block::P),
+    entry(15, "P", "phosphorus", "リン", 30.974, Some(15), 3, Block::P),
+    entry(16, "S", "sulfur", "硫黄", 32.06, Some(16), 3, Block::P),
+    entry(17, "Cl", "chlorine", "塩素", 35.45, Some(17), 3, Block::P),
+    entry(18, "Ar", "argon", "アルゴン", 39.95, Some(18), 3, Block::P),
+    entry(19, "K", "potassium", "カリウム", 39.098, Some(1), 4, Block::S),
+    entry(20, "Ca", "calcium", "カルシウム", 40.078, Some(2), 4, Block::S),
+    entry(21, "Sc", "scandium", "スカンジウム", 44.956, Some(3), 4, Block::D),
+    entry(22, "Ti", "titanium", "チタン", 47.867, Some(4), 4, Block::D),
+    entry(23, "V", "vanadium", "バナジウム", 50.942, Some(5), 4, Block::D),
+    entry(24, "Cr", "chromium", "クロム", 51.996, Some(6), 4, Block::D),
+    entry(25, "Mn", "manganese", "マンガン", 54.938, Some(7), 4, Block::D),
+    entry(26, "Fe", "iron", "鉄", 55.845, Some(8), 4, Block::D),
+    entry(27, "Co", "cobalt", "コバルト", 58.933, Some(9), 4, Block::D),
+    entry(28, "Ni", "nickel", "ニッケル", 58.693, Some(10), 4, Block::D),
+    entry(29, "Cu", "copper", "銅", 63.546, Some(11), 4, Block::D),
+    entry(30, "Zn", "zinc", "亜鉛", 65.38, Some(12), 4, Block::D),
+    entry(31, "Ga", "gallium", "ガリウム", 69.723, Some(13), 4, Block::P),
+    entry(32, "Ge", "germanium", "ゲルマニウム", 72.630, Some(14), 4, Block::P),
+    entry(33, "As", "arsenic", "ヒ素", 74.922, Some(15), 4, Block::P),
+    entry(34, "Se", "selenium", "セレン", 78.971, Some(16), 4, Block::P),
+    entry(35, "Br", "bromine", "臭素", 79.904, Some(17), 4, Block::P),
+    entry(36, "Kr", "krypton", "クリプトン", 83.798, Some(18), 4, Block::P),
+    entry(37, "Rb", "rubidium", "ルビジウム", 85.468, Some(1), 5, Block::S),
+    entry(38, "Sr", "strontium", "ストロンチウム", 87.62, Some(2), 5, Block::S),
+    entry(39, "Y", "yttrium", "イットリウム", 88.906, Some(3), 5, Block::D),
+    entry(40, "Zr", "zirconium", "ジルコニウム", 91.224, Some(4), 5, Block::D),
+    entry(41, "Nb", "niobium", "ニオブ", 92.906, Some(5), 5, Block::D),
+    entry(42, "Mo", "molybdenum", "モリブデン", 95.95, Some(6), 5, Block::D),
+    entry(43, "Tc", "technetium", "テクネチウム", 98.0, Some(7), 5, Block::D),
+    entry(44, "Ru", "ruthenium", "ルテニウム", 101.07, Some(8), 5, Block::D),
+    entry(45, "Rh", "rhodium", "ロジウム", 102.91, Some(9), 5, Block::D),
+    entry(46, "Pd", "palladium", "パラジウム", 106.42, Some(10), 5, Block::D),
+    entry(47, "Ag", "silver", "銀", 107.87, Some(11), 5, Block::D),
+    entry(48, "Cd", "cadmium", "カドミウム", 112.41, Some(12), 5, Block::D),
+    entry(49, "In", "indium", "インジウム", 114.82, Some(13), 5, Block::P),
+    entry(50, "Sn", "tin", "スズ", 118.71, Some(14), 5, Block::P),
+    entry(51, "Sb", "antimony", "アンチモン", 121.76, Some(15), 5, Block::P),
+    entry(52, "Te", "tellurium", "テルル", 127.60, Some(16), 5, Block::P),
+    entry(53, "I", "iodine", "ヨウ素", 126.90, Some(17), 5, Block::P),
+    entry(54, "Xe", "xenon", "キセノン", 131.29, Some(18), 5, Block::P),
+    entry(55, "Cs", "caesium", "セシウム", 132.91, Some(1), 6, Block::S),
+    entry(56, "Ba", "barium", "バリウム", 137.33, Some(2), 6, Block::S),
+    entry(57, "La", "lanthanum", "ランタン", 138.91, None, 6, Block::F),
+    entry(58, "Ce", "cerium", "セリウム", 140.12, None, 6, Block::F),
+    entry(59, "Pr", "praseodymium", "プラセオジム", 140.91, None, 6, Block::F),
+    entry(60, "Nd", "neodymium", "ネオジム", 144.24, None, 6, Block::F),
+    entry(61, "Pm", "promethium", "プロメチウム", 145.0, None, 6, Block::F),
+    entry(62, "Sm", "samarium", "サマリウム", 150.36, None, 6, Block::F),
+    entry(63, "Eu", "europium", "ユウロピウム", 151.96, None, 6, Block::F),
+    entry(64, "Gd", "gadolinium", "ガドリニウム", 157.25, None, 6, Block::F),
+    entry(65, "Tb", "terbium", "テルビウム", 158.93, None, 6, Block::F),
+    entry(66, "Dy", "dysprosium", "ジスプロシウム", 162.50, None, 6, Block::F),
+    entry(67, "Ho", "holmium", "ホルミウム", 164.93, None, 6, Block::F),
+    entry(68, "Er", "erbium", "エルビウム", 167.26, None, 6, Block::F),
+    entry(69, "Tm", "thulium", "ツリウム", 168.93, None, 6, Block::F),
+    entry(70, "Yb", "ytterbium", "イッテルビウム", 173.05, None, 6, Block::F),
+    entry(71, "Lu", "lutetium", "ルテチウム", 174.97, Some(3), 6, Block::D),
+    entry(72, "Hf", "hafnium", "ハフニウム", 178.49, Some(4), 6, Block::D),
+    entry(73, "Ta", "tantalum", "タンタル", 180.95, Some(5), 6, Block::D),
+    entry(74, "W", "tungsten", "タングステン", 183.84, Some(6), 6, Block::D),
+    entry(75, "Re", "rhenium", "レニウム", 186.21, Some(7), 6, Block::D),
+    entry(76, "Os", "osmium", "オスミウム", 190.23, Some(8), 6, Block::D),
+    entry(77, "Ir", "iridium", "イリジウム", 192.22, Some(9), 6, Block::D),
+    entry(78, "Pt", "platinum", "白金", 195.08, Some(10), 6, Block::D),
+    entry(79, "Au", "gold", "金", 196.97, Some(11), 6, Block::D),
+    entry(80, "Hg", "mercury", "水銀", 200.59, Some(12), 6, Block::D),
+    entry(81, "Tl", "thallium", "タリウム", 204.38, Some(13), 6, Block::P),
+    entry(82, "Pb", "lead", "鉛", 207.2, Some(14), 6, Block::P),
+    entry(83, "Bi", "bismuth", "ビスマス", 208.98, Some(15), 6, Block::P),
+    entry(84, "Po", "polonium", "ポロニウム", 209.0, Some(16), 6, Block::P),
+    entry(85, "At", "astatine", "アスタチン", 210.0, Some(17), 6, Block::P),
+    entry(86, "Rn", "radon", "ラドン", 222.0, Some(18), 6, Block::P),
+    entry(87, "Fr", "francium", "フランシウム", 223.0, Some(1), 7, Block::S),
+    entry(88, "Ra", "radium", "ラジウム", 226.0, Some(2), 7, Block::S),
+    entry(89, "Ac", "actinium", "アクチニウム", 227.0, None, 7, Block::F),
+    entry(90, "Th", "thorium", "トリウム", 232.04, None, 7, Block::F),
+    entry(91, "Pa", "protactinium", "プロトアクチニウム", 231.04, None, 7, Block::F),
+    entry(92, "U", "uranium", "ウラン", 238.03, None, 7, Block::F),
+    entry(93, "Np", "neptunium", "ネプツニウム", 237.0, None, 7, Block::F),
+    entry(94, "Pu", "plutonium", "プルトニウム", 244.0, None, 7, Block::F),
+    entry(95, "Am", "americium", "アメリシウム", 243.0, None, 7, Block::F),
+    entry(96, "Cm", "curium", "キュリウム", 247.0, None, 7, Block::F),
+    entry(97, "Bk", "berkelium", "バークリウム", 247.0, None, 7, Block::F),
+    entry(98, "Cf", "californium", "カリホルニウム", 251.0, None, 7, Block::F),
+    entry(99, "Es", "einsteinium", "アインスタイニウム", 252.0, None, 7, Block::F),
+    entry(100, "Fm", "fermium", "フェルミウム", 257.0, None, 7, Block::F),
+    entry(101, "Md", "mendelevium", "メンデレビウム", 258.0, None, 7, Block::F),
+    entry(102, "No", "nobelium", "ノーベリウム", 259.0, None, 7, Block::F),
+    entry(103, "Lr", "lawrencium", "ローレンシウム", 262.0, Some(3), 7, Block::D),
+    entry(104, "Rf", "rutherfordium", "ラザホージウム", 267.0, Some(4), 7, Block::D),
+    entry(105, "Db", "dubnium", "ドブニウム", 268.0, Some(5), 7, Block::D),
+    entry(106, "Sg", "seaborgium", "シーボーギウム", 269.0, Some(6), 7, Block::D),
+    entry(107, "Bh", "bohrium", "ボーリウム", 270.0, Some(7), 7, Block::D),
+    entry(108, "Hs", "hassium", "ハッシウム", 270.0, Some(8), 7, Block::D),
+    entry(109, "Mt", "meitnerium", "マイトネリウム", 278.0, Some(9), 7, Block::D),
+    entry(110, "Ds", "darmstadtium", "ダームスタチウム", 281.0, Some(10), 7, Block::D),
+    entry(111, "Rg", "roentgenium", "レントゲニウム", 282.0, Some(11), 7, Block::D),
+    entry(112, "Cn", "copernicium", "コペルニシウム", 285.0, Some(12), 7, Block::D),
+    entry(113, "Nh", "nihonium", "ニホニウム", 286.0, Some(13), 7, Block::P),
+    entry(114, "Fl", "flerovium", "フレロビウム", 289.0, Some(14), 7, Block::P),
+    entry(115, "Mc", "moscovium", "モスコビウム", 290.0, Some(15), 7, Block::P),
+    entry(116, "Lv", "livermorium", "リバモリウム", 293.0, Some(16), 7, Block::P),
+    entry(117, "Ts", "tennessine", "テネシン", 294.0, Some(17), 7, Block::P),
+    entry(118, "Og", "oganesson", "オガネソン", 294.0, Some(18), 7, Block::P),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_ordered_by_atomic_number() {
+        for (index, element) in elements().iter().enumerate() {
+            assert_eq!(usize::from(element.atomic_number), index + 1);
+        }
+    }
+
+    #[test]
+    fn looks_up_by_symbol_and_number() {
+        let iron = element("Fe").expect("iron should exist");
+        assert_eq!(iron.atomic_number, 26);
+        assert_eq!(iron.name_en, "iron");
+        assert_eq!(iron.name_ja, "鉄");
+        assert_eq!(iron.group, Some(8));
+        assert_eq!(iron.period, 4);
+        assert_eq!(iron.block, Block::D);
+
+        assert_eq!(
+            element_by_number(17).map(|element| element.symbol),
+            Some("Cl")
+        );
+        assert!(element("Xx").is_none());
+        assert!(element_by_number(0).is_none());
+        assert!(element_by_number(119).is_none());
+    }
+
+    #[test]
+    fn symbols_are_unique() {
+        let mut symbols: Vec<&str> = elements().iter().map(|element| element.symbol).collect();
+        symbols.sort_unstable();
+        symbols.dedup();
+        assert_eq!(symbols.len(), elements().len());
+    }
+}
@@ -1,8 +1,17 @@
 use rand::Rng;
 use rand::seq::SliceRandom;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-use crate::compound::Compound;
+use crate::composition::{self, MassComposition};
+use crate::compound::{Compound, Formula};
+use crate::functional_groups::FunctionalGroupKind;
+use crate::homologous_series::{HOMOLOGOUS_SERIES, HomologousSeries, series_with_general_formula};
+use crate::ionic::{self, IonicCompound};
+use crate::nomenclature;
+use crate::oxidation::OxidationNumber;
+use crate::reaction::PRECIPITATION_REACTIONS;
+use crate::smiles::Molecule;
+use crate::stereo::stereo_descriptors;
 
 /// Quiz type describing the relationship between prompt and answers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +20,68 @@ pub enum QuizMode {
     NameToStructure,
     /// Prompts with a skeletal structure and expects the compound name as the answer.
     StructureToName,
+    /// Prompts with a compound name and expects its molecular formula, e.g. `C2H6O`. Options
+    /// never share a molecular formula, so isomers do not appear together.
+    NameToMolecularFormula,
+    /// Prompts with a molecular formula and expects the compound name. Options never share
+    /// a molecular formula, so the prompt names one of them only.
+    MolecularFormulaToName,
+    /// Prompts with a compound name and expects its condensed formula, e.g. `CH3-CH2-OH`.
+    NameToCondensedFormula,
+    /// Prompts with a condensed formula and expects the compound name.
+    CondensedFormulaToName,
+    /// Prompts with the Japanese name (`local_name`) and expects the skeletal structure.
+    JapaneseNameToStructure,
+    /// Prompts with a skeletal structure and expects the Japanese name.
+    StructureToJapaneseName,
+    /// Prompts with the IUPAC name and expects the Japanese name, e.g. "ethanol" → エタノール.
+    NameToJapaneseName,
+    /// Prompts with the IUPAC name and expects the common name, e.g. "propan-2-ol" →
+    /// "isopropyl alcohol". Compounds whose common name is their IUPAC name are skipped.
+    IupacToCommonName,
+    /// Prompts with the common name and expects the IUPAC name, e.g. "acetic acid" →
+    /// "ethanoic acid".
+    CommonToIupacName,
+    /// Prompts with a compound name and offers only structures sharing its molecular formula.
+    IsomerToStructure,
+    /// Asks for the oxidation number of one element in a formula, e.g. Mn in `KMnO4`.
+    OxidationNumber,
+    /// Gives a cation and an anion and asks for the charge-balanced formula they form.
+    IonsToFormula,
+    /// Shows a randomly drawn structure (as SMILES) and asks for its systematic name. The
+    /// compounds passed to the generator are not used, so drills never run out.
+    NameDrill,
+    /// Shows a structure with a cis/trans double bond (as SMILES) and asks which geometric
+    /// isomer it is, e.g. maleic or fumaric acid.
+    GeometricIsomer,
+    /// Asks how many chiral centres a compound has, such as the α carbon of an amino acid.
+    ChiralCentres,
+    /// Asks for the mass percent of one element in a formula, e.g. C in `C2H6O`.
+    MassPercent,
+    /// States a compound's mass percentages and molar mass and asks for its molecular
+    /// formula, worked out through the empirical formula.
+    FormulaFromComposition,
+    /// Shows the unbalanced ionic equation of a metal-ion precipitation and asks for the
+    /// balanced one. The compounds passed to the generator are not used.
+    BalanceEquation,
+    /// Shows a structure and asks which functional groups it contains. Several options may
+    /// be correct, and answers earn partial credit through [`QuizItem::score`].
+    FunctionalGroups,
+    /// Shows a structure and asks which homologous series of
+    /// `dataset/organic_homologous_series.json` it belongs to, e.g. propyne → alkynes.
+    /// Series sharing its general formula come first among the distractors.
+    StructureToSeries,
+    /// Gives a general formula such as `CnH2n-2` and asks which compound fits it. No
+    /// distractor fits the formula, even through another series that shares it.
+    GeneralFormulaToCompound,
+}
+
+impl QuizMode {
+    /// Whether more than one option can be correct, so the answer is a set of options
+    /// submitted together rather than a single click.
+    pub fn allows_multiple_answers(self) -> bool {
+        matches!(self, Self::FunctionalGroups)
+    }
 }
 
 /// A single generated quiz question.
@@ -21,8 +92,44 @@ pub struct QuizItem {
     pub prompt: String,
     /// List of answer options. Length is always `option_count` passed to the generator.
     pub options: Vec<String>,
-    /// Index in `options` that contains the correct answer.
+    /// Index in `options` that contains the correct answer, or the first one when several
+    /// are correct.
     pub correct_index: usize,
+    /// Indices of every correct option in ascending order. Holds only `correct_index` unless
+    /// the mode [allows multiple answers](QuizMode::allows_multiple_answers).
+    pub correct_indices: Vec<usize>,
+    /// Index in the `compounds` given to [`generate_quiz`] of the compound the question is
+    /// about, or `None` when it is not one of them (name drills, and precipitates missing
+    /// from `compounds` when balancing equations).
+    pub compound_index: Option<usize>,
+}
+
+impl QuizItem {
+    pub fn is_correct(&self, index: usize) -> bool {
+        self.correct_indices.contains(&index)
+    }
+
+    /// Partial credit from 0 to 1 for the options in `selected`: each correct option chosen
+    /// earns a share, each wrong one takes a share away, and the result never drops below 0.
+    /// Choosing an option twice counts once.
+    pub fn score(&self, selected: &[usize]) -> f64 {
+        if self.correct_indices.is_empty() {
+            return 0.0;
+        }
+
+        let chosen: HashSet<usize> = selected
+            .iter()
+            .copied()
+            .filter(|index| *index < self.options.len())
+            .collect();
+        let hits = chosen
+            .iter()
+            .filter(|index| self.is_correct(**index))
+            .count();
+        let misses = chosen.len() - hits;
+
+        (hits as f64 - misses as f64).max(0.0) / self.correct_indices.len() as f64
+    }
 }
 
 #[derive(Debug, thiserror::Error, PartialEq, Eq)]
@@ -31,8 +138,34 @@ pub enum QuizError {
     NotEnoughCompounds { required: usize, available: usize },
     #[error("requires at least {required} unique options but only {unique} available")]
     InsufficientUniqueOptions { required: usize, unique: usize },
+    #[error("requires {required} isomers sharing a formula but the largest group has {largest}")]
+    NotEnoughIsomers { required: usize, largest: usize },
     #[error("option count must be at least 2")]
     OptionCountTooSmall,
+    #[error("no compound has an element whose oxidation number can be asked")]
+    NoOxidationNumbers,
+    #[error("no compound splits into a single cation and anion")]
+    NoIonicCompounds,
+    #[error("no compound has a cis/trans isomer among the compounds")]
+    NoGeometricIsomers,
+    #[error("no compound has a carbon skeleton given as SMILES")]
+    NoOrganicStructures,
+    #[error("no compound has a neutral formula of two or more elements")]
+    NoCompositions,
+    #[error("no carbon compound can be solved back from its rounded composition")]
+    NoMolecularFormulas,
+    #[error("no compound has functional groups that fit among the options")]
+    NoFunctionalGroups,
+    #[error("no compound belongs to a homologous series of the dataset")]
+    NoHomologousSeries,
+    #[error(
+        "requires at least {required} compounds with a Japanese name but only {available} have one"
+    )]
+    NotEnoughJapaneseNames { required: usize, available: usize },
+    #[error(
+        "requires at least {required} compounds with a distinct common name but only {available} have one"
+    )]
+    NotEnoughCommonNames { required: usize, available: usize },
 }
 
 /// Generates a quiz item for the given compounds and quiz mode.
@@ -45,6 +178,38 @@ pub enum QuizError {
 /// * Returns [`QuizError::NotEnoughCompounds`] if fewer than `option_count` compounds are available.
 /// * Returns [`QuizError::InsufficientUniqueOptions`] if the provided compounds do not contain
 ///   enough unique names or structures for the requested `option_count`.
+/// * Returns [`QuizError::NotEnoughIsomers`] in [`QuizMode::IsomerToStructure`] when no
+///   molecular formula is shared by `option_count` distinct compounds.
+/// * Returns [`QuizError::NoOxidationNumbers`] in [`QuizMode::OxidationNumber`] when no
+///   compound has an element with a single, whole-number oxidation number to ask about.
+/// * Returns [`QuizError::NoIonicCompounds`] in [`QuizMode::IonsToFormula`] when no compound
+///   splits into one kind of cation and one anion.
+/// * Returns [`QuizError::InsufficientUniqueOptions`] in [`QuizMode::NameDrill`] when not
+///   enough different names turn up among the random structures.
+/// * Returns [`QuizError::NoGeometricIsomers`] in [`QuizMode::GeometricIsomer`] when no
+///   compound with a written E/Z configuration has its geometric isomer among `compounds`.
+/// * Returns [`QuizError::NoOrganicStructures`] in [`QuizMode::ChiralCentres`] when no
+///   carbon compound has a parsable SMILES string.
+/// * Returns [`QuizError::NoCompositions`] in [`QuizMode::MassPercent`] when no compound has
+///   a parsable, neutral formula of at least two elements.
+/// * Returns [`QuizError::NoMolecularFormulas`] in [`QuizMode::FormulaFromComposition`] when
+///   no carbon compound's rounded percentages and molar mass solve back to its formula.
+/// * Returns [`QuizError::InsufficientUniqueOptions`] in [`QuizMode::BalanceEquation`] when
+///   too few wrong coefficient sets can be made for the requested `option_count`.
+/// * Returns [`QuizError::NotEnoughJapaneseNames`] in [`QuizMode::JapaneseNameToStructure`],
+///   [`QuizMode::StructureToJapaneseName`] and [`QuizMode::NameToJapaneseName`] when fewer
+///   than `option_count` compounds have a `local_name`.
+/// * Returns [`QuizError::NotEnoughCommonNames`] in [`QuizMode::IupacToCommonName`] and
+///   [`QuizMode::CommonToIupacName`] when fewer than `option_count` compounds have a common
+///   name that differs from their IUPAC name.
+/// * Returns [`QuizError::NoFunctionalGroups`] in [`QuizMode::FunctionalGroups`] when no
+///   compound has at least one functional group and fewer than `option_count` of them.
+/// * Returns [`QuizError::NoHomologousSeries`] in [`QuizMode::StructureToSeries`] and
+///   [`QuizMode::GeneralFormulaToCompound`] when no compound's `series_general_formula`
+///   names a series of the dataset that its molecular formula fits.
+/// * Returns [`QuizError::InsufficientUniqueOptions`] in
+///   [`QuizMode::GeneralFormulaToCompound`] when too few series members fall outside the
+///   asked general formula.
 pub fn generate_quiz<R: Rng + ?Sized>(
     rng: &mut R,
     compounds: &[Compound],
@@ -55,6 +220,41 @@ pub fn generate_quiz<R: Rng + ?Sized>(
         return Err(QuizError::OptionCountTooSmall);
     }
 
+    // Modes whose options are numbers, made-up formulas, groups or series rather than
+    // compounds build their own questions, so one eligible compound is enough. The rest draw
+    // every option from `compounds` below.
+    let compound_mode = match mode {
+        QuizMode::OxidationNumber => return oxidation_number_quiz(rng, compounds, option_count),
+        QuizMode::IonsToFormula => return ions_to_formula_quiz(rng, compounds, option_count),
+        QuizMode::NameDrill => return name_drill_quiz(rng, option_count),
+        QuizMode::GeometricIsomer => return geometric_isomer_quiz(rng, compounds, option_count),
+        QuizMode::ChiralCentres => return chiral_centre_quiz(rng, compounds, option_count),
+        QuizMode::MassPercent => return mass_percent_quiz(rng, compounds, option_count),
+        QuizMode::FormulaFromComposition => {
+            return formula_from_composition_quiz(rng, compounds, option_count);
+        }
+        QuizMode::BalanceEquation => return balance_equation_quiz(rng, compounds, option_count),
+        QuizMode::FunctionalGroups => return functional_group_quiz(rng, compounds, option_count),
+        QuizMode::StructureToSeries => {
+            return structure_to_series_quiz(rng, compounds, option_count);
+        }
+        QuizMode::GeneralFormulaToCompound => {
+            return general_formula_quiz(rng, compounds, option_count);
+        }
+        QuizMode::NameToStructure => CompoundMode::NameToStructure,
+        QuizMode::StructureToName => CompoundMode::StructureToName,
+        QuizMode::NameToMolecularFormula => CompoundMode::NameToMolecularFormula,
+        QuizMode::MolecularFormulaToName => CompoundMode::MolecularFormulaToName,
+        QuizMode::NameToCondensedFormula => CompoundMode::NameToCondensedFormula,
+        QuizMode::CondensedFormulaToName => CompoundMode::CondensedFormulaToName,
+        QuizMode::JapaneseNameToStructure => CompoundMode::JapaneseNameToStructure,
+        QuizMode::StructureToJapaneseName => CompoundMode::StructureToJapaneseName,
+        QuizMode::NameToJapaneseName => CompoundMode::NameToJapaneseName,
+        QuizMode::IupacToCommonName => CompoundMode::IupacToCommonName,
+        QuizMode::CommonToIupacName => CompoundMode::CommonToIupacName,
+        QuizMode::IsomerToStructure => CompoundMode::IsomerToStructure,
+    };
+
     if compounds.len() < option_count {
         return Err(QuizError::NotEnoughCompounds {
             required: option_count,
@@ -62,69 +262,1203 @@ pub fn generate_quiz<R: Rng + ?Sized>(
         });
     }
 
+    if matches!(
+        compound_mode,
+        CompoundMode::JapaneseNameToStructure
+            | CompoundMode::StructureToJapaneseName
+            | CompoundMode::NameToJapaneseName
+    ) {
+        let available = compounds
+            .iter()
+            .filter(|compound| japanese_name(compound).is_some())
+            .count();
+        if available < option_count {
+            return Err(QuizError::NotEnoughJapaneseNames {
+                required: option_count,
+                available,
+            });
+        }
+    }
+
+    if matches!(
+        compound_mode,
+        CompoundMode::IupacToCommonName | CompoundMode::CommonToIupacName
+    ) {
+        let available = compounds
+            .iter()
+            .filter(|compound| distinct_common_name(compound).is_some())
+            .count();
+        if available < option_count {
+            return Err(QuizError::NotEnoughCommonNames {
+                required: option_count,
+                available,
+            });
+        }
+    }
+
+    let constitutions: Vec<Option<(String, bool)>> = compounds.iter().map(constitution).collect();
+    let configured: HashSet<&str> = compounds
+        .iter()
+        .zip(&constitutions)
+        .filter(|(compound, _)| is_eligible(compound, compound_mode))
+        .filter_map(|(_, constitution)| match constitution {
+            Some((smiles, true)) => Some(smiles.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut seen_equivalents = HashSet::new();
+    let mut seen_keys = HashSet::new();
+    let mut unique_indices = Vec::new();
+
+    // Options must differ both as text and chemically: "CH3OH" and "CH3-OH" describe the
+    // same substance and would otherwise appear as two distinct choices. An entry without a
+    // configuration is left out when another writes one for the same constitution, since
+    // "but-2-ene" would be a second defensible answer beside "(Z)-but-2-ene".
+    for (idx, compound) in compounds.iter().enumerate() {
+        if !is_eligible(compound, compound_mode) {
+            continue;
+        }
+        if let Some((smiles, false)) = &constitutions[idx]
+            && configured.contains(smiles.as_str())
+        {
+            continue;
+        }
+        let label = option_text(compound, compound_mode);
+        let equivalence = compound.equivalence_key();
+        let key = distinct_key(compound, compound_mode);
+        if seen.contains(&label)
+            || seen_equivalents.contains(&equivalence)
+            || key.as_ref().is_some_and(|key| seen_keys.contains(key))
+        {
+            continue;
+        }
+
+        seen.insert(label);
+        seen_equivalents.insert(equivalence);
+        seen_keys.extend(key);
+        unique_indices.push(idx);
+    }
+
+    if unique_indices.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: unique_indices.len(),
+        });
+    }
+
+    let mut selected = if compound_mode == CompoundMode::IsomerToStructure {
+        isomer_group(rng, compounds, unique_indices, option_count)?
+    } else {
+        unique_indices
+    };
+    selected.shuffle(rng);
+    selected.truncate(option_count);
+
+    let correct_compound_index = selected[0];
+
+    let mut options: Vec<(usize, String)> = selected
+        .iter()
+        .map(|idx| (*idx, option_text(&compounds[*idx], compound_mode)))
+        .collect();
+
+    options.shuffle(rng);
+
+    let correct_index = options
+        .iter()
+        .position(|(idx, _)| *idx == correct_compound_index)
+        .expect("correct option must exist after shuffle");
+
+    let prompt = prompt_text(&compounds[correct_compound_index], compound_mode);
+
+    Ok(QuizItem {
+        mode,
+        prompt,
+        options: options.into_iter().map(|(_, text)| text).collect(),
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(correct_compound_index),
+    })
+}
+
+/// Canonical SMILES of `compound` without stereo descriptors, and whether its SMILES writes
+/// a configuration (R/S or E/Z) as well.
+fn constitution(compound: &Compound) -> Option<(String, bool)> {
+    let key = compound.structure_key()?;
+    Some(match key.split_once(' ') {
+        Some((smiles, _)) => (smiles.to_string(), true),
+        None => (key, false),
+    })
+}
+
+/// Modes whose options are compounds of the list, each shown by a name, structure or
+/// formula. [`generate_quiz`] hands the other modes to their own generators first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompoundMode {
+    NameToStructure,
+    StructureToName,
+    NameToMolecularFormula,
+    MolecularFormulaToName,
+    NameToCondensedFormula,
+    CondensedFormulaToName,
+    JapaneseNameToStructure,
+    StructureToJapaneseName,
+    NameToJapaneseName,
+    IupacToCommonName,
+    CommonToIupacName,
+    IsomerToStructure,
+}
+
+/// Whether `compound` has what `mode` asks about: a parsable molecular formula, a condensed
+/// formula, a Japanese name, or a common name of its own.
+fn is_eligible(compound: &Compound, mode: CompoundMode) -> bool {
+    match mode {
+        CompoundMode::NameToMolecularFormula | CompoundMode::MolecularFormulaToName => {
+            compound.normalized_formula().is_some()
+        }
+        CompoundMode::NameToCondensedFormula | CompoundMode::CondensedFormulaToName => {
+            !compound.skeletal_formula.trim().is_empty()
+        }
+        CompoundMode::JapaneseNameToStructure
+        | CompoundMode::StructureToJapaneseName
+        | CompoundMode::NameToJapaneseName => japanese_name(compound).is_some(),
+        CompoundMode::IupacToCommonName | CompoundMode::CommonToIupacName => {
+            distinct_common_name(compound).is_some()
+        }
+        CompoundMode::NameToStructure
+        | CompoundMode::StructureToName
+        | CompoundMode::IsomerToStructure => true,
+    }
+}
+
+/// Key that must also differ between the options, so the prompt fits one of them only: the
+/// Hill formula in the molecular-formula modes, so isomers never appear together, the
+/// condensed formula without spaces and bond dashes, so two structures written alike never
+/// do, and the common name ignoring case in the common-name modes.
+fn distinct_key(compound: &Compound, mode: CompoundMode) -> Option<String> {
+    match mode {
+        CompoundMode::NameToMolecularFormula | CompoundMode::MolecularFormulaToName => {
+            compound.normalized_formula()
+        }
+        CompoundMode::NameToCondensedFormula | CompoundMode::CondensedFormulaToName => Some(
+            compound
+                .skeletal_formula
+                .chars()
+                .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '–' | '—'))
+                .collect(),
+        ),
+        CompoundMode::IupacToCommonName | CompoundMode::CommonToIupacName => {
+            distinct_common_name(compound).map(str::to_lowercase)
+        }
+        CompoundMode::NameToStructure
+        | CompoundMode::StructureToName
+        | CompoundMode::IsomerToStructure
+        | CompoundMode::JapaneseNameToStructure
+        | CompoundMode::StructureToJapaneseName
+        | CompoundMode::NameToJapaneseName => None,
+    }
+}
+
+/// Trimmed `local_name`, or `None` when it is missing or blank.
+fn japanese_name(compound: &Compound) -> Option<&str> {
+    compound
+        .local_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
+
+/// Trimmed `common_name`, or `None` when it is missing, blank or only the IUPAC name again
+/// (ignoring case and spacing).
+fn distinct_common_name(compound: &Compound) -> Option<&str> {
+    let normalize = |name: &str| name.split_whitespace().collect::<String>().to_lowercase();
+    compound
+        .common_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && normalize(name) != normalize(&compound.iupac_name))
+}
+
+fn prompt_text(compound: &Compound, mode: CompoundMode) -> String {
+    match mode {
+        CompoundMode::NameToStructure
+        | CompoundMode::IsomerToStructure
+        | CompoundMode::NameToMolecularFormula
+        | CompoundMode::NameToCondensedFormula => compound.english_label(),
+        CompoundMode::MolecularFormulaToName => compound.molecular_formula.trim().to_string(),
+        CompoundMode::CondensedFormulaToName => compound.skeletal_formula.trim().to_string(),
+        CompoundMode::JapaneseNameToStructure => {
+            japanese_name(compound).unwrap_or_default().to_string()
+        }
+        CompoundMode::NameToJapaneseName | CompoundMode::IupacToCommonName => {
+            compound.iupac_name.clone()
+        }
+        CompoundMode::CommonToIupacName => distinct_common_name(compound)
+            .unwrap_or_default()
+            .to_string(),
+        CompoundMode::StructureToName | CompoundMode::StructureToJapaneseName => {
+            compound.display_structure()
+        }
+    }
+}
+
+fn option_text(compound: &Compound, mode: CompoundMode) -> String {
+    match mode {
+        CompoundMode::NameToStructure
+        | CompoundMode::IsomerToStructure
+        | CompoundMode::JapaneseNameToStructure => compound.display_structure(),
+        CompoundMode::StructureToJapaneseName | CompoundMode::NameToJapaneseName => {
+            japanese_name(compound).unwrap_or_default().to_string()
+        }
+        CompoundMode::IupacToCommonName => distinct_common_name(compound)
+            .unwrap_or_default()
+            .to_string(),
+        CompoundMode::CommonToIupacName => compound.iupac_name.clone(),
+        CompoundMode::NameToMolecularFormula => compound.molecular_formula.trim().to_string(),
+        CompoundMode::NameToCondensedFormula => compound.skeletal_formula.trim().to_string(),
+        CompoundMode::StructureToName
+        | CompoundMode::MolecularFormulaToName
+        | CompoundMode::CondensedFormulaToName => compound.english_label(),
+    }
+}
+
+/// Lowest and highest oxidation numbers offered as filler distractors.
+const OXIDATION_NUMBER_RANGE: std::ops::RangeInclusive<i32> = -4..=8;
+
+/// Asks for the oxidation number of an element that had to be worked out from the charge
+/// balance (not one fixed by a rule such as O −2) in a compound of at least two elements.
+///
+/// Distractors are first drawn from the states the same element takes elsewhere in
+/// `compounds` (Mn +2 and +4 next to Mn +7), then from the usual range of oxidation numbers.
+fn oxidation_number_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen_formulas = HashSet::new();
+    let mut targets: Vec<(usize, String, String, i32)> = Vec::new();
+
+    for (index, compound) in compounds.iter().enumerate() {
+        let formula = compound.molecular_formula.trim();
+        let Some(states) = compound.oxidation_states() else {
+            continue;
+        };
+        if !seen_formulas.insert(formula.to_string()) {
+            continue;
+        }
+
+        let elements: HashSet<&str> = states
+            .assignments()
+            .iter()
+            .map(|assignment| assignment.element.as_str())
+            .collect();
+        if elements.len() < 2 {
+            continue;
+        }
+
+        for assignment in states.assignments() {
+            if assignment.by_rule {
+                continue;
+            }
+            if let Some(value) = states
+                .state_of(&assignment.element)
+                .and_then(|state| state.as_integer())
+            {
+                targets.push((
+                    index,
+                    formula.to_string(),
+                    assignment.element.clone(),
+                    value,
+                ));
+            }
+        }
+    }
+
+    let (compound_index, formula, element, value) = targets
+        .choose(rng)
+        .cloned()
+        .ok_or(QuizError::NoOxidationNumbers)?;
+
+    let mut related: Vec<i32> = targets
+        .iter()
+        .filter(|(_, _, other, _)| *other == element)
+        .map(|(_, _, _, state)| *state)
+        .collect();
+    related.shuffle(rng);
+    let mut filler: Vec<i32> = OXIDATION_NUMBER_RANGE.collect();
+    filler.shuffle(rng);
+
+    let mut values = vec![value];
+    for candidate in related.into_iter().chain(filler) {
+        if values.len() == option_count {
+            break;
+        }
+        if !values.contains(&candidate) {
+            values.push(candidate);
+        }
+    }
+
+    if values.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: values.len(),
+        });
+    }
+
+    values.shuffle(rng);
+    let correct_index = values
+        .iter()
+        .position(|candidate| *candidate == value)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::OxidationNumber,
+        prompt: format!("What is the oxidation number of {element} in {formula}?"),
+        options: values
+            .into_iter()
+            .map(|value| OxidationNumber::from(value).to_string())
+            .collect(),
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(compound_index),
+    })
+}
+
+/// Largest ion count tried when writing unbalanced distractor formulas.
+const MAX_DISTRACTOR_ION_COUNT: u32 = 4;
+
+/// Gives the ions of a salt with one kind of cation (`Fe^{3+}` and `SO4^{2-}`) and asks for
+/// the formula they form (`Fe2(SO4)3`). Distractors combine the same ions in ratios that do
+/// not balance the charges.
+fn ions_to_formula_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let salts: Vec<(usize, IonicCompound)> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, compound)| Some((index, compound.ionic_composition()?)))
+        .filter(|(_, salt)| salt.cations().len() == 1)
+        .map(|(index, salt)| (index, salt.with_hydrate_water(0)))
+        .filter(|(_, salt)| seen.insert(salt.formula()))
+        .collect();
+
+    let (compound_index, salt) = salts.choose(rng).ok_or(QuizError::NoIonicCompounds)?;
+    let (cation, _) = &salt.cations()[0];
+    let (anion, _) = salt.anion();
+    let answer = salt.formula();
+
+    let mut ratios: Vec<(u32, u32)> = (1..=MAX_DISTRACTOR_ION_COUNT)
+        .flat_map(|cations| (1..=MAX_DISTRACTOR_ION_COUNT).map(move |anions| (cations, anions)))
+        .filter(|(cations, anions)| {
+            cation.charge * *cations as i32 + anion.charge * *anions as i32 != 0
+        })
+        .collect();
+    ratios.shuffle(rng);
+
+    let mut options = vec![answer.clone()];
+    for (cations, anions) in ratios {
+        if options.len() == option_count {
+            break;
+        }
+        let formula = ionic::unbalanced_formula(cation, cations, anion, anions);
+        if !options.contains(&formula) {
+            options.push(formula);
+        }
+    }
+
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+
+    options.shuffle(rng);
+    let correct_index = options
+        .iter()
+        .position(|option| *option == answer)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::IonsToFormula,
+        prompt: format!(
+            "Which formula do {} and {} form?",
+            cation.notation(),
+            anion.notation()
+        ),
+        options,
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(*compound_index),
+    })
+}
+
+/// Random structures drawn while looking for distractor names.
+const NAME_DRILL_ATTEMPTS: usize = 1000;
+
+/// Draws a random structure and offers its systematic name among names of other random
+/// structures, preferring isomers so the molecular formula gives nothing away.
+fn name_drill_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let named = |smiles: String| {
+        let molecule = Molecule::parse(&smiles).ok()?;
+        let name = nomenclature::name_molecule(&molecule).ok()?;
+        Some((smiles, name, molecule.formula()))
+    };
+
+    let (prompt, answer, formula) = std::iter::repeat_with(|| nomenclature::random_structure(rng))
+        .find_map(named)
+        .expect("random structures can always be named");
+
+    let mut isomers = vec![answer.clone()];
+    let mut others = Vec::new();
+    for _ in 0..NAME_DRILL_ATTEMPTS {
+        if isomers.len() == option_count {
+            break;
+        }
+        let Some((_, name, candidate_formula)) = named(nomenclature::random_structure(rng)) else {
+            continue;
+        };
+        if isomers.contains(&name) || others.contains(&name) {
+            continue;
+        }
+        if candidate_formula == formula {
+            isomers.push(name);
+        } else {
+            others.push(name);
+        }
+    }
+
+    let mut options = isomers;
+    others.shuffle(rng);
+    let missing = option_count.saturating_sub(options.len());
+    options.extend(others.into_iter().take(missing));
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+
+    options.shuffle(rng);
+    let correct_index = options
+        .iter()
+        .position(|option| *option == answer)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::NameDrill,
+        prompt,
+        options,
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: None,
+    })
+}
+
+/// Shows a structure whose double bond has a written E/Z configuration and offers its name
+/// next to the names of its geometric isomers, then of other cis/trans compounds, then of
+/// anything else. Entries with the same constitution but no configuration are left out,
+/// since their names would also fit the structure.
+fn geometric_isomer_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let structures: Vec<(usize, &str, Molecule)> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, compound)| {
+            let smiles = compound.smiles.as_deref()?.trim();
+            let molecule = Molecule::parse(smiles).ok()?;
+            seen.insert(compound.equivalence_key())
+                .then_some((index, smiles, molecule))
+        })
+        .collect();
+    let configured: Vec<bool> = structures
+        .iter()
+        .map(|(_, _, molecule)| {
+            stereo_descriptors(molecule)
+                .double_bonds
+                .iter()
+                .any(|bond| bond.geometry.is_some())
+        })
+        .collect();
+    let constitutions: Vec<String> = structures
+        .iter()
+        .map(|(_, _, molecule)| molecule.canonical_smiles())
+        .collect();
+
+    let is_isomer = |left: usize, right: usize| {
+        left != right
+            && configured[left]
+            && configured[right]
+            && constitutions[left] == constitutions[right]
+    };
+    let targets: Vec<usize> = (0..structures.len())
+        .filter(|&index| (0..structures.len()).any(|other| is_isomer(index, other)))
+        .collect();
+    let &target = targets.choose(rng).ok_or(QuizError::NoGeometricIsomers)?;
+
+    let mut isomers = Vec::new();
+    let mut stereo_others = Vec::new();
+    let mut rest = Vec::new();
+    for (index, (compound_index, _, _)) in structures.iter().enumerate() {
+        if index == target {
+            continue;
+        }
+        let label = compounds[*compound_index].english_label();
+        if is_isomer(target, index) {
+            isomers.push(label);
+        } else if constitutions[index] == constitutions[target] {
+            continue;
+        } else if configured[index] {
+            stereo_others.push(label);
+        } else {
+            rest.push(label);
+        }
+    }
+    isomers.shuffle(rng);
+    stereo_others.shuffle(rng);
+    rest.shuffle(rng);
+
+    let (compound_index, smiles, _) = &structures[target];
+    let answer = compounds[*compound_index].english_label();
+    let mut options = vec![answer.clone()];
+    for label in isomers.into_iter().chain(stereo_others).chain(rest) {
+        if options.len() == option_count {
+            break;
+        }
+        if !options.contains(&label) {
+            options.push(label);
+        }
+    }
+
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+
+    options.shuffle(rng);
+    let correct_index = options
+        .iter()
+        .position(|option| *option == answer)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::GeometricIsomer,
+        prompt: smiles.to_string(),
+        options,
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(*compound_index),
+    })
+}
+
+/// Asks for the number of chiral centres in a carbon compound, counted from the SMILES
+/// graph whether or not the configuration is written. Distractors are first drawn from the
+/// counts of the other compounds, then from small numbers next to the answer.
+fn chiral_centre_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let targets: Vec<(usize, String, usize)> = compounds
+        .iter()
+        .enumerate()
+        .filter(|(_, compound)| compound.descriptors().is_some())
+        .filter_map(|(index, compound)| {
+            let centres = compound.stereo_descriptors()?.centres.len();
+            let label = compound.english_label();
+            seen.insert(label.clone())
+                .then_some((index, label, centres))
+        })
+        .collect();
+
+    let (compound_index, label, count) = targets
+        .choose(rng)
+        .cloned()
+        .ok_or(QuizError::NoOrganicStructures)?;
+
+    let mut related: Vec<usize> = targets.iter().map(|(_, _, centres)| *centres).collect();
+    related.shuffle(rng);
+    let mut filler: Vec<usize> = (0..count + option_count).collect();
+    filler.shuffle(rng);
+
+    let mut values = vec![count];
+    for candidate in related.into_iter().chain(filler) {
+        if values.len() == option_count {
+            break;
+        }
+        if !values.contains(&candidate) {
+            values.push(candidate);
+        }
+    }
+
+    values.shuffle(rng);
+    let correct_index = values
+        .iter()
+        .position(|candidate| *candidate == count)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::ChiralCentres,
+        prompt: format!("How many chiral centres does {label} have?"),
+        options: values.into_iter().map(|value| value.to_string()).collect(),
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(compound_index),
+    })
+}
+
+/// Percentage points that must separate two mass-percent options.
+const MIN_PERCENT_GAP: f64 = 1.0;
+
+/// Asks for the mass percent of one element in a neutral compound of at least two elements.
+///
+/// Distractors are the usual slips first (the element's share of the atoms rather than of
+/// the mass, and the shares of the other elements), then the share of the same element in
+/// other compounds, then values 5, 10, … points either side. Options within one point of
+/// an earlier option are skipped.
+fn mass_percent_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let targets: Vec<(usize, String, MassComposition)> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, compound)| {
+            let formula = compound.formula().ok()?;
+            if formula.charge() != 0
+                || formula.elements().len() < 2
+                || !seen.insert(formula.hill_notation())
+            {
+                return None;
+            }
+            Some((
+                index,
+                compound.molecular_formula.trim().to_string(),
+                composition::mass_composition(&formula),
+            ))
+        })
+        .collect();
+
+    let (compound_index, formula, composition) =
+        targets.choose(rng).ok_or(QuizError::NoCompositions)?;
+    let share = composition
+        .shares()
+        .choose(rng)
+        .expect("targets have at least two elements");
+    let value = share.mass_percent;
+
+    let atoms: u32 = composition.shares().iter().map(|other| other.atoms).sum();
+    let mut slips: Vec<f64> = composition
+        .shares()
+        .iter()
+        .filter(|other| other.element != share.element)
+        .map(|other| other.mass_percent)
+        .chain([f64::from(share.atoms) / f64::from(atoms) * 100.0])
+        .collect();
+    slips.shuffle(rng);
+    let mut related: Vec<f64> = targets
+        .iter()
+        .filter_map(|(_, _, other)| other.percent_of(&share.element))
+        .collect();
+    related.shuffle(rng);
+    let filler = (1..=2 * option_count)
+        .flat_map(|step| [-1.0, 1.0].map(|sign| value + sign * 5.0 * step as f64))
+        .filter(|candidate| *candidate > 0.0 && *candidate < 100.0);
+
+    let mut values = vec![value];
+    for candidate in slips.into_iter().chain(related).chain(filler) {
+        if values.len() == option_count {
+            break;
+        }
+        if values
+            .iter()
+            .all(|existing| (existing - candidate).abs() >= MIN_PERCENT_GAP)
+        {
+            values.push(candidate);
+        }
+    }
+
+    if values.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: values.len(),
+        });
+    }
+
+    values.shuffle(rng);
+    let correct_index = values
+        .iter()
+        .position(|candidate| *candidate == value)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::MassPercent,
+        prompt: format!(
+            "What is the mass percent of {} in {formula}?",
+            share.element
+        ),
+        options: values
+            .into_iter()
+            .map(|value| format!("{value:.1}%"))
+            .collect(),
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(*compound_index),
+    })
+}
+
+/// Largest multiple of the empirical formula offered as a distractor.
+const MAX_FORMULA_MULTIPLE: u32 = 6;
+
+/// Figures stated in a composition problem with the formulas the solver reaches from them.
+struct CompositionProblem {
+    compound_index: usize,
+    percentages: Vec<(String, f64)>,
+    molar_mass: f64,
+    empirical: Formula,
+    solved: Formula,
+}
+
+/// States the mass percentages (to one decimal place) and molar mass of a neutral carbon
+/// compound and asks for its molecular formula in Hill notation. The answer is the one the
+/// composition solver reaches from the stated figures, and compounds whose figures do not
+/// lead back to their own formula are skipped.
+///
+/// Distractors are other multiples of the empirical formula, the empirical formula itself
+/// first and then those nearest the answer, followed by the formulas of other compounds.
+fn formula_from_composition_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let targets: Vec<CompositionProblem> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(compound_index, compound)| {
+            let formula = compound.formula().ok()?;
+            if formula.charge() != 0
+                || formula.hydrate_water() > 0
+                || formula.count("C") == 0
+                || formula.elements().len() < 2
+                || !seen.insert(formula.hill_notation())
+            {
+                return None;
+            }
+
+            let percentages = composition::mass_composition(&formula).rounded();
+            let molar_mass = (formula.molar_mass() * 10.0).round() / 10.0;
+            let empirical = composition::empirical_formula_from_percentages(&percentages).ok()?;
+            let solved =
+                composition::molecular_formula_from_empirical(&empirical, molar_mass).ok()?;
+            solved
+                .same_composition(&formula)
+                .then_some(CompositionProblem {
+                    compound_index,
+                    percentages,
+                    molar_mass,
+                    empirical,
+                    solved,
+                })
+        })
+        .collect();
+
+    let CompositionProblem {
+        compound_index,
+        percentages,
+        molar_mass,
+        empirical,
+        solved,
+    } = targets.choose(rng).ok_or(QuizError::NoMolecularFormulas)?;
+    let answer = solved.hill_notation();
+    let multiple = solved.atom_count() / empirical.atom_count();
+
+    let mut multiples: Vec<u32> = (2..=MAX_FORMULA_MULTIPLE)
+        .filter(|factor| *factor != multiple)
+        .collect();
+    multiples.sort_by_key(|factor| (factor.abs_diff(multiple), *factor));
+    let scaled = (multiple != 1)
+        .then_some(1)
+        .into_iter()
+        .chain(multiples)
+        .map(|factor| composition::multiply(empirical, factor).hill_notation());
+    let mut others: Vec<String> = targets
+        .iter()
+        .map(|other| other.solved.hill_notation())
+        .collect();
+    others.shuffle(rng);
+
+    let mut options = vec![answer.clone()];
+    for candidate in scaled.chain(others) {
+        if options.len() == option_count {
+            break;
+        }
+        if !options.contains(&candidate) {
+            options.push(candidate);
+        }
+    }
+
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+
+    options.shuffle(rng);
+    let correct_index = options
+        .iter()
+        .position(|option| *option == answer)
+        .expect("correct option must exist after shuffle");
+
+    let mut parts: Vec<String> = percentages
+        .iter()
+        .map(|(element, percent)| format!("{percent:.1}% {element}"))
+        .collect();
+    let last = parts.pop().expect("targets have at least two elements");
+
+    Ok(QuizItem {
+        mode: QuizMode::FormulaFromComposition,
+        prompt: format!(
+            "A compound is {} and {last} by mass and has a molar mass of {molar_mass:.1} g/mol. \
+             What is its molecular formula?",
+            parts.join(", ")
+        ),
+        options,
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(*compound_index),
+    })
+}
+
+/// Changes made to one coefficient of the balanced equation to write a distractor.
+const COEFFICIENT_CHANGES: [i64; 4] = [1, -1, 2, -2];
+
+/// Picks a precipitation reaction from the metal-ion dataset and asks for its balanced
+/// equation. The answer comes from the balancer; distractors are the unbalanced skeleton,
+/// when it differs from the answer, then the answer with one coefficient changed. The
+/// question is about the first of `compounds` with the precipitate's formula, if any.
+fn balance_equation_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let reaction = PRECIPITATION_REACTIONS
+        .choose(rng)
+        .expect("the precipitation table is not empty");
+    let skeleton = reaction.equation().skeleton();
+    let balanced = skeleton
+        .balanced()
+        .expect("built-in precipitation reactions balance");
+    let answer = balanced.to_string();
+    let coefficients = balanced.coefficients();
+
+    let mut changed: Vec<Vec<u32>> = (0..coefficients.len())
+        .flat_map(|index| COEFFICIENT_CHANGES.map(|change| (index, change)))
+        .filter_map(|(index, change)| {
+            let mut candidate = coefficients.clone();
+            candidate[index] = u32::try_from(i64::from(candidate[index]) + change)
+                .ok()
+                .filter(|coefficient| *coefficient > 0)?;
+            Some(candidate)
+        })
+        .collect();
+    changed.shuffle(rng);
+
+    let mut options = vec![answer.clone()];
+    for candidate in std::iter::once(skeleton.coefficients()).chain(changed) {
+        if options.len() == option_count {
+            break;
+        }
+        let equation = balanced.with_coefficients(&candidate);
+        let text = equation.to_string();
+        if !equation.is_balanced() && !options.contains(&text) {
+            options.push(text);
+        }
+    }
+
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+
+    options.shuffle(rng);
+    let correct_index = options
+        .iter()
+        .position(|option| *option == answer)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::BalanceEquation,
+        prompt: format!(
+            "{} with {} precipitates {}. Balance the equation: {skeleton}",
+            reaction.ion, reaction.reagent, reaction.precipitate
+        ),
+        options,
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: compounds
+            .iter()
+            .position(|compound| compound.molecular_formula.trim() == reaction.precipitate),
+    })
+}
+
+/// Asks which functional groups a compound contains, recognised from its SMILES or else
+/// from its `functional_groups` labels. Every group of the compound is an option, and the
+/// rest are other groups of the dataset drawn at random, so compounds need at least one
+/// group and fewer than `option_count` of them.
+fn functional_group_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let targets: Vec<(usize, Vec<FunctionalGroupKind>)> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, compound)| {
+            let kinds = compound.functional_group_kinds();
+            (!kinds.is_empty() && kinds.len() < option_count).then_some((index, kinds))
+        })
+        .filter(|(index, _)| seen.insert(compounds[*index].display_structure()))
+        .collect();
+
+    let (compound_index, kinds) = targets.choose(rng).ok_or(QuizError::NoFunctionalGroups)?;
+
+    let mut distractors: Vec<FunctionalGroupKind> = FunctionalGroupKind::ALL
+        .into_iter()
+        .filter(|kind| !kinds.contains(kind))
+        .collect();
+    distractors.shuffle(rng);
+
+    let mut options: Vec<FunctionalGroupKind> = kinds.clone();
+    options.extend(distractors.into_iter().take(option_count - kinds.len()));
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+    options.shuffle(rng);
+
+    let correct_indices: Vec<usize> = options
+        .iter()
+        .enumerate()
+        .filter(|(_, kind)| kinds.contains(kind))
+        .map(|(index, _)| index)
+        .collect();
+
+    Ok(QuizItem {
+        mode: QuizMode::FunctionalGroups,
+        prompt: format!(
+            "Which functional groups does {} contain? Select all that apply.",
+            compounds[*compound_index].display_structure()
+        ),
+        options: options
+            .into_iter()
+            .map(|kind| kind.name_en().to_string())
+            .collect(),
+        correct_index: correct_indices[0],
+        correct_indices,
+        compound_index: Some(*compound_index),
+    })
+}
+
+/// Asks which homologous series a compound belongs to. Series with the same general formula
+/// come first among the distractors (alkenes for a cycloalkane, esters for a carboxylic
+/// acid), then the other series at random.
+fn structure_to_series_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
+    let mut seen = HashSet::new();
+    let targets: Vec<(usize, &HomologousSeries)> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, compound)| Some((index, compound.homologous_series()?)))
+        .filter(|(index, _)| seen.insert(compounds[*index].display_structure()))
+        .collect();
+
+    let (compound_index, series) = targets.choose(rng).ok_or(QuizError::NoHomologousSeries)?;
+
+    let mut same_formula = Vec::new();
+    let mut rest = Vec::new();
+    for other in HOMOLOGOUS_SERIES.iter() {
+        if other.name_en == series.name_en {
+            continue;
+        }
+        if other.general_formula == series.general_formula {
+            same_formula.push(other.name_en.as_str());
+        } else {
+            rest.push(other.name_en.as_str());
+        }
+    }
+    same_formula.shuffle(rng);
+    rest.shuffle(rng);
+
+    let mut options = vec![series.name_en.as_str()];
+    options.extend(
+        same_formula
+            .into_iter()
+            .chain(rest)
+            .take(option_count.saturating_sub(1)),
+    );
+    if options.len() < option_count {
+        return Err(QuizError::InsufficientUniqueOptions {
+            required: option_count,
+            unique: options.len(),
+        });
+    }
+
+    options.shuffle(rng);
+    let correct_index = options
+        .iter()
+        .position(|name| *name == series.name_en)
+        .expect("correct option must exist after shuffle");
+
+    Ok(QuizItem {
+        mode: QuizMode::StructureToSeries,
+        prompt: format!(
+            "Which homologous series does {} belong to?",
+            compounds[*compound_index].display_structure()
+        ),
+        options: options.into_iter().map(str::to_string).collect(),
+        correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(*compound_index),
+    })
+}
+
+/// Asks which compound fits the general formula of a series one of them belongs to. The
+/// distractors are other series members that fit no series with that general formula, those
+/// with as many carbons as the answer first (propene and propane next to propyne).
+fn general_formula_quiz<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    option_count: usize,
+) -> Result<QuizItem, QuizError> {
     let mut seen = HashSet::new();
-    let mut unique_indices = Vec::new();
+    let members: Vec<(usize, String, Formula, &HomologousSeries)> = compounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, compound)| {
+            let series = compound.homologous_series()?;
+            Some((
+                index,
+                compound.english_label(),
+                compound.formula().ok()?,
+                series,
+            ))
+        })
+        .filter(|(_, label, _, _)| seen.insert(label.clone()))
+        .collect();
 
-    for (idx, compound) in compounds.iter().enumerate() {
-        let label = match mode {
-            QuizMode::NameToStructure => compound.display_structure(),
-            QuizMode::StructureToName => compound.english_label(),
-        };
+    let (compound_index, answer, answer_formula, series) =
+        members.choose(rng).ok_or(QuizError::NoHomologousSeries)?;
+    let siblings = series_with_general_formula(&series.general_formula);
 
-        if seen.insert(label) {
-            unique_indices.push(idx);
+    let mut same_carbons = Vec::new();
+    let mut rest = Vec::new();
+    for (_, label, formula, _) in &members {
+        if siblings.iter().any(|sibling| sibling.fits(formula)) {
+            continue;
+        }
+        if formula.count("C") == answer_formula.count("C") {
+            same_carbons.push(label.clone());
+        } else {
+            rest.push(label.clone());
         }
     }
+    same_carbons.shuffle(rng);
+    rest.shuffle(rng);
 
-    if unique_indices.len() < option_count {
+    let mut options = vec![answer.clone()];
+    options.extend(
+        same_carbons
+            .into_iter()
+            .chain(rest)
+            .take(option_count.saturating_sub(1)),
+    );
+    if options.len() < option_count {
         return Err(QuizError::InsufficientUniqueOptions {
             required: option_count,
-            unique: unique_indices.len(),
+            unique: options.len(),
         });
     }
 
-    let mut selected = unique_indices;
-    selected.shuffle(rng);
-    selected.truncate(option_count);
-
-    let correct_compound_index = selected[0];
-
-    let mut options: Vec<(usize, String)> = selected
-        .iter()
-        .map(|idx| {
-            let compound = &compounds[*idx];
-            let option_text = match mode {
-                QuizMode::NameToStructure => compound.display_structure(),
-                QuizMode::StructureToName => compound.english_label(),
-            };
-            (*idx, option_text)
-        })
-        .collect();
-
     options.shuffle(rng);
-
     let correct_index = options
         .iter()
-        .position(|(idx, _)| *idx == correct_compound_index)
+        .position(|option| option == answer)
         .expect("correct option must exist after shuffle");
 
-    let prompt = match mode {
-        QuizMode::NameToStructure => compounds[correct_compound_index].english_label(),
-        QuizMode::StructureToName => compounds[correct_compound_index].display_structure(),
-    };
-
     Ok(QuizItem {
-        mode,
-        prompt,
-        options: options.into_iter().map(|(_, text)| text).collect(),
+        mode: QuizMode::GeneralFormulaToCompound,
+        prompt: format!(
+            "Which compound matches the general formula {}?",
+            series.general_formula
+        ),
+        options,
         correct_index,
+        correct_indices: vec![correct_index],
+        compound_index: Some(*compound_index),
     })
 }
 
+/// Picks one molecular formula shared by at least `option_count` of the candidates and
+/// returns the indices of the compounds with that formula.
+fn isomer_group<R: Rng + ?Sized>(
+    rng: &mut R,
+    compounds: &[Compound],
+    candidates: Vec<usize>,
+    option_count: usize,
+) -> Result<Vec<usize>, QuizError> {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for idx in candidates {
+        if let Some(formula) = compounds[idx].normalized_formula() {
+            groups.entry(formula).or_default().push(idx);
+        }
+    }
+
+    let largest = groups.values().map(Vec::len).max().unwrap_or(0);
+    let eligible: Vec<Vec<usize>> = groups
+        .into_values()
+        .filter(|group| group.len() >= option_count)
+        .collect();
+
+    eligible
+        .choose(rng)
+        .cloned()
+        .ok_or(QuizError::NotEnoughIsomers {
+            required: option_count,
+            largest,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::compound::Compound;
+    use crate::reaction::Equation;
     use rand::SeedableRng;
 
     fn sample_compounds() -> Vec<Compound> {
@@ -139,6 +1473,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CCO".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
             Compound {
                 iupac_name: "propan-2-ol".to_string(),
@@ -150,6 +1489,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CC(O)C".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
             Compound {
                 iupac_name: "ethanoic acid".to_string(),
@@ -161,6 +1505,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CC(=O)O".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
             Compound {
                 iupac_name: "benzene".to_string(),
@@ -172,6 +1521,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("c1ccccc1".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
         ]
     }
@@ -222,6 +1576,177 @@ mod tests {
         );
     }
 
+    #[test]
+    fn formula_modes_ask_for_formulas_without_isomer_clashes() {
+        let mut compounds = sample_compounds();
+        compounds.push(Compound {
+            iupac_name: "methoxymethane".to_string(),
+            common_name: Some("dimethyl ether".to_string()),
+            skeletal_formula: "CH3-O-CH3".to_string(),
+            smiles: Some("COC".to_string()),
+            ..compounds[0].clone()
+        });
+
+        for seed in 0..20 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::NameToMolecularFormula, 4)
+                .expect("four distinct formulas are available");
+            let answer = compounds
+                .iter()
+                .find(|compound| compound.english_label() == quiz.prompt)
+                .expect("prompt names a compound");
+            assert_eq!(quiz.options[quiz.correct_index], answer.molecular_formula);
+            let distinct: HashSet<&String> = quiz.options.iter().collect();
+            assert_eq!(distinct.len(), 4);
+
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::MolecularFormulaToName, 4)
+                .expect("four distinct formulas are available");
+            let named: Vec<&Compound> = quiz
+                .options
+                .iter()
+                .filter_map(|option| {
+                    compounds
+                        .iter()
+                        .find(|compound| &compound.english_label() == option)
+                })
+                .collect();
+            assert_eq!(
+                named
+                    .iter()
+                    .filter(|compound| compound.molecular_formula == quiz.prompt)
+                    .count(),
+                1,
+                "{:?}",
+                quiz
+            );
+            assert_eq!(named[quiz.correct_index].molecular_formula, quiz.prompt);
+            let target = quiz
+                .compound_index
+                .expect("the prompt is a compound's formula");
+            assert_eq!(
+                compounds[target].english_label(),
+                quiz.options[quiz.correct_index]
+            );
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let error = generate_quiz(&mut rng, &compounds, QuizMode::NameToMolecularFormula, 5)
+            .expect_err("ethanol and methoxymethane share C2H6O");
+        assert_eq!(
+            error,
+            QuizError::InsufficientUniqueOptions {
+                required: 5,
+                unique: 4
+            }
+        );
+
+        let quiz = generate_quiz(&mut rng, &compounds, QuizMode::NameToCondensedFormula, 5)
+            .expect("condensed formulas tell the isomers apart");
+        let answer = compounds
+            .iter()
+            .find(|compound| compound.english_label() == quiz.prompt)
+            .expect("prompt names a compound");
+        assert_eq!(quiz.options[quiz.correct_index], answer.skeletal_formula);
+
+        let quiz = generate_quiz(&mut rng, &compounds, QuizMode::CondensedFormulaToName, 5)
+            .expect("condensed formulas tell the isomers apart");
+        let answer = compounds
+            .iter()
+            .find(|compound| compound.skeletal_formula == quiz.prompt)
+            .expect("prompt is a condensed formula");
+        assert_eq!(quiz.options[quiz.correct_index], answer.english_label());
+    }
+
+    #[test]
+    fn japanese_name_modes_skip_compounds_without_one() {
+        let mut compounds = sample_compounds();
+        compounds[3].local_name = None;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(22);
+
+        let quiz = generate_quiz(&mut rng, &compounds, QuizMode::NameToJapaneseName, 3)
+            .expect("three compounds have Japanese names");
+        let answer = compounds
+            .iter()
+            .find(|compound| compound.iupac_name == quiz.prompt)
+            .expect("prompt is an IUPAC name");
+        assert_eq!(
+            Some(&quiz.options[quiz.correct_index]),
+            answer.local_name.as_ref()
+        );
+        assert!(!quiz.options.iter().any(String::is_empty));
+
+        let quiz = generate_quiz(&mut rng, &compounds, QuizMode::JapaneseNameToStructure, 3)
+            .expect("three compounds have Japanese names");
+        let answer = compounds
+            .iter()
+            .find(|compound| compound.local_name.as_deref() == Some(quiz.prompt.as_str()))
+            .expect("prompt is a Japanese name");
+        assert_eq!(quiz.options[quiz.correct_index], answer.display_structure());
+        assert!(!quiz.options.contains(&compounds[3].display_structure()));
+
+        let quiz = generate_quiz(&mut rng, &compounds, QuizMode::StructureToJapaneseName, 3)
+            .expect("three compounds have Japanese names");
+        assert!(quiz.options.contains(&"エタノール".to_string()));
+
+        let error = generate_quiz(&mut rng, &compounds, QuizMode::StructureToJapaneseName, 4)
+            .expect_err("benzene has no Japanese name");
+        assert_eq!(
+            error,
+            QuizError::NotEnoughJapaneseNames {
+                required: 4,
+                available: 3
+            }
+        );
+    }
+
+    #[test]
+    fn common_name_modes_pair_iupac_and_common_names() {
+        let mut compounds = sample_compounds();
+        compounds.push(Compound {
+            iupac_name: "methanol".to_string(),
+            common_name: Some("Methanol".to_string()),
+            skeletal_formula: "CH3OH".to_string(),
+            molecular_formula: "CH4O".to_string(),
+            smiles: Some("CO".to_string()),
+            ..compounds[0].clone()
+        });
+
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::IupacToCommonName, 3)
+                .expect("three compounds have common names");
+            let answer = compounds
+                .iter()
+                .find(|compound| compound.iupac_name == quiz.prompt)
+                .expect("prompt is an IUPAC name");
+            assert_eq!(
+                Some(&quiz.options[quiz.correct_index]),
+                answer.common_name.as_ref()
+            );
+            assert!(!quiz.options.contains(&"Methanol".to_string()));
+
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::CommonToIupacName, 3)
+                .expect("three compounds have common names");
+            let answer = compounds
+                .iter()
+                .find(|compound| compound.common_name.as_deref() == Some(quiz.prompt.as_str()))
+                .expect("prompt is a common name");
+            assert_eq!(quiz.options[quiz.correct_index], answer.iupac_name);
+            assert!(!quiz.options.iter().any(|option| option.contains('(')));
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(23);
+        let error = generate_quiz(&mut rng, &compounds, QuizMode::CommonToIupacName, 4)
+            .expect_err("benzene and methanol have no separate common name");
+        assert_eq!(
+            error,
+            QuizError::NotEnoughCommonNames {
+                required: 4,
+                available: 3
+            }
+        );
+    }
+
     #[test]
     fn error_when_too_few_compounds() {
         let compounds = sample_compounds();
@@ -252,6 +1777,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CCO".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
             Compound {
                 iupac_name: "ethanol".to_string(),
@@ -263,6 +1793,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CCO".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
             Compound {
                 iupac_name: "propan-1-ol".to_string(),
@@ -274,6 +1809,11 @@ mod tests {
                 functional_groups: Vec::new(),
                 notes: None,
                 smiles: Some("CCCO".to_string()),
+                katex_skeletal_formula: None,
+                katex_molecular_formula: None,
+                state: None,
+                charge: None,
+                hydrate_water: None,
             },
         ];
 
@@ -291,6 +1831,221 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn chemically_equivalent_options_are_not_repeated() {
+        let mut compounds = sample_compounds();
+        compounds.push(Compound {
+            iupac_name: "ethanol".to_string(),
+            common_name: None,
+            local_name: None,
+            skeletal_formula: "CH3CH2OH".to_string(),
+            molecular_formula: "C2H6O".to_string(),
+            series_general_formula: None,
+            functional_groups: Vec::new(),
+            notes: None,
+            smiles: Some("OCC".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
+        });
+
+        for seed in 0..20 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::NameToStructure, 4)
+                .expect("quiz should generate");
+
+            let ethanol_options = quiz
+                .options
+                .iter()
+                .filter(|option| option.ends_with("(C2H6O)"))
+                .count();
+            assert!(ethanol_options <= 1, "seed {seed}: {:?}", quiz.options);
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let error = generate_quiz(&mut rng, &compounds, QuizMode::NameToStructure, 5)
+            .expect_err("equivalent compounds count once");
+        assert!(matches!(
+            error,
+            QuizError::InsufficientUniqueOptions {
+                required: 5,
+                unique: 4
+            }
+        ));
+    }
+
+    fn isomer_compounds() -> Vec<Compound> {
+        let compound = |name: &str, skeletal: &str, formula: &str, smiles: &str| Compound {
+            iupac_name: name.to_string(),
+            common_name: None,
+            local_name: None,
+            skeletal_formula: skeletal.to_string(),
+            molecular_formula: formula.to_string(),
+            series_general_formula: None,
+            functional_groups: Vec::new(),
+            notes: None,
+            smiles: Some(smiles.to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
+        };
+
+        vec![
+            compound("ethanol", "CH3-CH2-OH", "C2H6O", "CCO"),
+            compound("methoxymethane", "CH3-O-CH3", "C2H6O", "COC"),
+            compound("propan-1-ol", "CH3-CH2-CH2-OH", "C3H8O", "CCCO"),
+            compound("propan-2-ol", "(CH3)2CHOH", "C3H8O", "CC(O)C"),
+            compound("methoxyethane", "CH3-O-CH2-CH3", "C3H8O", "COCC"),
+            compound("benzene", "C6H6", "C6H6", "c1ccccc1"),
+        ]
+    }
+
+    #[test]
+    fn isomer_quiz_offers_only_isomers() {
+        let compounds = isomer_compounds();
+
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::IsomerToStructure, 3)
+                .expect("C3H8O group has three isomers");
+
+            assert_eq!(quiz.mode, QuizMode::IsomerToStructure);
+            assert!(
+                quiz.options
+                    .iter()
+                    .all(|option| option.ends_with("(C3H8O)"))
+            );
+
+            let correct_compound = compounds
+                .iter()
+                .find(|compound| compound.english_label() == quiz.prompt)
+                .expect("prompt should name a compound");
+            assert_eq!(
+                quiz.options[quiz.correct_index],
+                correct_compound.display_structure()
+            );
+        }
+    }
+
+    #[test]
+    fn error_when_not_enough_isomers() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(4);
+
+        let error = generate_quiz(
+            &mut rng,
+            &isomer_compounds(),
+            QuizMode::IsomerToStructure,
+            4,
+        )
+        .expect_err("no formula has four isomers");
+
+        assert_eq!(
+            error,
+            QuizError::NotEnoughIsomers {
+                required: 4,
+                largest: 3
+            }
+        );
+    }
+
+    fn inorganic_compound(name: &str, formula: &str) -> Compound {
+        Compound {
+            iupac_name: name.to_string(),
+            common_name: None,
+            local_name: None,
+            skeletal_formula: formula.to_string(),
+            molecular_formula: formula.to_string(),
+            series_general_formula: None,
+            functional_groups: Vec::new(),
+            notes: None,
+            smiles: None,
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
+        }
+    }
+
+    #[test]
+    fn oxidation_number_quiz_asks_for_the_balanced_element() {
+        let compounds = vec![
+            inorganic_compound("potassium permanganate", "KMnO4"),
+            inorganic_compound("manganese(IV) oxide", "MnO2"),
+            inorganic_compound("oxygen", "O2"),
+            inorganic_compound("ammonium nitrate", "NH4NO3"),
+        ];
+
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::OxidationNumber, 4)
+                .expect("manganese compounds are eligible");
+
+            assert_eq!(quiz.mode, QuizMode::OxidationNumber);
+            assert_eq!(quiz.options.len(), 4);
+            let expected = match quiz.prompt.as_str() {
+                "What is the oxidation number of Mn in KMnO4?" => "+7",
+                "What is the oxidation number of Mn in MnO2?" => "+4",
+                other => panic!("unexpected prompt {other}"),
+            };
+            assert_eq!(quiz.options[quiz.correct_index], expected);
+            assert!(quiz.options.contains(&"+7".to_string()));
+            assert!(quiz.options.contains(&"+4".to_string()));
+            let target = quiz.compound_index.expect("the prompt names a compound");
+            assert!(
+                quiz.prompt
+                    .ends_with(&format!("in {}?", compounds[target].molecular_formula))
+            );
+        }
+    }
+
+    #[test]
+    fn error_when_no_oxidation_number_can_be_asked() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(6);
+        let compounds = vec![
+            inorganic_compound("iron", "Fe"),
+            inorganic_compound("ammonium nitrate", "NH4NO3"),
+            inorganic_compound("magnetite", "Fe3O4"),
+        ];
+
+        let error = generate_quiz(&mut rng, &compounds, QuizMode::OxidationNumber, 4)
+            .expect_err("no eligible element");
+
+        assert_eq!(error, QuizError::NoOxidationNumbers);
+    }
+
+    #[test]
+    fn ions_to_formula_quiz_offers_one_balanced_formula() {
+        let compounds = vec![
+            inorganic_compound("iron(III) sulfate", "Fe2(SO4)3"),
+            inorganic_compound("ethanol", "C2H6O"),
+        ];
+
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::IonsToFormula, 4)
+                .expect("iron(III) sulfate is a salt");
+
+            assert_eq!(quiz.prompt, "Which formula do Fe^{3+} and SO4^{2-} form?");
+            assert_eq!(quiz.options.len(), 4);
+            assert_eq!(quiz.options[quiz.correct_index], "Fe2(SO4)3");
+            assert!(
+                !quiz.options.contains(&"Fe4(SO4)6".to_string()),
+                "{:?}",
+                quiz.options
+            );
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let error = generate_quiz(&mut rng, &compounds[1..], QuizMode::IonsToFormula, 4)
+            .expect_err("ethanol is not a salt");
+        assert_eq!(error, QuizError::NoIonicCompounds);
+    }
+
     #[test]
     fn error_when_option_count_too_small() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(3);
@@ -301,4 +2056,481 @@ mod tests {
 
         assert_eq!(error, QuizError::OptionCountTooSmall);
     }
+
+    #[test]
+    fn name_drill_needs_no_compounds() {
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &[], QuizMode::NameDrill, 4)
+                .expect("drills draw their own structures");
+
+            assert_eq!(quiz.mode, QuizMode::NameDrill);
+            assert_eq!(quiz.options.len(), 4);
+            assert_eq!(quiz.compound_index, None);
+            let unique: HashSet<&String> = quiz.options.iter().collect();
+            assert_eq!(unique.len(), 4, "{:?}", quiz.options);
+            assert_eq!(
+                nomenclature::systematic_name(&quiz.prompt).as_ref(),
+                Ok(&quiz.options[quiz.correct_index])
+            );
+        }
+    }
+
+    fn organic_compound(name: &str, common: &str, formula: &str, smiles: &str) -> Compound {
+        Compound {
+            common_name: Some(common.to_string()),
+            smiles: Some(smiles.to_string()),
+            ..inorganic_compound(name, formula)
+        }
+    }
+
+    fn stereo_compounds() -> Vec<Compound> {
+        vec![
+            organic_compound(
+                "(Z)-but-2-enedioic acid",
+                "maleic acid",
+                "C4H4O4",
+                "OC(=O)/C=C\\C(=O)O",
+            ),
+            organic_compound(
+                "(E)-but-2-enedioic acid",
+                "fumaric acid",
+                "C4H4O4",
+                "OC(=O)/C=C/C(=O)O",
+            ),
+            organic_compound("but-2-ene", "2-butene", "C4H8", "CC=CC"),
+            organic_compound("2-aminoethanoic acid", "glycine", "C2H5NO2", "NCC(=O)O"),
+            organic_compound(
+                "2-aminopropanoic acid",
+                "alanine",
+                "C3H7NO2",
+                "C[C@@H](C(=O)O)N",
+            ),
+            organic_compound("butan-2-ol", "sec-butyl alcohol", "C4H10O", "CCC(C)O"),
+        ]
+    }
+
+    #[test]
+    fn geometric_isomer_quiz_pairs_cis_and_trans_names() {
+        let compounds = stereo_compounds();
+
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::GeometricIsomer, 4)
+                .expect("maleic and fumaric acid are geometric isomers");
+
+            assert_eq!(quiz.mode, QuizMode::GeometricIsomer);
+            assert_eq!(quiz.options.len(), 4);
+            let expected = match quiz.prompt.as_str() {
+                "OC(=O)/C=C\\C(=O)O" => "(Z)-but-2-enedioic acid (maleic acid)",
+                "OC(=O)/C=C/C(=O)O" => "(E)-but-2-enedioic acid (fumaric acid)",
+                other => panic!("unexpected prompt {other}"),
+            };
+            assert_eq!(quiz.options[quiz.correct_index], expected);
+            assert!(quiz.options.iter().any(|option| option.contains("maleic")));
+            assert!(quiz.options.iter().any(|option| option.contains("fumaric")));
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert_eq!(
+            generate_quiz(&mut rng, &compounds[1..], QuizMode::GeometricIsomer, 4),
+            Err(QuizError::NoGeometricIsomers)
+        );
+    }
+
+    #[test]
+    fn chiral_centre_quiz_counts_stereocentres() {
+        let mut compounds = stereo_compounds();
+        compounds.push(inorganic_compound("sodium chloride", "NaCl"));
+
+        for seed in 0..20 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::ChiralCentres, 4)
+                .expect("carbon compounds have SMILES");
+
+            assert_eq!(quiz.mode, QuizMode::ChiralCentres);
+            assert_eq!(quiz.options.len(), 4);
+            let unique: HashSet<&String> = quiz.options.iter().collect();
+            assert_eq!(unique.len(), 4, "{:?}", quiz.options);
+            let expected = if quiz.prompt.contains("alanine") || quiz.prompt.contains("butan-2-ol")
+            {
+                "1"
+            } else {
+                "0"
+            };
+            assert_eq!(
+                quiz.options[quiz.correct_index], expected,
+                "{}",
+                quiz.prompt
+            );
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert_eq!(
+            generate_quiz(
+                &mut rng,
+                &[inorganic_compound("sodium chloride", "NaCl")],
+                QuizMode::ChiralCentres,
+                4
+            ),
+            Err(QuizError::NoOrganicStructures)
+        );
+    }
+
+    #[test]
+    fn mass_percent_quiz_offers_the_computed_share() {
+        let mut compounds = sample_compounds();
+        compounds.push(inorganic_compound("ammonium chloride", "NH4Cl"));
+        compounds.push(inorganic_compound("sulfate ion", "SO4^2-"));
+
+        for seed in 0..20 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::MassPercent, 4)
+                .expect("neutral compounds have compositions");
+
+            let (element, formula) = quiz
+                .prompt
+                .strip_prefix("What is the mass percent of ")
+                .and_then(|rest| rest.strip_suffix('?'))
+                .and_then(|rest| rest.split_once(" in "))
+                .expect("prompt names an element and a formula");
+            assert_ne!(formula, "SO4^2-");
+            let expected = composition::mass_composition(
+                &Formula::parse(formula).expect("prompt formula parses"),
+            )
+            .percent_of(element)
+            .expect("element is in the formula");
+            assert_eq!(
+                quiz.options[quiz.correct_index],
+                format!("{expected:.1}%"),
+                "{}",
+                quiz.prompt
+            );
+
+            let values: Vec<f64> = quiz
+                .options
+                .iter()
+                .map(|option| {
+                    option
+                        .trim_end_matches('%')
+                        .parse()
+                        .expect("numeric option")
+                })
+                .collect();
+            for (index, value) in values.iter().enumerate() {
+                assert!(
+                    values[..index]
+                        .iter()
+                        .all(|other| (other - value).abs() >= 0.9),
+                    "{:?}",
+                    quiz.options
+                );
+            }
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert_eq!(
+            generate_quiz(
+                &mut rng,
+                &[inorganic_compound("nitrogen", "N2")],
+                QuizMode::MassPercent,
+                4
+            ),
+            Err(QuizError::NoCompositions)
+        );
+    }
+
+    #[test]
+    fn formula_from_composition_quiz_solves_back_to_the_formula() {
+        let mut compounds = sample_compounds();
+        compounds.push(inorganic_compound("water", "H2O"));
+
+        for seed in 0..20 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::FormulaFromComposition, 4)
+                .expect("carbon compounds solve back to their formulas");
+
+            assert_eq!(quiz.options.len(), 4);
+            let answer = &quiz.options[quiz.correct_index];
+            assert!(
+                ["C2H6O", "C3H8O", "C2H4O2", "C6H6"].contains(&answer.as_str()),
+                "{answer}"
+            );
+            let molar_mass = Formula::parse(answer).expect("answer parses").molar_mass();
+            assert!(
+                quiz.prompt
+                    .contains(&format!("molar mass of {molar_mass:.1} g/mol")),
+                "{}",
+                quiz.prompt
+            );
+            match answer.as_str() {
+                "C2H4O2" => assert!(quiz.options.contains(&"CH2O".to_string())),
+                "C6H6" => assert!(quiz.options.contains(&"CH".to_string())),
+                _ => {}
+            }
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert_eq!(
+            generate_quiz(
+                &mut rng,
+                &[inorganic_compound("water", "H2O")],
+                QuizMode::FormulaFromComposition,
+                4
+            ),
+            Err(QuizError::NoMolecularFormulas)
+        );
+    }
+
+    #[test]
+    fn balance_equation_quiz_offers_the_balanced_equation() {
+        for seed in 0..30 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &[], QuizMode::BalanceEquation, 4)
+                .expect("precipitation reactions need no compounds");
+
+            assert_eq!(quiz.options.len(), 4);
+            let unique: HashSet<&String> = quiz.options.iter().collect();
+            assert_eq!(unique.len(), 4, "{:?}", quiz.options);
+
+            let skeleton = quiz
+                .prompt
+                .split_once("Balance the equation: ")
+                .map(|(_, equation)| equation)
+                .expect("prompt ends with the skeleton equation");
+            let expected = Equation::parse(skeleton)
+                .and_then(|equation| equation.balanced())
+                .expect("skeleton balances");
+            for (index, option) in quiz.options.iter().enumerate() {
+                let equation = Equation::parse(option).expect("options parse");
+                assert_eq!(
+                    equation.is_balanced(),
+                    index == quiz.correct_index,
+                    "{option}"
+                );
+            }
+            assert_eq!(quiz.options[quiz.correct_index], expected.to_string());
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let quiz = generate_quiz(&mut rng, &[], QuizMode::BalanceEquation, 2)
+            .expect("two options always exist");
+        assert_eq!(quiz.options.len(), 2);
+
+        let compounds = vec![inorganic_compound("copper(II) hydroxide", "Cu(OH)2")];
+        for seed in 0..30 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::BalanceEquation, 4)
+                .expect("precipitation reactions need no compounds");
+            let about_copper = quiz.prompt.contains(" precipitates Cu(OH)2.");
+            assert_eq!(
+                quiz.compound_index,
+                about_copper.then_some(0),
+                "{}",
+                quiz.prompt
+            );
+        }
+    }
+
+    #[test]
+    fn functional_group_quiz_accepts_several_answers_with_partial_credit() {
+        let compounds = vec![
+            organic_compound(
+                "2-hydroxypropanoic acid",
+                "lactic acid",
+                "C3H6O3",
+                "CC(O)C(=O)O",
+            ),
+            // Amino, carboxyl, phenolic hydroxyl and the ring leave no room for a distractor.
+            organic_compound(
+                "2-amino-3-(4-hydroxyphenyl)propanoic acid",
+                "tyrosine",
+                "C9H11NO3",
+                "NC(Cc1ccc(O)cc1)C(=O)O",
+            ),
+            inorganic_compound("sodium chloride", "NaCl"),
+        ];
+
+        for seed in 0..10 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::FunctionalGroups, 4)
+                .expect("lactic acid has two groups");
+
+            assert!(quiz.mode.allows_multiple_answers());
+            assert!(quiz.prompt.contains("C3H6O3"), "{}", quiz.prompt);
+            assert_eq!(quiz.options.len(), 4);
+            let unique: HashSet<&String> = quiz.options.iter().collect();
+            assert_eq!(unique.len(), 4, "{:?}", quiz.options);
+
+            let answers: Vec<&str> = quiz
+                .correct_indices
+                .iter()
+                .map(|index| quiz.options[*index].as_str())
+                .collect();
+            assert_eq!(answers.len(), 2);
+            assert!(answers.contains(&FunctionalGroupKind::Hydroxyl.name_en()));
+            assert!(answers.contains(&FunctionalGroupKind::Carboxyl.name_en()));
+            assert_eq!(quiz.correct_index, quiz.correct_indices[0]);
+            assert!(
+                quiz.correct_indices
+                    .windows(2)
+                    .all(|pair| pair[0] < pair[1])
+            );
+
+            let wrong: Vec<usize> = (0..4).filter(|index| !quiz.is_correct(*index)).collect();
+            let [first, second] = [quiz.correct_indices[0], quiz.correct_indices[1]];
+            assert_eq!(quiz.score(&[second, first]), 1.0);
+            assert_eq!(quiz.score(&[first, first]), 0.5);
+            assert_eq!(quiz.score(&[first, second, wrong[0]]), 0.5);
+            assert_eq!(quiz.score(&[first, wrong[0], wrong[1]]), 0.0);
+            assert_eq!(quiz.score(&[]), 0.0);
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert_eq!(
+            generate_quiz(&mut rng, &compounds[1..], QuizMode::FunctionalGroups, 4),
+            Err(QuizError::NoFunctionalGroups)
+        );
+
+        let quiz = generate_quiz(&mut rng, &compounds, QuizMode::ChiralCentres, 4)
+            .expect("carbon compounds have SMILES");
+        assert!(!quiz.mode.allows_multiple_answers());
+        assert_eq!(quiz.correct_indices, [quiz.correct_index]);
+        assert_eq!(quiz.score(&[quiz.correct_index]), 1.0);
+    }
+
+    fn series_compound(name: &str, formula: &str, smiles: &str, series: &str) -> Compound {
+        Compound {
+            series_general_formula: Some(series.to_string()),
+            ..organic_compound(name, name, formula, smiles)
+        }
+    }
+
+    fn series_compounds() -> Vec<Compound> {
+        vec![
+            series_compound(
+                "propyne",
+                "C3H4",
+                "CC#C",
+                "Alkynes (one C≡C): CnH2n-2 (n>=2)",
+            ),
+            series_compound("propene", "C3H6", "CC=C", "Alkenes (one C=C): CnH2n (n>=2)"),
+            series_compound(
+                "cyclopropane",
+                "C3H6",
+                "C1CC1",
+                "Cycloalkanes: CnH2n (n>=3)",
+            ),
+            series_compound("propane", "C3H8", "CCC", "Alkanes: CnH2n+2 (n>=1)"),
+            series_compound(
+                "ethanol",
+                "C2H6O",
+                "CCO",
+                "Primary alcohols (1°): CnH2n+1OH (n>=1)",
+            ),
+            series_compound(
+                "methoxymethane",
+                "C2H6O",
+                "COC",
+                "Simple ethers: CnH2n+2O (same as monohydric alcohols)",
+            ),
+            series_compound(
+                "benzoic acid",
+                "C7H6O2",
+                "OC(=O)c1ccccc1",
+                "Aromatic monocarboxylic acids: Ar-COOH (Ar = aromatic ring)",
+            ),
+        ]
+    }
+
+    #[test]
+    fn structure_to_series_quiz_asks_for_the_named_series() {
+        let compounds = series_compounds();
+
+        for seed in 0..30 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::StructureToSeries, 4)
+                .expect("five compounds belong to a series");
+
+            assert_eq!(quiz.options.len(), 4);
+            let unique: HashSet<&String> = quiz.options.iter().collect();
+            assert_eq!(unique.len(), 4, "{:?}", quiz.options);
+
+            let compound = compounds
+                .iter()
+                .find(|compound| quiz.prompt.contains(&compound.display_structure()))
+                .expect("prompt shows a compound");
+            let series = compound.homologous_series().expect("prompt has a series");
+            assert_eq!(quiz.options[quiz.correct_index], series.name_en);
+
+            if series.general_formula == "CnH2n" {
+                let sibling = if series.name_en == "Alkenes" {
+                    "Cycloalkanes"
+                } else {
+                    "Alkenes"
+                };
+                assert!(quiz.options.iter().any(|option| option == sibling));
+            }
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert_eq!(
+            generate_quiz(&mut rng, &compounds[5..], QuizMode::StructureToSeries, 4),
+            Err(QuizError::NoHomologousSeries)
+        );
+    }
+
+    #[test]
+    fn general_formula_quiz_offers_one_fitting_compound() {
+        let compounds = series_compounds();
+
+        for seed in 0..30 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let quiz = generate_quiz(&mut rng, &compounds, QuizMode::GeneralFormulaToCompound, 4)
+                .expect("enough series members fall outside each formula");
+
+            let general = quiz
+                .prompt
+                .strip_prefix("Which compound matches the general formula ")
+                .and_then(|rest| rest.strip_suffix('?'))
+                .expect("prompt names a general formula");
+            let siblings = series_with_general_formula(general);
+            assert!(!siblings.is_empty(), "{general}");
+
+            assert_eq!(quiz.options.len(), 4);
+            for (index, option) in quiz.options.iter().enumerate() {
+                let compound = compounds
+                    .iter()
+                    .find(|compound| compound.english_label() == *option)
+                    .expect("options are compounds");
+                assert!(compound.homologous_series().is_some(), "{option}");
+                let formula = compound.formula().expect("formula parses");
+                assert_eq!(
+                    siblings.iter().any(|series| series.fits(&formula)),
+                    index == quiz.correct_index,
+                    "{option} for {general}"
+                );
+            }
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert!(matches!(
+            generate_quiz(
+                &mut rng,
+                &compounds[..3],
+                QuizMode::GeneralFormulaToCompound,
+                4
+            ),
+            Err(QuizError::InsufficientUniqueOptions { required: 4, .. })
+        ));
+        assert_eq!(
+            generate_quiz(
+                &mut rng,
+                &compounds[5..],
+                QuizMode::GeneralFormulaToCompound,
+                4
+            ),
+            Err(QuizError::NoHomologousSeries)
+        );
+    }
 }
@@ -0,0 +1,522 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use crate::compound::{Formula, FormulaError};
+
+/// Arrows accepted between the two sides, longest first so `<->` is not read as `->`.
+const ARROWS: [&str; 6] = ["<=>", "<->", "⇌", "⟶", "->", "→"];
+
+/// One reactant or product with its stoichiometric coefficient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Species {
+    pub coefficient: u32,
+    /// Formula as written, with any charge and state but without the coefficient, e.g.
+    /// `"OH-"` or `"NaOH (aq)"`.
+    pub text: String,
+    pub formula: Formula,
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coefficient != 1 {
+            write!(f, "{}", self.coefficient)?;
+        }
+        f.write_str(&self.text)
+    }
+}
+
+/// Chemical or ionic equation such as `Cu2+ + 2OH- -> Cu(OH)2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    reactants: Vec<Species>,
+    products: Vec<Species>,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum ReactionError {
+    #[error("equation has no arrow between reactants and products")]
+    MissingArrow,
+    #[error("equation has more than one arrow")]
+    MultipleArrows,
+    #[error("equation has an empty side or species")]
+    EmptySpecies,
+    #[error("coefficient of '{species}' is not a positive integer")]
+    InvalidCoefficient { species: String },
+    #[error("species '{species}' could not be parsed: {source}")]
+    Species {
+        species: String,
+        source: FormulaError,
+    },
+    #[error("no positive whole-number coefficients balance the equation")]
+    Unbalanceable,
+    #[error("equation combines several independent reactions, so its coefficients are not unique")]
+    Ambiguous,
+}
+
+impl Equation {
+    /// Parses an equation such as `"Cu2+ + 2OH- -> Cu(OH)2"` or
+    /// `"2H2 (g) + O2 (g) → 2H2O (l)"`.
+    ///
+    /// The sides are separated by `->`, `→`, `⟶`, `⇌`, `<->` or `<=>`, and species by a `+`
+    /// standing on its own between spaces, so the trailing signs of `Cu2+` and `OH-` are
+    /// read as charges. A leading number is the coefficient; formulas, charges and states
+    /// follow [`Formula::parse`].
+    pub fn parse(input: &str) -> Result<Self, ReactionError> {
+        let mut normalized = input.to_string();
+        for arrow in ARROWS {
+            normalized = normalized.replace(arrow, "→");
+        }
+
+        let sides: Vec<&str> = normalized.split('→').collect();
+        let (left, right) = match sides.as_slice() {
+            [_] => return Err(ReactionError::MissingArrow),
+            [left, right] => (*left, *right),
+            _ => return Err(ReactionError::MultipleArrows),
+        };
+
+        Ok(Self {
+            reactants: parse_side(left)?,
+            products: parse_side(right)?,
+        })
+    }
+
+    pub fn reactants(&self) -> &[Species] {
+        &self.reactants
+    }
+
+    pub fn products(&self) -> &[Species] {
+        &self.products
+    }
+
+    /// Coefficients of the reactants followed by those of the products.
+    pub fn coefficients(&self) -> Vec<u32> {
+        self.species().map(|species| species.coefficient).collect()
+    }
+
+    /// Returns `true` when every element and the total charge are conserved.
+    pub fn is_balanced(&self) -> bool {
+        self.conservation_rows()
+            .iter()
+            .all(|row| row.iter().sum::<i128>() == 0)
+    }
+
+    /// Returns the equation with the smallest whole-number coefficients that conserve every
+    /// element and, for ionic equations, the charge. The written coefficients are ignored.
+    ///
+    /// # Errors
+    /// * Returns [`ReactionError::Unbalanceable`] when no set of positive coefficients
+    ///   conserves the atoms and charge, e.g. when an element appears on one side only.
+    /// * Returns [`ReactionError::Ambiguous`] when several independent sets of coefficients
+    ///   do, as in `H2 + O2 -> H2O + H2O2`.
+    pub fn balanced(&self) -> Result<Self, ReactionError> {
+        let coefficients = null_vector(self.composition_rows(), self.species().count())?;
+        Ok(self.with_coefficients(&coefficients))
+    }
+
+    /// The equation with every coefficient set to 1.
+    pub fn skeleton(&self) -> Self {
+        self.with_coefficients(&vec![1; self.species().count()])
+    }
+
+    /// The equation with the given coefficients, reactants first.
+    pub(crate) fn with_coefficients(&self, coefficients: &[u32]) -> Self {
+        let mut coefficients = coefficients.iter().copied();
+        let mut assign = |species: &[Species]| {
+            species
+                .iter()
+                .map(|species| Species {
+                    coefficient: coefficients.next().unwrap_or(species.coefficient),
+                    ..species.clone()
+                })
+                .collect()
+        };
+
+        Self {
+            reactants: assign(&self.reactants),
+            products: assign(&self.products),
+        }
+    }
+
+    fn species(&self) -> impl Iterator<Item = &Species> {
+        self.reactants.iter().chain(&self.products)
+    }
+
+    /// [`composition_rows`](Self::composition_rows) with every column multiplied by the
+    /// written coefficient of its species.
+    fn conservation_rows(&self) -> Vec<Vec<i128>> {
+        let coefficients: Vec<i128> = self
+            .species()
+            .map(|species| i128::from(species.coefficient))
+            .collect();
+
+        self.composition_rows()
+            .into_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&coefficients)
+                    .map(|(count, coefficient)| count * coefficient)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// One row per element plus one for charge, with a column per species holding its count
+    /// in one formula unit, negated for products. Written coefficients are left out.
+    fn composition_rows(&self) -> Vec<Vec<i128>> {
+        let elements: BTreeSet<&String> = self
+            .species()
+            .flat_map(|species| species.formula.elements().keys())
+            .collect();
+        let signed = self
+            .reactants
+            .iter()
+            .map(|species| (1, species))
+            .chain(self.products.iter().map(|species| (-1, species)));
+
+        let mut rows: Vec<Vec<i128>> = elements
+            .iter()
+            .map(|element| {
+                signed
+                    .clone()
+                    .map(|(sign, species)| sign * i128::from(species.formula.count(element)))
+                    .collect()
+            })
+            .collect();
+        rows.push(
+            signed
+                .map(|(sign, species)| sign * i128::from(species.formula.charge()))
+                .collect(),
+        );
+        rows
+    }
+}
+
+impl fmt::Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |species: &[Species]| {
+            species
+                .iter()
+                .map(Species::to_string)
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
+        write!(f, "{} → {}", join(&self.reactants), join(&self.products))
+    }
+}
+
+impl FromStr for Equation {
+    type Err = ReactionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+fn parse_side(side: &str) -> Result<Vec<Species>, ReactionError> {
+    let mut groups: Vec<Vec<&str>> = vec![Vec::new()];
+    for token in side.split_whitespace() {
+        if token == "+" {
+            groups.push(Vec::new());
+        } else if let Some(group) = groups.last_mut() {
+            group.push(token);
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|tokens| parse_species(&tokens.join(" ")))
+        .collect()
+}
+
+fn parse_species(written: &str) -> Result<Species, ReactionError> {
+    if written.is_empty() {
+        return Err(ReactionError::EmptySpecies);
+    }
+
+    let digits = written
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(written.len());
+    let coefficient = if digits == 0 {
+        1
+    } else {
+        written[..digits]
+            .parse()
+            .ok()
+            .filter(|coefficient| *coefficient > 0)
+            .ok_or_else(|| ReactionError::InvalidCoefficient {
+                species: written.to_string(),
+            })?
+    };
+
+    let text = written[digits..].trim();
+    if text.is_empty() {
+        return Err(ReactionError::EmptySpecies);
+    }
+    let formula = Formula::parse(text).map_err(|source| ReactionError::Species {
+        species: text.to_string(),
+        source,
+    })?;
+
+    Ok(Species {
+        coefficient,
+        text: text.to_string(),
+        formula,
+    })
+}
+
+/// Smallest positive integer vector `x` with `rows · x = 0`, found by fraction-free
+/// Gauss–Jordan elimination. The solution is only unique up to scale when exactly one
+/// column is left without a pivot.
+fn null_vector(mut rows: Vec<Vec<i128>>, columns: usize) -> Result<Vec<u32>, ReactionError> {
+    let mut pivots: Vec<usize> = Vec::new();
+    for column in 0..columns {
+        let rank = pivots.len();
+        let Some(found) = (rank..rows.len()).find(|&row| rows[row][column] != 0) else {
+            continue;
+        };
+        rows.swap(rank, found);
+
+        let pivot_row = rows[rank].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if index == rank || factor == 0 {
+                continue;
+            }
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row) {
+                *value = *value * pivot_row[column] - pivot_value * factor;
+            }
+            let divisor = row.iter().fold(0, |divisor, value| gcd(divisor, *value));
+            if divisor > 1 {
+                row.iter_mut().for_each(|value| *value /= divisor);
+            }
+        }
+        pivots.push(column);
+    }
+
+    let free: Vec<usize> = (0..columns)
+        .filter(|column| !pivots.contains(column))
+        .collect();
+    let free = match free.as_slice() {
+        [] => return Err(ReactionError::Unbalanceable),
+        [free] => *free,
+        _ => return Err(ReactionError::Ambiguous),
+    };
+
+    let scale = pivots
+        .iter()
+        .enumerate()
+        .fold(1, |scale, (row, column)| lcm(scale, rows[row][*column]));
+    let mut solution = vec![0i128; columns];
+    solution[free] = scale;
+    for (row, column) in pivots.iter().enumerate() {
+        solution[*column] = -rows[row][free] * scale / rows[row][*column];
+    }
+
+    let divisor = solution
+        .iter()
+        .fold(0, |divisor, value| gcd(divisor, *value));
+    let sign = if solution[free] < 0 { -1 } else { 1 };
+    solution
+        .into_iter()
+        .map(|value| {
+            u32::try_from(sign * value / divisor)
+                .ok()
+                .filter(|value| *value > 0)
+        })
+        .collect::<Option<Vec<u32>>>()
+        .ok_or(ReactionError::Unbalanceable)
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn lcm(a: i128, b: i128) -> i128 {
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Precipitation reaction of a metal ion from `dataset/metal_ions_and_colors.json`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct PrecipitationReaction {
+    /// Metal ion as written in the dataset, e.g. `"Cu2+"`.
+    #[serde(skip)]
+    pub ion: String,
+    /// Reagent as described in the dataset, e.g. `"NH3 (aq) (limited)"`.
+    pub reagent: String,
+    #[serde(rename = "precipitate_formula")]
+    pub precipitate: String,
+    pub color: String,
+}
+
+impl PrecipitationReaction {
+    /// Unbalanced ionic equation, e.g. `"Cu2+ + NH3 + H2O -> Cu(OH)2 + NH4+"`. Sodium
+    /// hydroxide reacts as `OH-` and ammonia as `NH3 + H2O`, leaving `NH4+`; any other
+    /// reagent reacts as the ion it is written with first, such as `S2-` or `SO4^2-`.
+    pub fn skeleton(&self) -> String {
+        let (ion, precipitate) = (&self.ion, &self.precipitate);
+        match self.reagent.split_whitespace().next().unwrap_or_default() {
+            "NaOH" => format!("{ion} + OH- -> {precipitate}"),
+            "NH3" => format!("{ion} + NH3 + H2O -> {precipitate} + NH4+"),
+            reagent => format!("{ion} + {reagent} -> {precipitate}"),
+        }
+    }
+
+    /// The skeleton equation, unbalanced.
+    pub fn equation(&self) -> Equation {
+        Equation::parse(&self.skeleton()).expect("dataset equations parse")
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct MetalIonDataset {
+    metal_ions: Vec<MetalIon>,
+}
+
+#[derive(serde::Deserialize)]
+struct MetalIon {
+    ion: String,
+    precipitates: Vec<PrecipitationReaction>,
+}
+
+/// Precipitates of `dataset/metal_ions_and_colors.json`, in its order.
+pub static PRECIPITATION_REACTIONS: LazyLock<Vec<PrecipitationReaction>> = LazyLock::new(|| {
+    let dataset: MetalIonDataset =
+        serde_json::from_str(include_str!("../dataset/metal_ions_and_colors.json"))
+            .expect("the metal ion dataset parses");
+    dataset
+        .metal_ions
+        .into_iter()
+        .flat_map(|metal| {
+            metal
+                .precipitates
+                .into_iter()
+                .map(move |reaction| PrecipitationReaction {
+                    ion: metal.ion.clone(),
+                    ..reaction
+                })
+        })
+        .collect()
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balanced(input: &str) -> String {
+        Equation::parse(input)
+            .and_then(|equation| equation.balanced())
+            .map(|equation| equation.to_string())
+            .unwrap_or_else(|error| panic!("{input}: {error}"))
+    }
+
+    #[test]
+    fn parses_species_coefficients_charges_and_states() {
+        let equation = Equation::parse("Cu2+ (aq) + 2OH- (aq) -> Cu(OH)2 (s)").expect("parses");
+        let reactants = equation.reactants();
+
+        assert_eq!(reactants.len(), 2);
+        assert_eq!(reactants[0].formula.charge(), 2);
+        assert_eq!(reactants[1].coefficient, 2);
+        assert_eq!(reactants[1].text, "OH- (aq)");
+        assert_eq!(reactants[1].formula.charge(), -1);
+        assert_eq!(
+            equation.products()[0].formula.state(),
+            Some(crate::compound::PhysicalState::Solid)
+        );
+        assert!(equation.is_balanced());
+        assert_eq!(equation.to_string(), "Cu2+ (aq) + 2OH- (aq) → Cu(OH)2 (s)");
+    }
+
+    #[test]
+    fn balances_molecular_and_ionic_equations() {
+        assert_eq!(balanced("H2 + O2 -> H2O"), "2H2 + O2 → 2H2O");
+        assert_eq!(
+            balanced("C3H8 + O2 → CO2 + H2O"),
+            "C3H8 + 5O2 → 3CO2 + 4H2O"
+        );
+        assert_eq!(balanced("Al3+ + OH- -> Al(OH)3"), "Al3+ + 3OH- → Al(OH)3");
+        assert_eq!(
+            balanced("MnO4- + Fe2+ + H+ -> Mn2+ + Fe3+ + H2O"),
+            "MnO4- + 5Fe2+ + 8H+ → Mn2+ + 5Fe3+ + 4H2O"
+        );
+        assert_eq!(balanced("Cu + Ag+ -> Cu2+ + Ag"), "Cu + 2Ag+ → Cu2+ + 2Ag");
+    }
+
+    #[test]
+    fn rebalancing_ignores_written_coefficients() {
+        assert_eq!(balanced("2H2 + O2 -> 2H2O"), "2H2 + O2 → 2H2O");
+        assert_eq!(balanced("4H2 + 3O2 -> 5H2O"), "2H2 + O2 → 2H2O");
+        assert_eq!(
+            balanced("MnO4- + 5Fe2+ + 8H+ -> Mn2+ + 5Fe3+ + 4H2O"),
+            "MnO4- + 5Fe2+ + 8H+ → Mn2+ + 5Fe3+ + 4H2O"
+        );
+    }
+
+    #[test]
+    fn charge_must_balance_as_well_as_atoms() {
+        let equation = Equation::parse("Cu + Ag+ -> Cu2+ + Ag").expect("parses");
+        assert!(!equation.is_balanced());
+        assert!(!equation.with_coefficients(&[1, 1, 1, 1]).is_balanced());
+        assert!(equation.with_coefficients(&[1, 2, 1, 2]).is_balanced());
+    }
+
+    #[test]
+    fn reports_malformed_and_unbalanceable_equations() {
+        assert_eq!(Equation::parse("H2 + O2"), Err(ReactionError::MissingArrow));
+        assert_eq!(
+            Equation::parse("A -> B -> C"),
+            Err(ReactionError::MultipleArrows)
+        );
+        assert_eq!(
+            Equation::parse("H2 + -> H2"),
+            Err(ReactionError::EmptySpecies)
+        );
+        assert_eq!(
+            Equation::parse("0H2 -> H2"),
+            Err(ReactionError::InvalidCoefficient {
+                species: "0H2".to_string()
+            })
+        );
+        assert!(matches!(
+            Equation::parse("Xq -> X"),
+            Err(ReactionError::Species { .. })
+        ));
+
+        let unbalanceable = Equation::parse("H2O -> O2").expect("parses");
+        assert_eq!(unbalanceable.balanced(), Err(ReactionError::Unbalanceable));
+        let ambiguous = Equation::parse("H2 + O2 -> H2O + H2O2").expect("parses");
+        assert_eq!(ambiguous.balanced(), Err(ReactionError::Ambiguous));
+    }
+
+    #[test]
+    fn precipitation_reactions_balance() {
+        assert_eq!(PRECIPITATION_REACTIONS.len(), 25);
+        for reaction in PRECIPITATION_REACTIONS.iter() {
+            let balanced = reaction
+                .equation()
+                .balanced()
+                .unwrap_or_else(|error| panic!("{}: {error}", reaction.skeleton()));
+            assert!(balanced.is_balanced());
+            assert!(
+                balanced
+                    .products()
+                    .iter()
+                    .any(|species| species.text == reaction.precipitate),
+                "{}",
+                reaction.skeleton()
+            );
+        }
+
+        assert_eq!(
+            balanced(&PRECIPITATION_REACTIONS[1].skeleton()),
+            "Cu2+ + 2NH3 + 2H2O → Cu(OH)2 + 2NH4+"
+        );
+        assert_eq!(PRECIPITATION_REACTIONS[12].skeleton(), "Ag+ + Cl- -> AgCl");
+    }
+}
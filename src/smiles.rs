@@ -0,0 +1,1047 @@
+mod canonical;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use crate::compound::Formula;
+use crate::periodic_table;
+
+/// Order of a bond in the molecular graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BondOrder {
+    Single,
+    Double,
+    Triple,
+    Quadruple,
+    /// Bond between two aromatic atoms written without an explicit symbol (or with `:`).
+    Aromatic,
+}
+
+impl BondOrder {
+    /// Valence contribution used when computing implicit hydrogens. Aromatic bonds count
+    /// as single bonds; the extra π electron is added per aromatic atom instead.
+    fn valence(self) -> u32 {
+        match self {
+            Self::Single | Self::Aromatic => 1,
+            Self::Double => 2,
+            Self::Triple => 3,
+            Self::Quadruple => 4,
+        }
+    }
+}
+
+/// Tetrahedral mark written inside a bracket atom. Looking from the first neighbour written
+/// before the atom, the remaining neighbours run anticlockwise for `@` and clockwise for `@@`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chirality {
+    /// `@`
+    Anticlockwise,
+    /// `@@`
+    Clockwise,
+}
+
+/// Direction of a `/` or `\` single bond, read from [`Bond::from`] to [`Bond::to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BondDirection {
+    /// `/`: `to` lies above `from`.
+    Up,
+    /// `\`: `to` lies below `from`.
+    Down,
+}
+
+impl BondDirection {
+    fn reversed(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+/// Atom in a parsed SMILES graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Atom {
+    /// Element symbol with standard capitalisation (`c` is stored as `C` with `aromatic` set).
+    pub element: String,
+    pub aromatic: bool,
+    /// Formal charge written inside brackets.
+    pub charge: i32,
+    pub isotope: Option<u16>,
+    /// Whether the atom was written in bracket form such as `[NH4+]`.
+    pub bracketed: bool,
+    /// Hydrogens attached to the atom, either written in brackets or derived from the
+    /// default valence of organic-subset atoms.
+    pub hydrogens: u32,
+    /// `@` or `@@` mark, interpreted against [`Molecule::stereo_neighbors`].
+    pub chirality: Option<Chirality>,
+}
+
+/// Bond between two atoms, referenced by their index in [`Molecule::atoms`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bond {
+    pub from: usize,
+    pub to: usize,
+    pub order: BondOrder,
+    /// `/` or `\` mark around a double bond; ring-closure marks are stored from `from`.
+    pub direction: Option<BondDirection>,
+}
+
+impl Bond {
+    /// Returns the atom on the other side of the bond from `atom`.
+    pub fn partner(&self, atom: usize) -> usize {
+        if self.from == atom {
+            self.to
+        } else {
+            self.from
+        }
+    }
+}
+
+/// Errors raised while parsing SMILES. Positions are character offsets into the input.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum SmilesError {
+    #[error("SMILES string is empty")]
+    Empty,
+    #[error("unexpected character '{character}' at position {position}")]
+    UnexpectedCharacter { character: char, position: usize },
+    #[error("unknown element '{symbol}' at position {position}")]
+    UnknownElement { symbol: String, position: usize },
+    #[error("unclosed bracket atom starting at position {position}")]
+    UnclosedBracket { position: usize },
+    #[error("unclosed branch opened at position {position}")]
+    UnclosedBranch { position: usize },
+    #[error("branch closed at position {position} was never opened")]
+    UnmatchedBranch { position: usize },
+    #[error("ring bond {label} is never closed")]
+    UnclosedRing { label: u32 },
+    #[error("ring bond {label} at position {position} closes on its own atom")]
+    SelfLoop { label: u32, position: usize },
+    #[error("bond at position {position} is not followed by an atom")]
+    DanglingBond { position: usize },
+}
+
+/// Molecular graph parsed from SMILES, with hydrogens stored as counts on heavy atoms.
+///
+/// Dot-disconnected SMILES such as `[Ca+2].[O-]C(=O)[O-]` produce a single molecule with
+/// several disconnected components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Molecule {
+    atoms: Vec<Atom>,
+    bonds: Vec<Bond>,
+    adjacency: Vec<Vec<usize>>,
+    written: Vec<Vec<Option<usize>>>,
+}
+
+impl Molecule {
+    /// Parses a SMILES string covering the organic subset, bracket atoms with charges and
+    /// hydrogen counts, aromatic atoms, ring closures, branches, and `.` separators.
+    /// Stereo markers are recorded as [`Atom::chirality`] and [`Bond::direction`]; see
+    /// [`crate::stereo`] for the descriptors derived from them.
+    pub fn parse(input: &str) -> Result<Self, SmilesError> {
+        let text: Vec<char> = input.trim().chars().collect();
+        if text.is_empty() {
+            return Err(SmilesError::Empty);
+        }
+
+        let mut molecule = SmilesParser::new(&text).parse()?;
+        molecule.assign_implicit_hydrogens();
+        Ok(molecule)
+    }
+
+    pub fn atoms(&self) -> &[Atom] {
+        &self.atoms
+    }
+
+    pub fn bonds(&self) -> &[Bond] {
+        &self.bonds
+    }
+
+    /// Iterates over `(neighbour index, bond)` pairs for `atom`.
+    pub fn neighbors(&self, atom: usize) -> impl Iterator<Item = (usize, &Bond)> + '_ {
+        self.adjacency[atom].iter().map(move |bond_index| {
+            let bond = &self.bonds[*bond_index];
+            (bond.partner(atom), bond)
+        })
+    }
+
+    /// Neighbours of `atom` in the order they were written, which is the order `@` and `@@`
+    /// refer to. `None` stands for a hydrogen written inside the atom's brackets.
+    pub fn stereo_neighbors(&self, atom: usize) -> &[Option<usize>] {
+        &self.written[atom]
+    }
+
+    /// Number of heavy-atom neighbours of `atom`.
+    pub fn degree(&self, atom: usize) -> usize {
+        self.adjacency[atom].len()
+    }
+
+    /// Returns the bond connecting `left` and `right`, if any.
+    pub fn bond_between(&self, left: usize, right: usize) -> Option<&Bond> {
+        self.neighbors(left)
+            .find(|(neighbor, _)| *neighbor == right)
+            .map(|(_, bond)| bond)
+    }
+
+    /// Sum of formal charges over all atoms.
+    pub fn net_charge(&self) -> i32 {
+        self.atoms.iter().map(|atom| atom.charge).sum()
+    }
+
+    /// Element counts including attached hydrogens.
+    pub fn element_counts(&self) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+
+        for atom in &self.atoms {
+            *counts.entry(atom.element.clone()).or_insert(0) += 1;
+            if atom.hydrogens > 0 {
+                *counts.entry("H".to_string()).or_insert(0) += atom.hydrogens;
+            }
+        }
+
+        counts
+    }
+
+    /// Molecular formula derived from the graph, including the net charge.
+    pub fn formula(&self) -> Formula {
+        Formula::from_counts(self.element_counts(), self.net_charge())
+    }
+
+    /// Groups atom indices into connected components, in order of first appearance.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut component_of = vec![usize::MAX; self.atoms.len()];
+        let mut components = Vec::new();
+
+        for start in 0..self.atoms.len() {
+            if component_of[start] != usize::MAX {
+                continue;
+            }
+
+            let id = components.len();
+            let mut members = vec![start];
+            component_of[start] = id;
+            let mut cursor = 0;
+
+            while cursor < members.len() {
+                let atom = members[cursor];
+                cursor += 1;
+                for (neighbor, _) in self.neighbors(atom) {
+                    if component_of[neighbor] == usize::MAX {
+                        component_of[neighbor] = id;
+                        members.push(neighbor);
+                    }
+                }
+            }
+
+            members.sort_unstable();
+            components.push(members);
+        }
+
+        components
+    }
+
+    /// Number of independent rings (the cyclomatic number of the graph).
+    pub fn ring_count(&self) -> usize {
+        self.bonds.len() + self.components().len() - self.atoms.len()
+    }
+
+    /// Returns one small ring per independent cycle, each listed as atoms in bond order.
+    ///
+    /// Every bond outside a breadth-first spanning tree closes a ring; the ring reported for
+    /// it is the shortest path between its atoms that avoids the bond itself. For the fused
+    /// and isolated rings found in textbook compounds this yields the smallest rings.
+    pub fn rings(&self) -> Vec<Vec<usize>> {
+        let mut tree_bonds = vec![false; self.bonds.len()];
+        let mut visited = vec![false; self.atoms.len()];
+
+        for start in 0..self.atoms.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut queue = VecDeque::from([start]);
+            while let Some(atom) = queue.pop_front() {
+                for &bond_index in &self.adjacency[atom] {
+                    let neighbor = self.bonds[bond_index].partner(atom);
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        tree_bonds[bond_index] = true;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        let mut rings = Vec::new();
+        for (bond_index, bond) in self.bonds.iter().enumerate() {
+            if tree_bonds[bond_index] {
+                continue;
+            }
+            if let Some(ring) = self.shortest_path_avoiding(bond.from, bond.to, bond_index) {
+                let mut key = ring.clone();
+                key.sort_unstable();
+                if seen.insert(key) {
+                    rings.push(ring);
+                }
+            }
+        }
+
+        rings
+    }
+
+    fn shortest_path_avoiding(&self, from: usize, to: usize, skipped: usize) -> Option<Vec<usize>> {
+        let mut previous = vec![usize::MAX; self.atoms.len()];
+        previous[from] = from;
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(atom) = queue.pop_front() {
+            if atom == to {
+                let mut path = vec![to];
+                let mut cursor = to;
+                while cursor != from {
+                    cursor = previous[cursor];
+                    path.push(cursor);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &bond_index in &self.adjacency[atom] {
+                if bond_index == skipped {
+                    continue;
+                }
+                let neighbor = self.bonds[bond_index].partner(atom);
+                if previous[neighbor] == usize::MAX {
+                    previous[neighbor] = atom;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn assign_implicit_hydrogens(&mut self) {
+        for index in 0..self.atoms.len() {
+            if !self.atoms[index].bracketed {
+                self.atoms[index].hydrogens = self.implicit_hydrogens(index);
+            }
+        }
+    }
+
+    /// Hydrogens an organic-subset atom written without brackets would be given.
+    fn implicit_hydrogens(&self, index: usize) -> u32 {
+        let mut used: u32 = self
+            .neighbors(index)
+            .map(|(_, bond)| bond.order.valence())
+            .sum();
+        let valences = default_valences(&self.atoms[index].element);
+        // Only an atom with a valence left over shares a π electron: the `s` of thiophene
+        // already uses its two bonds and takes no hydrogen.
+        let open = valences.iter().find(|valence| **valence >= used) != Some(&used);
+        if self.atoms[index].aromatic && open {
+            used += 1;
+        }
+
+        let valence = valences
+            .iter()
+            .copied()
+            .find(|valence| *valence >= used)
+            .unwrap_or(used);
+        valence - used
+    }
+}
+
+impl FromStr for Molecule {
+    type Err = SmilesError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+const ORGANIC_SUBSET: [&str; 10] = ["B", "C", "N", "O", "P", "S", "F", "Cl", "Br", "I"];
+const AROMATIC_ORGANIC: [char; 6] = ['b', 'c', 'n', 'o', 'p', 's'];
+
+fn default_valences(element: &str) -> &'static [u32] {
+    match element {
+        "B" => &[3],
+        "C" => &[4],
+        "N" | "P" => &[3, 5],
+        "O" => &[2],
+        "S" => &[2, 4, 6],
+        "F" | "Cl" | "Br" | "I" => &[1],
+        _ => &[],
+    }
+}
+
+struct RingOpening {
+    atom: usize,
+    order: Option<BondOrder>,
+    direction: Option<BondDirection>,
+    /// Index in the opening atom's written neighbours reserved for the closing atom.
+    slot: usize,
+}
+
+struct SmilesParser<'a> {
+    text: &'a [char],
+    position: usize,
+    atoms: Vec<Atom>,
+    bonds: Vec<Bond>,
+    rings: HashMap<u32, RingOpening>,
+    branches: Vec<(usize, usize)>,
+    previous: Option<usize>,
+    pending_bond: Option<(BondOrder, Option<BondDirection>, usize)>,
+    written: Vec<Vec<Option<usize>>>,
+}
+
+impl<'a> SmilesParser<'a> {
+    fn new(text: &'a [char]) -> Self {
+        Self {
+            text,
+            position: 0,
+            atoms: Vec::new(),
+            bonds: Vec::new(),
+            rings: HashMap::new(),
+            branches: Vec::new(),
+            previous: None,
+            pending_bond: None,
+            written: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text.get(self.position).copied()
+    }
+
+    fn unexpected(&self) -> SmilesError {
+        match self.peek() {
+            Some(character) => SmilesError::UnexpectedCharacter {
+                character,
+                position: self.position,
+            },
+            None => SmilesError::DanglingBond {
+                position: self.position.saturating_sub(1),
+            },
+        }
+    }
+
+    fn parse(mut self) -> Result<Molecule, SmilesError> {
+        while let Some(character) = self.peek() {
+            match character {
+                '(' => {
+                    let anchor = self.previous.ok_or_else(|| self.unexpected())?;
+                    self.branches.push((anchor, self.position));
+                    self.position += 1;
+                }
+                ')' => {
+                    if let Some((_, _, position)) = self.pending_bond {
+                        return Err(SmilesError::DanglingBond { position });
+                    }
+                    let (anchor, _) = self.branches.pop().ok_or(SmilesError::UnmatchedBranch {
+                        position: self.position,
+                    })?;
+                    self.previous = Some(anchor);
+                    self.position += 1;
+                }
+                '.' => {
+                    if let Some((_, _, position)) = self.pending_bond {
+                        return Err(SmilesError::DanglingBond { position });
+                    }
+                    if self.previous.is_none() {
+                        return Err(self.unexpected());
+                    }
+                    self.previous = None;
+                    self.position += 1;
+                }
+                '-' | '=' | '#' | '$' | ':' | '/' | '\\' => {
+                    if self.previous.is_none() || self.pending_bond.is_some() {
+                        return Err(self.unexpected());
+                    }
+                    let order = match character {
+                        '=' => BondOrder::Double,
+                        '#' => BondOrder::Triple,
+                        '$' => BondOrder::Quadruple,
+                        ':' => BondOrder::Aromatic,
+                        _ => BondOrder::Single,
+                    };
+                    let direction = match character {
+                        '/' => Some(BondDirection::Up),
+                        '\\' => Some(BondDirection::Down),
+                        _ => None,
+                    };
+                    self.pending_bond = Some((order, direction, self.position));
+                    self.position += 1;
+                }
+                '0'..='9' | '%' => self.parse_ring_closure()?,
+                '[' => {
+                    let atom = self.parse_bracket_atom()?;
+                    self.add_atom(atom);
+                }
+                _ => {
+                    let atom = self.parse_organic_atom()?;
+                    self.add_atom(atom);
+                }
+            }
+        }
+
+        if let Some((_, _, position)) = self.pending_bond {
+            return Err(SmilesError::DanglingBond { position });
+        }
+        if let Some((_, position)) = self.branches.first() {
+            return Err(SmilesError::UnclosedBranch {
+                position: *position,
+            });
+        }
+        if let Some(label) = self.rings.keys().min() {
+            return Err(SmilesError::UnclosedRing { label: *label });
+        }
+        if self.atoms.is_empty() {
+            return Err(SmilesError::Empty);
+        }
+
+        let mut adjacency = vec![Vec::new(); self.atoms.len()];
+        for (index, bond) in self.bonds.iter().enumerate() {
+            adjacency[bond.from].push(index);
+            adjacency[bond.to].push(index);
+        }
+
+        Ok(Molecule {
+            atoms: self.atoms,
+            bonds: self.bonds,
+            adjacency,
+            written: self.written,
+        })
+    }
+
+    fn add_atom(&mut self, atom: Atom) {
+        let index = self.atoms.len();
+        let bracket_hydrogen = atom.chirality.is_some() && atom.hydrogens > 0;
+        self.atoms.push(atom);
+        self.written.push(Vec::new());
+
+        if let Some(previous) = self.previous {
+            let (explicit, direction) = match self.pending_bond.take() {
+                Some((order, direction, _)) => (Some(order), direction),
+                None => (None, None),
+            };
+            let order = self.resolve_order(explicit, previous, index);
+            self.bonds.push(Bond {
+                from: previous,
+                to: index,
+                order,
+                direction,
+            });
+            self.written[previous].push(Some(index));
+            self.written[index].push(Some(previous));
+        }
+        if bracket_hydrogen {
+            self.written[index].push(None);
+        }
+
+        self.previous = Some(index);
+    }
+
+    fn resolve_order(&self, explicit: Option<BondOrder>, left: usize, right: usize) -> BondOrder {
+        match explicit {
+            Some(order) => order,
+            None if self.atoms[left].aromatic && self.atoms[right].aromatic => BondOrder::Aromatic,
+            None => BondOrder::Single,
+        }
+    }
+
+    fn parse_ring_closure(&mut self) -> Result<(), SmilesError> {
+        let start = self.position;
+        let current = self.previous.ok_or_else(|| self.unexpected())?;
+
+        let label = if self.peek() == Some('%') {
+            let digits = self.text.get(start + 1..start + 3).unwrap_or_default();
+            if digits.len() != 2 || !digits.iter().all(char::is_ascii_digit) {
+                self.position += 1;
+                return Err(self.unexpected());
+            }
+            self.position += 3;
+            digits
+                .iter()
+                .collect::<String>()
+                .parse()
+                .unwrap_or_default()
+        } else {
+            self.position += 1;
+            self.text[start].to_digit(10).unwrap_or_default()
+        };
+
+        let (explicit, direction) = match self.pending_bond.take() {
+            Some((order, direction, _)) => (Some(order), direction),
+            None => (None, None),
+        };
+
+        match self.rings.remove(&label) {
+            Some(opening) => {
+                if opening.atom == current {
+                    return Err(SmilesError::SelfLoop {
+                        label,
+                        position: start,
+                    });
+                }
+                let order = self.resolve_order(explicit.or(opening.order), opening.atom, current);
+                self.bonds.push(Bond {
+                    from: opening.atom,
+                    to: current,
+                    order,
+                    direction: opening.direction.or(direction.map(BondDirection::reversed)),
+                });
+                self.written[opening.atom][opening.slot] = Some(current);
+                self.written[current].push(Some(opening.atom));
+            }
+            None => {
+                let slot = self.written[current].len();
+                self.written[current].push(None);
+                self.rings.insert(
+                    label,
+                    RingOpening {
+                        atom: current,
+                        order: explicit,
+                        direction,
+                        slot,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_organic_atom(&mut self) -> Result<Atom, SmilesError> {
+        let start = self.position;
+        let character = self.text[start];
+
+        if AROMATIC_ORGANIC.contains(&character) {
+            self.position += 1;
+            return Ok(new_atom(character.to_ascii_uppercase().to_string(), true));
+        }
+
+        if !character.is_ascii_uppercase() {
+            return Err(self.unexpected());
+        }
+
+        let two_letter: String = self.text[start..(start + 2).min(self.text.len())]
+            .iter()
+            .collect();
+        if two_letter.len() == 2 && ORGANIC_SUBSET.contains(&two_letter.as_str()) {
+            self.position += 2;
+            return Ok(new_atom(two_letter, false));
+        }
+
+        let symbol = character.to_string();
+        if ORGANIC_SUBSET.contains(&symbol.as_str()) {
+            self.position += 1;
+            return Ok(new_atom(symbol, false));
+        }
+
+        Err(self.unexpected())
+    }
+
+    fn parse_bracket_atom(&mut self) -> Result<Atom, SmilesError> {
+        let open = self.position;
+        self.position += 1;
+
+        let isotope = self.parse_digits().map(|value| value as u16);
+        let symbol_start = self.position;
+        let (element, aromatic) = self.parse_bracket_symbol()?;
+        if periodic_table::element(&element).is_none() {
+            return Err(SmilesError::UnknownElement {
+                symbol: self.text[symbol_start..self.position].iter().collect(),
+                position: symbol_start,
+            });
+        }
+
+        let chirality = match (self.peek(), self.text.get(self.position + 1)) {
+            (Some('@'), Some('@')) => {
+                self.position += 2;
+                Some(Chirality::Clockwise)
+            }
+            (Some('@'), _) => {
+                self.position += 1;
+                Some(Chirality::Anticlockwise)
+            }
+            _ => None,
+        };
+
+        let mut hydrogens = 0;
+        if self.peek() == Some('H') {
+            self.position += 1;
+            hydrogens = self.parse_digits().unwrap_or(1);
+        }
+
+        let mut charge = 0i32;
+        if let Some(sign @ ('+' | '-')) = self.peek() {
+            let unit = if sign == '+' { 1 } else { -1 };
+            self.position += 1;
+            charge = match self.parse_digits() {
+                Some(magnitude) => unit * magnitude as i32,
+                None => {
+                    let mut total = unit;
+                    while self.peek() == Some(sign) {
+                        self.position += 1;
+                        total += unit;
+                    }
+                    total
+                }
+            };
+        }
+
+        if self.peek() == Some(':') {
+            self.position += 1;
+            if self.parse_digits().is_none() {
+                return Err(self.unexpected());
+            }
+        }
+
+        match self.peek() {
+            Some(']') => self.position += 1,
+            Some(_) => return Err(self.unexpected()),
+            None => return Err(SmilesError::UnclosedBracket { position: open }),
+        }
+
+        Ok(Atom {
+            element,
+            aromatic,
+            charge,
+            isotope,
+            bracketed: true,
+            hydrogens,
+            chirality,
+        })
+    }
+
+    fn parse_bracket_symbol(&mut self) -> Result<(String, bool), SmilesError> {
+        let Some(first) = self.peek() else {
+            return Err(self.unexpected());
+        };
+
+        if first.is_ascii_lowercase() {
+            let pair: String = self.text[self.position..(self.position + 2).min(self.text.len())]
+                .iter()
+                .collect();
+            if pair == "se" || pair == "as" {
+                self.position += 2;
+                let mut chars = pair.chars();
+                let element = chars
+                    .next()
+                    .map(|letter| letter.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default();
+                return Ok((element, true));
+            }
+            if AROMATIC_ORGANIC.contains(&first) {
+                self.position += 1;
+                return Ok((first.to_ascii_uppercase().to_string(), true));
+            }
+            return Err(self.unexpected());
+        }
+
+        if !first.is_ascii_uppercase() {
+            return Err(self.unexpected());
+        }
+
+        self.position += 1;
+        let mut symbol = first.to_string();
+        if let Some(second) = self.peek().filter(char::is_ascii_lowercase) {
+            let candidate = format!("{}{}", first, second);
+            if periodic_table::element(&candidate).is_some() {
+                symbol = candidate;
+                self.position += 1;
+            }
+        }
+
+        Ok((symbol, false))
+    }
+
+    fn parse_digits(&mut self) -> Option<u32> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|character| character.is_ascii_digit())
+        {
+            self.position += 1;
+        }
+
+        (start != self.position)
+            .then(|| self.text[start..self.position].iter().collect::<String>())
+            .and_then(|digits| digits.parse().ok())
+    }
+}
+
+fn new_atom(element: String, aromatic: bool) -> Atom {
+    Atom {
+        element,
+        aromatic,
+        charge: 0,
+        isotope: None,
+        bracketed: false,
+        hydrogens: 0,
+        chirality: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hydrogen_total(molecule: &Molecule) -> u32 {
+        molecule.atoms().iter().map(|atom| atom.hydrogens).sum()
+    }
+
+    #[test]
+    fn parses_chain_with_branch_and_implicit_hydrogens() {
+        let molecule = Molecule::parse("CC(C)CO").expect("SMILES should parse");
+
+        assert_eq!(molecule.atoms().len(), 5);
+        assert_eq!(molecule.bonds().len(), 4);
+        assert_eq!(molecule.degree(1), 3);
+        assert_eq!(molecule.atoms()[1].hydrogens, 1);
+        assert_eq!(molecule.atoms()[4].hydrogens, 1);
+        assert_eq!(hydrogen_total(&molecule), 10);
+    }
+
+    #[test]
+    fn parses_multiple_bonds() {
+        let molecule = Molecule::parse("CC(=O)O").expect("acetic acid should parse");
+        assert_eq!(
+            molecule.bond_between(1, 2).map(|bond| bond.order),
+            Some(BondOrder::Double)
+        );
+        assert_eq!(hydrogen_total(&molecule), 4);
+
+        let alkyne = Molecule::parse("CC#CC").expect("but-2-yne should parse");
+        assert_eq!(
+            alkyne.bond_between(1, 2).map(|bond| bond.order),
+            Some(BondOrder::Triple)
+        );
+        assert_eq!(hydrogen_total(&alkyne), 6);
+    }
+
+    #[test]
+    fn parses_aromatic_rings() {
+        let benzene = Molecule::parse("c1ccccc1").expect("benzene should parse");
+        assert_eq!(benzene.bonds().len(), 6);
+        assert!(
+            benzene
+                .bonds()
+                .iter()
+                .all(|bond| bond.order == BondOrder::Aromatic)
+        );
+        assert_eq!(hydrogen_total(&benzene), 6);
+
+        let pyridine = Molecule::parse("n1ccccc1").expect("pyridine should parse");
+        assert_eq!(pyridine.atoms()[0].hydrogens, 0);
+        assert_eq!(hydrogen_total(&pyridine), 5);
+
+        let furan = Molecule::parse("c1ccoc1").expect("furan should parse");
+        assert_eq!(hydrogen_total(&furan), 4);
+        let pyrrole = Molecule::parse("c1cc[nH]c1").expect("pyrrole should parse");
+        assert_eq!(hydrogen_total(&pyrrole), 5);
+        let thiophene = Molecule::parse("c1ccsc1").expect("thiophene should parse");
+        assert_eq!(thiophene.atoms()[3].hydrogens, 0);
+        assert_eq!(hydrogen_total(&thiophene), 4);
+
+        let toluene = Molecule::parse("Cc1ccccc1").expect("toluene should parse");
+        assert_eq!(
+            toluene.bond_between(0, 1).map(|bond| bond.order),
+            Some(BondOrder::Single)
+        );
+        assert_eq!(hydrogen_total(&toluene), 8);
+    }
+
+    #[test]
+    fn parses_bracket_atoms_and_salts() {
+        let molecule = Molecule::parse("[Ca+2].[O-]C(=O)[O-]").expect("salt should parse");
+
+        assert_eq!(molecule.components().len(), 2);
+        assert_eq!(molecule.net_charge(), 0);
+        assert_eq!(molecule.atoms()[0].element, "Ca");
+        assert_eq!(molecule.atoms()[0].charge, 2);
+        assert_eq!(hydrogen_total(&molecule), 0);
+
+        let ammonium = Molecule::parse("[NH4+]").expect("ammonium should parse");
+        assert_eq!(ammonium.atoms()[0].hydrogens, 4);
+        assert_eq!(ammonium.atoms()[0].charge, 1);
+
+        let double_minus = Molecule::parse("[O--]").expect("repeated sign should parse");
+        assert_eq!(double_minus.atoms()[0].charge, -2);
+    }
+
+    #[test]
+    fn accepts_stereo_markers() {
+        let molecule = Molecule::parse("OC[C@H](O)[C@@H](O)C=O").expect("sugar should parse");
+        assert_eq!(molecule.atoms().len(), 8);
+
+        let alkene = Molecule::parse("C/C=C\\C").expect("directional bonds should parse");
+        assert_eq!(alkene.bonds().len(), 3);
+    }
+
+    #[test]
+    fn records_stereo_markers() {
+        let molecule = Molecule::parse("N[C@@H](C)C(=O)O").expect("alanine should parse");
+        assert_eq!(molecule.atoms()[1].chirality, Some(Chirality::Clockwise));
+        assert_eq!(molecule.atoms()[0].chirality, None);
+        assert_eq!(
+            molecule.stereo_neighbors(1),
+            &[Some(0), None, Some(2), Some(3)]
+        );
+
+        let ring = Molecule::parse("C[C@H]1CCCCO1").expect("ring should parse");
+        assert_eq!(ring.atoms()[1].chirality, Some(Chirality::Anticlockwise));
+        assert_eq!(ring.stereo_neighbors(1), &[Some(0), None, Some(6), Some(2)]);
+
+        let alkene = Molecule::parse("C/C=C\\C").expect("alkene should parse");
+        let directions: Vec<_> = alkene.bonds().iter().map(|bond| bond.direction).collect();
+        assert_eq!(
+            directions,
+            vec![Some(BondDirection::Up), None, Some(BondDirection::Down)]
+        );
+
+        let closure = Molecule::parse("C/1=C/C.F1").expect("ring bond should parse");
+        assert_eq!(
+            closure.bond_between(0, 3).and_then(|bond| bond.direction),
+            Some(BondDirection::Up)
+        );
+        let closing = Molecule::parse("F1.C/1=C/C").expect("ring bond should parse");
+        assert_eq!(
+            closing.bond_between(0, 1).and_then(|bond| bond.direction),
+            Some(BondDirection::Down)
+        );
+    }
+
+    #[test]
+    fn derives_formula() {
+        let molecule = Molecule::parse("[Na+].[O-]C(=O)O").expect("SMILES should parse");
+        let expected = Formula::parse("NaHCO3").expect("formula should parse");
+
+        assert_eq!(molecule.formula(), expected);
+    }
+
+    #[test]
+    fn counts_rings() {
+        let rings = |smiles: &str| {
+            Molecule::parse(smiles)
+                .expect("SMILES should parse")
+                .ring_count()
+        };
+
+        assert_eq!(rings("CCO"), 0);
+        assert_eq!(rings("c1ccccc1"), 1);
+        assert_eq!(rings("c1ccc2ccccc2c1"), 2);
+        assert_eq!(rings("C1CC1.C1CC1"), 2);
+    }
+
+    #[test]
+    fn finds_smallest_rings() {
+        let ring_sizes = |smiles: &str| {
+            let mut sizes: Vec<usize> = Molecule::parse(smiles)
+                .expect("SMILES should parse")
+                .rings()
+                .iter()
+                .map(Vec::len)
+                .collect();
+            sizes.sort_unstable();
+            sizes
+        };
+
+        assert!(ring_sizes("CCO").is_empty());
+        assert_eq!(ring_sizes("c1ccccc1"), vec![6]);
+        assert_eq!(ring_sizes("c1ccc2ccccc2c1"), vec![6, 6]);
+        assert_eq!(ring_sizes("C1CC12CCCC2"), vec![3, 5]);
+        assert_eq!(ring_sizes("C1CCC2CCCCC2C1"), vec![6, 6]);
+
+        let cyclohexane = Molecule::parse("C1CCCCC1").expect("SMILES should parse");
+        let ring = &cyclohexane.rings()[0];
+        for (index, &atom) in ring.iter().enumerate() {
+            let next = ring[(index + 1) % ring.len()];
+            assert!(cyclohexane.bond_between(atom, next).is_some());
+        }
+    }
+
+    #[test]
+    fn key_ignores_atom_order() {
+        let key = |smiles: &str| {
+            Molecule::parse(smiles)
+                .expect("SMILES should parse")
+                .canonical_key()
+        };
+
+        assert_eq!(key("CCO"), key("OCC"));
+        assert_eq!(key("CC(C)CO"), key("OCC(C)C"));
+        assert_eq!(key("Cl[Na]"), key("[Na]Cl"));
+        assert_ne!(key("CCO"), key("COC"));
+        assert_ne!(key("CCCO"), key("CC(O)C"));
+    }
+
+    #[test]
+    fn key_tells_written_stereoisomers_apart() {
+        let parse = |smiles: &str| Molecule::parse(smiles).expect("SMILES should parse");
+        let maleic = parse("OC(=O)/C=C\\C(=O)O");
+        let fumaric = parse("OC(=O)/C=C/C(=O)O");
+
+        assert_ne!(maleic.canonical_key(), fumaric.canonical_key());
+        assert_eq!(
+            maleic.canonical_key(),
+            parse("OC(=O)\\C=C/C(=O)O").canonical_key()
+        );
+        assert_eq!(maleic.canonical_smiles(), fumaric.canonical_smiles());
+        assert_eq!(
+            parse("C[C@@H](C(=O)O)N").canonical_key(),
+            parse("N[C@@H](C)C(=O)O").canonical_key()
+        );
+        assert_ne!(
+            parse("C[C@@H](C(=O)O)N").canonical_key(),
+            parse("C[C@H](C(=O)O)N").canonical_key()
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(Molecule::parse(""), Err(SmilesError::Empty));
+        assert_eq!(
+            Molecule::parse("CN(C)H"),
+            Err(SmilesError::UnexpectedCharacter {
+                character: 'H',
+                position: 5
+            })
+        );
+        assert_eq!(
+            Molecule::parse("[Na+].[O-]Si(=O)O"),
+            Err(SmilesError::UnexpectedCharacter {
+                character: 'i',
+                position: 11
+            })
+        );
+        assert_eq!(
+            Molecule::parse("[Xx]"),
+            Err(SmilesError::UnknownElement {
+                symbol: "X".to_string(),
+                position: 1
+            })
+        );
+        assert_eq!(
+            Molecule::parse("CC(C"),
+            Err(SmilesError::UnclosedBranch { position: 2 })
+        );
+        assert_eq!(
+            Molecule::parse("CC)C"),
+            Err(SmilesError::UnmatchedBranch { position: 2 })
+        );
+        assert_eq!(
+            Molecule::parse("C1CC"),
+            Err(SmilesError::UnclosedRing { label: 1 })
+        );
+        assert_eq!(
+            Molecule::parse("CC="),
+            Err(SmilesError::DanglingBond { position: 2 })
+        );
+        assert_eq!(
+            Molecule::parse("[Na+"),
+            Err(SmilesError::UnclosedBracket { position: 0 })
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::{AROMATIC_ORGANIC, BondOrder, Molecule, ORGANIC_SUBSET};
+use crate::periodic_table;
+
+impl Molecule {
+    /// Writes the molecule as canonical SMILES: `OCC`, `C(O)C` and `[CH3]CO` all give `CCO`.
+    /// Stereo marks are left out, so the string describes the constitution only; see
+    /// [`canonical_key`](Self::canonical_key).
+    ///
+    /// Atoms are ranked by degree, element, isotope, charge and hydrogen count, the ranks
+    /// are refined with neighbouring ranks and bond orders, and remaining ties are broken one
+    /// at a time at the lowest atom index. Each component is written depth-first from its
+    /// lowest-ranked atom with branches in rank order, and components are joined in sorted
+    /// order. Two different structures never share a string, but a graph whose refinement
+    /// leaves atoms tied that are not symmetry-equivalent may be written differently for
+    /// different input orders.
+    pub fn canonical_smiles(&self) -> String {
+        self.canonical_form(&vec![None; self.atoms.len()]).0
+    }
+
+    /// Canonical SMILES followed by the written R/S and E/Z descriptors at their 1-based
+    /// positions in it, such as `CC(N)C(=O)O 2S` for L-alanine. Stereoisomers get different
+    /// keys once their configuration is written; without stereo marks the key is the
+    /// canonical SMILES alone.
+    pub fn canonical_key(&self) -> String {
+        let labels = crate::stereo::descriptor_labels(self);
+        let (smiles, order) = self.canonical_form(&labels);
+        let layer: Vec<String> = order
+            .iter()
+            .enumerate()
+            .filter_map(|(position, atom)| {
+                labels[*atom].map(|label| format!("{}{}", position + 1, label))
+            })
+            .collect();
+
+        if layer.is_empty() {
+            smiles
+        } else {
+            format!("{} {}", smiles, layer.join(","))
+        }
+    }
+
+    /// Canonical SMILES written with `stereo` as part of each atom's invariant, together with
+    /// the atoms in the order they were written.
+    fn canonical_form(&self, stereo: &[Option<&str>]) -> (String, Vec<usize>) {
+        let ranks = self.canonical_ranks(stereo);
+        let mut written: Vec<(String, Vec<usize>)> = self
+            .components()
+            .into_iter()
+            .filter_map(|component| {
+                let start = component.into_iter().min_by_key(|atom| ranks[*atom])?;
+                Some(CanonicalWriter::new(self, &ranks).write(start))
+            })
+            .collect();
+        written.sort();
+
+        let smiles: Vec<&str> = written.iter().map(|(text, _)| text.as_str()).collect();
+        let order = written
+            .iter()
+            .flat_map(|(_, atoms)| atoms.iter().copied())
+            .collect();
+        (smiles.join("."), order)
+    }
+
+    /// Distinct rank for every atom. Atoms tied after refinement are split at the lowest
+    /// index, so the ranks follow the input order wherever refinement leaves a choice.
+    fn canonical_ranks(&self, stereo: &[Option<&str>]) -> Vec<usize> {
+        let invariants: Vec<_> = self
+            .atoms
+            .iter()
+            .zip(stereo)
+            .enumerate()
+            .map(|(index, (atom, descriptor))| {
+                (
+                    self.degree(index),
+                    periodic_table::element(&atom.element).map_or(0, |data| data.atomic_number),
+                    atom.isotope,
+                    atom.charge,
+                    atom.hydrogens,
+                    atom.aromatic,
+                    *descriptor,
+                )
+            })
+            .collect();
+        let mut ranks = self.refine_ranks(rank_by(&invariants));
+
+        // Atoms still tied are usually symmetry-equivalent, and then any pick gives the same
+        // string. Refinement cannot prove that for every graph, and when the tied atoms are
+        // not equivalent the lowest index decides, so the string can depend on input order.
+        // Whichever atom is picked, the string spells out the whole graph: two different
+        // structures never share it.
+        while let Some(tied) = lowest_tied_rank(&ranks) {
+            let chosen = ranks
+                .iter()
+                .position(|rank| *rank == tied)
+                .expect("tied rank belongs to an atom");
+            let split: Vec<(usize, bool)> = ranks
+                .iter()
+                .enumerate()
+                .map(|(index, rank)| (*rank, index != chosen))
+                .collect();
+            ranks = self.refine_ranks(rank_by(&split));
+        }
+
+        ranks
+    }
+
+    fn refine_ranks(&self, mut ranks: Vec<usize>) -> Vec<usize> {
+        loop {
+            let keys: Vec<(usize, Vec<(usize, u8)>)> = (0..self.atoms.len())
+                .map(|index| {
+                    let mut neighborhood: Vec<(usize, u8)> = self
+                        .neighbors(index)
+                        .map(|(neighbor, bond)| (ranks[neighbor], bond.order as u8))
+                        .collect();
+                    neighborhood.sort_unstable();
+                    (ranks[index], neighborhood)
+                })
+                .collect();
+            let refined = rank_by(&keys);
+            if distinct_ranks(&refined) == distinct_ranks(&ranks) {
+                return ranks;
+            }
+            ranks = refined;
+        }
+    }
+}
+
+/// Writes one connected component, first finding which bonds close rings and then emitting
+/// atoms, ring digits and branches in depth-first order.
+struct CanonicalWriter<'a> {
+    molecule: &'a Molecule,
+    ranks: &'a [usize],
+    visited: Vec<bool>,
+    order: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    ring_bonds: HashSet<(usize, usize)>,
+    /// Ring bonds opened at each atom, closed later at the partner.
+    openings: Vec<Vec<usize>>,
+    closings: Vec<Vec<usize>>,
+    /// Ring digit held by each open ring bond, keyed by (opening atom, closing atom).
+    digits: Vec<(usize, usize, u32)>,
+}
+
+impl<'a> CanonicalWriter<'a> {
+    fn new(molecule: &'a Molecule, ranks: &'a [usize]) -> Self {
+        let count = molecule.atoms.len();
+        Self {
+            molecule,
+            ranks,
+            visited: vec![false; count],
+            order: Vec::new(),
+            children: vec![Vec::new(); count],
+            ring_bonds: HashSet::new(),
+            openings: vec![Vec::new(); count],
+            closings: vec![Vec::new(); count],
+            digits: Vec::new(),
+        }
+    }
+
+    fn write(mut self, start: usize) -> (String, Vec<usize>) {
+        self.explore(start, None);
+        let mut text = String::new();
+        self.emit(start, &mut text);
+        (text, self.order)
+    }
+
+    fn explore(&mut self, atom: usize, parent: Option<usize>) {
+        self.visited[atom] = true;
+        self.order.push(atom);
+
+        let mut neighbors: Vec<usize> = self
+            .molecule
+            .neighbors(atom)
+            .map(|(neighbor, _)| neighbor)
+            .filter(|neighbor| Some(*neighbor) != parent)
+            .collect();
+        neighbors.sort_by_key(|neighbor| self.ranks[*neighbor]);
+
+        for neighbor in neighbors {
+            if !self.visited[neighbor] {
+                self.children[atom].push(neighbor);
+                self.explore(neighbor, Some(atom));
+            } else if self
+                .ring_bonds
+                .insert((atom.min(neighbor), atom.max(neighbor)))
+            {
+                self.openings[neighbor].push(atom);
+                self.closings[atom].push(neighbor);
+            }
+        }
+    }
+
+    fn emit(&mut self, atom: usize, text: &mut String) {
+        text.push_str(&self.atom_text(atom));
+
+        let closings = self.closings[atom].clone();
+        let mut released = Vec::new();
+        for partner in closings {
+            let slot = self
+                .digits
+                .iter()
+                .position(|(from, to, _)| *from == partner && *to == atom)
+                .expect("ring bond was opened earlier");
+            let (_, _, digit) = self.digits.remove(slot);
+            push_ring_digit(text, digit);
+            released.push(digit);
+        }
+
+        for partner in self.openings[atom].clone() {
+            let digit = (1..)
+                .find(|digit| {
+                    !released.contains(digit)
+                        && !self.digits.iter().any(|(_, _, held)| held == digit)
+                })
+                .expect("ring digits are unbounded");
+            text.push_str(self.bond_text(atom, partner));
+            push_ring_digit(text, digit);
+            self.digits.push((atom, partner, digit));
+        }
+
+        let children = self.children[atom].clone();
+        for (position, child) in children.iter().enumerate() {
+            let branch = position + 1 < children.len();
+            if branch {
+                text.push('(');
+            }
+            text.push_str(self.bond_text(atom, *child));
+            self.emit(*child, text);
+            if branch {
+                text.push(')');
+            }
+        }
+    }
+
+    fn bond_text(&self, left: usize, right: usize) -> &'static str {
+        let order = self
+            .molecule
+            .bond_between(left, right)
+            .expect("written atoms are bonded")
+            .order;
+        let aromatic = self.molecule.atoms[left].aromatic && self.molecule.atoms[right].aromatic;
+
+        match order {
+            BondOrder::Single if aromatic => "-",
+            BondOrder::Single => "",
+            BondOrder::Aromatic if aromatic => "",
+            BondOrder::Aromatic => ":",
+            BondOrder::Double => "=",
+            BondOrder::Triple => "#",
+            BondOrder::Quadruple => "$",
+        }
+    }
+
+    fn atom_text(&self, atom: usize) -> String {
+        let data = &self.molecule.atoms[atom];
+        let symbol = if data.aromatic {
+            data.element.to_lowercase()
+        } else {
+            data.element.clone()
+        };
+
+        let organic = data.charge == 0
+            && data.isotope.is_none()
+            && ORGANIC_SUBSET.contains(&data.element.as_str())
+            && (!data.aromatic || symbol.chars().all(|c| AROMATIC_ORGANIC.contains(&c)))
+            && self.molecule.implicit_hydrogens(atom) == data.hydrogens;
+        if organic {
+            return symbol;
+        }
+
+        let mut text = String::from("[");
+        if let Some(isotope) = data.isotope {
+            text.push_str(&isotope.to_string());
+        }
+        text.push_str(&symbol);
+        match data.hydrogens {
+            0 => {}
+            1 => text.push('H'),
+            count => text.push_str(&format!("H{}", count)),
+        }
+        match data.charge {
+            0 => {}
+            1 => text.push('+'),
+            -1 => text.push('-'),
+            charge if charge > 0 => text.push_str(&format!("+{}", charge)),
+            charge => text.push_str(&format!("-{}", -charge)),
+        }
+        text.push(']');
+        text
+    }
+}
+
+fn push_ring_digit(text: &mut String, digit: u32) {
+    if digit < 10 {
+        text.push_str(&digit.to_string());
+    } else {
+        text.push_str(&format!("%{}", digit));
+    }
+}
+
+/// Ranks values by their sorted position, equal values sharing a rank.
+fn rank_by<K: Ord>(keys: &[K]) -> Vec<usize> {
+    let sorted: Vec<&K> = keys.iter().collect::<BTreeSet<_>>().into_iter().collect();
+    keys.iter()
+        .map(|key| {
+            sorted
+                .binary_search(&key)
+                .expect("every key is in the sorted set")
+        })
+        .collect()
+}
+
+fn distinct_ranks(ranks: &[usize]) -> usize {
+    ranks.iter().collect::<HashSet<_>>().len()
+}
+
+fn lowest_tied_rank(ranks: &[usize]) -> Option<usize> {
+    let mut sorted = ranks.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .windows(2)
+        .find(|pair| pair[0] == pair[1])
+        .map(|pair| pair[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(smiles: &str) -> String {
+        Molecule::parse(smiles)
+            .expect("SMILES should parse")
+            .canonical_smiles()
+    }
+
+    fn key(smiles: &str) -> String {
+        Molecule::parse(smiles)
+            .expect("SMILES should parse")
+            .canonical_key()
+    }
+
+    #[test]
+    fn writes_one_string_for_every_atom_order() {
+        assert_eq!(canonical("OCC"), "CCO");
+        assert_eq!(canonical("C(O)C"), "CCO");
+        assert_eq!(canonical("[CH3]CO"), "CCO");
+        assert_eq!(canonical("OC(=O)C"), canonical("CC(O)=O"));
+        assert_eq!(canonical("C1CCCCC1"), "C1CCCCC1");
+        assert_eq!(canonical("c1ccccc1O"), canonical("Oc1ccccc1"));
+        assert_eq!(canonical("[O-]C(=O)[O-].[Ca+2]"), "[Ca+2].[O-]C([O-])=O");
+        assert_ne!(canonical("CCO"), canonical("COC"));
+        // Decalin and bicyclopentyl refine to the same ranks but not the same string.
+        assert_ne!(canonical("C1CCC2CCCCC2C1"), canonical("C1CCC(C1)C1CCCC1"));
+    }
+
+    #[test]
+    fn canonical_smiles_parses_back_to_the_same_graph() {
+        for smiles in [
+            "CC(C)(C)c1ccc(O)cc1",
+            "C1CC2CCC1CC2",
+            "c1ccc2ccccc2c1",
+            "[NH4+].[Cl-]",
+            "OC[C@H]1OC(O)[C@H](O)[C@@H](O)[C@@H]1O",
+            "C#N",
+            "[13CH4]",
+            "C1CCC2CCCCC2C1",
+        ] {
+            let molecule = Molecule::parse(smiles).expect("SMILES should parse");
+            let written = molecule.canonical_smiles();
+            let reparsed = Molecule::parse(&written)
+                .unwrap_or_else(|error| panic!("{smiles} → {written}: {error}"));
+            assert_eq!(reparsed.canonical_smiles(), written, "{smiles}");
+            assert_eq!(reparsed.formula(), molecule.formula(), "{smiles}");
+        }
+    }
+
+    #[test]
+    fn key_carries_written_stereo_descriptors() {
+        assert_eq!(key("C[C@@H](C(=O)O)N"), "CC(N)C(=O)O 2S");
+        assert_eq!(key("N[C@@H](C)C(=O)O"), key("C[C@H](N)C(=O)O"));
+        assert_ne!(key("C[C@@H](C(=O)O)N"), key("C[C@H](C(=O)O)N"));
+        assert_eq!(key("C/C=C\\C"), "CC=CC 2Z,3Z");
+        assert_eq!(key("C/C=C/C"), key("C\\C=C\\C"));
+        assert_eq!(key("CC(N)C(=O)O"), "CC(N)C(=O)O");
+    }
+}
@@ -0,0 +1,514 @@
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+
+use crate::periodic_table;
+use crate::smiles::{BondDirection, BondOrder, Chirality, Molecule};
+
+/// Hierarchical digraphs larger than this are not explored further, and the branches being
+/// compared are treated as tied.
+const MAX_SPHERE_SIZE: usize = 4096;
+
+/// Cahn–Ingold–Prelog configuration of a stereocentre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Configuration {
+    R,
+    S,
+}
+
+impl Configuration {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::R => "R",
+            Self::S => "S",
+        }
+    }
+}
+
+impl fmt::Display for Configuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Configuration of a double bond: the higher-ranked substituents at its two ends lie on
+/// the same side (Z) or on opposite sides (E).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Geometry {
+    E,
+    Z,
+}
+
+impl Geometry {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::E => "E",
+            Self::Z => "Z",
+        }
+    }
+}
+
+impl fmt::Display for Geometry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Tetrahedral atom carrying four differently ranked substituents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stereocentre {
+    pub atom: usize,
+    /// `None` when the SMILES string has no `@`/`@@` mark on the atom.
+    pub configuration: Option<Configuration>,
+}
+
+/// Non-ring double bond whose ends each carry two differently ranked substituents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StereoBond {
+    pub atoms: (usize, usize),
+    /// `None` when the SMILES string has no `/` or `\` marks around the bond.
+    pub geometry: Option<Geometry>,
+}
+
+/// Stereocentres and stereogenic double bonds of a molecule, whether or not their
+/// configuration is written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StereoDescriptors {
+    pub centres: Vec<Stereocentre>,
+    pub double_bonds: Vec<StereoBond>,
+}
+
+impl StereoDescriptors {
+    pub fn is_empty(&self) -> bool {
+        self.centres.is_empty() && self.double_bonds.is_empty()
+    }
+
+    /// One line per stereocentre and double bond such as "Chiral centre C2: S" or
+    /// "Double bond C2=C3: Z", numbering atoms in SMILES order.
+    pub fn summary(&self, molecule: &Molecule) -> Vec<String> {
+        let label = |atom: usize| format!("{}{}", molecule.atoms()[atom].element, atom + 1);
+        let centres = self.centres.iter().map(|centre| {
+            format!(
+                "Chiral centre {}: {}",
+                label(centre.atom),
+                centre
+                    .configuration
+                    .map_or("not specified", Configuration::as_str)
+            )
+        });
+        let bonds = self.double_bonds.iter().map(|bond| {
+            format!(
+                "Double bond {}={}: {}",
+                label(bond.atoms.0),
+                label(bond.atoms.1),
+                bond.geometry.map_or("not specified", Geometry::as_str)
+            )
+        });
+
+        centres.chain(bonds).collect()
+    }
+}
+
+/// Finds stereocentres and stereogenic double bonds, reading R/S from `@`/`@@` and E/Z from
+/// `/` and `\` where the SMILES string specifies them.
+///
+/// Substituents are ranked with the CIP sequence rules on atomic number, exploring the
+/// hierarchical digraph sphere by sphere with duplicate atoms for multiple bonds and ring
+/// closures. Aromatic atoms receive one duplicate of an aromatic neighbour, standing in
+/// for a Kekulé structure.
+pub fn stereo_descriptors(molecule: &Molecule) -> StereoDescriptors {
+    let centres = (0..molecule.atoms().len())
+        .filter_map(|atom| stereocentre(molecule, atom))
+        .collect();
+    let rings = molecule.rings();
+    let double_bonds = molecule
+        .bonds()
+        .iter()
+        .filter(|bond| bond.order == BondOrder::Double && !in_ring(&rings, bond.from, bond.to))
+        .filter_map(|bond| stereo_bond(molecule, bond.from, bond.to))
+        .collect();
+
+    StereoDescriptors {
+        centres,
+        double_bonds,
+    }
+}
+
+/// Written descriptor of each atom, for structure keys that must tell stereoisomers apart.
+/// Both ends of a double bond carry its E/Z label.
+pub(crate) fn descriptor_labels(molecule: &Molecule) -> Vec<Option<&'static str>> {
+    let mut labels = vec![None; molecule.atoms().len()];
+    let marked = molecule.atoms().iter().any(|atom| atom.chirality.is_some())
+        || molecule.bonds().iter().any(|bond| bond.direction.is_some());
+    if !marked {
+        return labels;
+    }
+
+    let descriptors = stereo_descriptors(molecule);
+    for centre in &descriptors.centres {
+        labels[centre.atom] = centre.configuration.map(Configuration::as_str);
+    }
+    for bond in &descriptors.double_bonds {
+        let geometry = bond.geometry.map(Geometry::as_str);
+        labels[bond.atoms.0] = geometry;
+        labels[bond.atoms.1] = geometry;
+    }
+    labels
+}
+
+fn stereocentre(molecule: &Molecule, atom: usize) -> Option<Stereocentre> {
+    let hydrogens = molecule.atoms()[atom].hydrogens as usize;
+    if hydrogens > 1
+        || molecule.degree(atom) + hydrogens != 4
+        || molecule
+            .neighbors(atom)
+            .any(|(_, bond)| bond.order != BondOrder::Single)
+    {
+        return None;
+    }
+
+    let ranked = rank_substituents(molecule, atom, None)?;
+    Some(Stereocentre {
+        atom,
+        configuration: configuration(molecule, atom, &ranked),
+    })
+}
+
+/// Reads R/S from the `@`/`@@` mark: with the lowest-ranked substituent written first, `@`
+/// lists the rest anticlockwise, which is clockwise seen from the opposite side, so an
+/// even permutation of [lowest, highest, second, third] under `@` is R.
+fn configuration(
+    molecule: &Molecule,
+    atom: usize,
+    ranked: &[Option<usize>],
+) -> Option<Configuration> {
+    let chirality = molecule.atoms()[atom].chirality?;
+    let written = molecule.stereo_neighbors(atom);
+    if written.len() != 4 {
+        return None;
+    }
+
+    let reference = [ranked[3], ranked[0], ranked[1], ranked[2]];
+    let keys = written
+        .iter()
+        .map(|neighbor| reference.iter().position(|candidate| candidate == neighbor))
+        .collect::<Option<Vec<usize>>>()?;
+    let inversions = (0..4)
+        .flat_map(|i| (i + 1..4).map(move |j| (i, j)))
+        .filter(|&(i, j)| keys[i] > keys[j])
+        .count();
+
+    Some(match (chirality, inversions % 2 == 0) {
+        (Chirality::Anticlockwise, true) | (Chirality::Clockwise, false) => Configuration::R,
+        _ => Configuration::S,
+    })
+}
+
+fn stereo_bond(molecule: &Molecule, left: usize, right: usize) -> Option<StereoBond> {
+    let left_ranked = rank_substituents(molecule, left, Some(right))?;
+    let right_ranked = rank_substituents(molecule, right, Some(left))?;
+    if left_ranked.len() != 2 || right_ranked.len() != 2 {
+        return None;
+    }
+
+    let geometry = match (
+        substituent_side(molecule, left, right, left_ranked[0]),
+        substituent_side(molecule, right, left, right_ranked[0]),
+    ) {
+        (Some(left_side), Some(right_side)) if left_side == right_side => Some(Geometry::Z),
+        (Some(_), Some(_)) => Some(Geometry::E),
+        _ => None,
+    };
+
+    Some(StereoBond {
+        atoms: (left, right),
+        geometry,
+    })
+}
+
+/// Whether two substituents on either end of a double bond lie on the same side according
+/// to the written `/` and `\` marks, or `None` when the marks do not say.
+pub(crate) fn same_side(
+    molecule: &Molecule,
+    (left, left_substituent): (usize, usize),
+    (right, right_substituent): (usize, usize),
+) -> Option<bool> {
+    let left_side = substituent_side(molecule, left, right, Some(left_substituent))?;
+    let right_side = substituent_side(molecule, right, left, Some(right_substituent))?;
+    Some(left_side == right_side)
+}
+
+/// Side (+1 above, -1 below) of a substituent (`None` for a hydrogen) on the `end` of a
+/// double bond to `partner`, taken from its own directional bond or opposite to the other
+/// substituent's.
+fn substituent_side(
+    molecule: &Molecule,
+    end: usize,
+    partner: usize,
+    substituent: Option<usize>,
+) -> Option<i8> {
+    let written = |neighbor: usize| {
+        let bond = molecule.bond_between(end, neighbor)?;
+        let up = match bond.direction? {
+            BondDirection::Up => 1,
+            BondDirection::Down => -1,
+        };
+        Some(if bond.from == neighbor { -up } else { up })
+    };
+
+    substituent.and_then(written).or_else(|| {
+        molecule
+            .neighbors(end)
+            .map(|(neighbor, _)| neighbor)
+            .filter(|&neighbor| neighbor != partner && Some(neighbor) != substituent)
+            .find_map(written)
+            .map(|side| -side)
+    })
+}
+
+/// Substituents of `atom` other than `excluded`, highest CIP rank first, with `None`
+/// standing for a hydrogen. Returns `None` when two substituents tie.
+fn rank_substituents(
+    molecule: &Molecule,
+    atom: usize,
+    excluded: Option<usize>,
+) -> Option<Vec<Option<usize>>> {
+    let hydrogens = molecule.atoms()[atom].hydrogens as usize;
+    let mut substituents: Vec<Option<usize>> = molecule
+        .neighbors(atom)
+        .map(|(neighbor, _)| neighbor)
+        .filter(|neighbor| Some(*neighbor) != excluded)
+        .map(Some)
+        .chain(std::iter::repeat_n(None, hydrogens))
+        .collect();
+
+    let branch = |substituent: Option<usize>| match substituent {
+        Some(neighbor) => Node::branch(molecule, atom, neighbor),
+        None => Node::leaf(1),
+    };
+    substituents.sort_by(|left, right| compare_branches(molecule, branch(*right), branch(*left)));
+
+    let tied = substituents.windows(2).any(|pair| {
+        compare_branches(molecule, branch(pair[0]), branch(pair[1])) == Ordering::Equal
+    });
+    (!tied).then_some(substituents)
+}
+
+/// Vertex of the hierarchical digraph grown outward from a stereocentre. Leaves stand for
+/// hydrogens and duplicate atoms and have no substituents of their own.
+#[derive(Debug, Clone)]
+struct Node {
+    atomic_number: u8,
+    /// Atoms from the stereocentre up to this vertex, or empty for a leaf.
+    path: Vec<usize>,
+}
+
+impl Node {
+    fn branch(molecule: &Molecule, centre: usize, atom: usize) -> Self {
+        Self {
+            atomic_number: atomic_number(molecule, atom),
+            path: vec![centre, atom],
+        }
+    }
+
+    fn leaf(atomic_number: u8) -> Self {
+        Self {
+            atomic_number,
+            path: Vec::new(),
+        }
+    }
+
+    /// Substituents of this vertex, highest atomic number first.
+    fn children(&self, molecule: &Molecule) -> Vec<Node> {
+        let [.., parent, atom] = self.path[..] else {
+            return Vec::new();
+        };
+
+        let mut children = Vec::new();
+        for (neighbor, bond) in molecule.neighbors(atom) {
+            let duplicates = match bond.order {
+                BondOrder::Double => 1,
+                BondOrder::Triple => 2,
+                BondOrder::Quadruple => 3,
+                BondOrder::Single | BondOrder::Aromatic => 0,
+            };
+            let number = atomic_number(molecule, neighbor);
+            children.extend(std::iter::repeat_n(Node::leaf(number), duplicates));
+
+            if neighbor == parent {
+                continue;
+            }
+            if self.path.contains(&neighbor) {
+                children.push(Node::leaf(number));
+            } else {
+                let mut path = self.path.clone();
+                path.push(neighbor);
+                children.push(Node {
+                    atomic_number: number,
+                    path,
+                });
+            }
+        }
+
+        if molecule.atoms()[atom].aromatic
+            && let Some((neighbor, _)) = molecule
+                .neighbors(atom)
+                .find(|(_, bond)| bond.order == BondOrder::Aromatic)
+        {
+            children.push(Node::leaf(atomic_number(molecule, neighbor)));
+        }
+        children.extend(std::iter::repeat_n(
+            Node::leaf(1),
+            molecule.atoms()[atom].hydrogens as usize,
+        ));
+
+        children.sort_by_key(|child| Reverse(child.atomic_number));
+        children
+    }
+}
+
+/// Compares two branches sphere by sphere: first their root atoms, then the sets of
+/// substituents of each vertex in the previous sphere, taken in rank order.
+fn compare_branches(molecule: &Molecule, left: Node, right: Node) -> Ordering {
+    let mut left_sphere = vec![vec![left]];
+    let mut right_sphere = vec![vec![right]];
+
+    while !left_sphere.is_empty() || !right_sphere.is_empty() {
+        let sets = left_sphere.len().max(right_sphere.len());
+        for index in 0..sets {
+            let ordering = compare_sets(
+                left_sphere.get(index).map_or(&[], Vec::as_slice),
+                right_sphere.get(index).map_or(&[], Vec::as_slice),
+            );
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        left_sphere = next_sphere(molecule, &left_sphere);
+        right_sphere = next_sphere(molecule, &right_sphere);
+        let size = |sphere: &[Vec<Node>]| sphere.iter().map(Vec::len).sum::<usize>();
+        if size(&left_sphere).max(size(&right_sphere)) > MAX_SPHERE_SIZE {
+            break;
+        }
+    }
+
+    Ordering::Equal
+}
+
+fn next_sphere(molecule: &Molecule, sphere: &[Vec<Node>]) -> Vec<Vec<Node>> {
+    sphere
+        .iter()
+        .flatten()
+        .filter(|node| !node.path.is_empty())
+        .map(|node| node.children(molecule))
+        .collect()
+}
+
+/// Compares two substituent sets by atomic number, highest first; a missing substituent
+/// counts as a phantom atom of atomic number zero.
+fn compare_sets(left: &[Node], right: &[Node]) -> Ordering {
+    (0..left.len().max(right.len()))
+        .map(|index| {
+            let number = |set: &[Node]| set.get(index).map_or(0, |node| node.atomic_number);
+            number(left).cmp(&number(right))
+        })
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn atomic_number(molecule: &Molecule, atom: usize) -> u8 {
+    periodic_table::element(&molecule.atoms()[atom].element)
+        .map_or(0, |element| element.atomic_number)
+}
+
+fn in_ring(rings: &[Vec<usize>], left: usize, right: usize) -> bool {
+    rings.iter().any(|ring| {
+        ring.iter().enumerate().any(|(index, &atom)| {
+            let next = ring[(index + 1) % ring.len()];
+            (atom, next) == (left, right) || (atom, next) == (right, left)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptors(smiles: &str) -> StereoDescriptors {
+        stereo_descriptors(&Molecule::parse(smiles).expect("SMILES should parse"))
+    }
+
+    fn configurations(smiles: &str) -> Vec<(usize, Option<Configuration>)> {
+        descriptors(smiles)
+            .centres
+            .iter()
+            .map(|centre| (centre.atom, centre.configuration))
+            .collect()
+    }
+
+    fn geometries(smiles: &str) -> Vec<Option<Geometry>> {
+        descriptors(smiles)
+            .double_bonds
+            .iter()
+            .map(|bond| bond.geometry)
+            .collect()
+    }
+
+    #[test]
+    fn assigns_r_and_s_from_chirality_marks() {
+        use Configuration::{R, S};
+
+        assert_eq!(configurations("N[C@@H](C)C(=O)O"), vec![(1, Some(S))]);
+        assert_eq!(configurations("C[C@@H](C(=O)O)N"), vec![(1, Some(S))]);
+        assert_eq!(configurations("C[C@H](C(=O)O)N"), vec![(1, Some(R))]);
+        assert_eq!(configurations("[C@@H](C)(C(=O)O)N"), vec![(0, Some(R))]);
+        assert_eq!(configurations("C([C@@H](C(=O)O)N)S"), vec![(1, Some(R))]);
+        assert_eq!(
+            configurations("C([C@H]([C@H]([C@@H]([C@H](C=O)O)O)O)O)O"),
+            vec![(1, Some(R)), (2, Some(R)), (3, Some(S)), (4, Some(R))]
+        );
+        assert_eq!(configurations("C[C@H]1CCCCO1"), vec![(1, Some(S))]);
+    }
+
+    #[test]
+    fn finds_unmarked_stereocentres() {
+        assert_eq!(configurations("CC(N)C(=O)O"), vec![(1, None)]);
+        assert!(configurations("NCC(=O)O").is_empty());
+        assert!(configurations("CC(C)C").is_empty());
+        assert!(configurations("CC(O)(C)CC").is_empty());
+        assert_eq!(configurations("CCC(C)O").len(), 1);
+    }
+
+    #[test]
+    fn assigns_e_and_z_from_bond_directions() {
+        use Geometry::{E, Z};
+
+        assert_eq!(geometries("OC(=O)/C=C\\C(=O)O"), vec![Some(Z)]);
+        assert_eq!(geometries("OC(=O)/C=C/C(=O)O"), vec![Some(E)]);
+        assert_eq!(geometries("F/C=C/F"), vec![Some(E)]);
+        assert_eq!(geometries("C(\\F)=C/F"), vec![Some(E)]);
+        assert_eq!(geometries("F/C=C(/Cl)Br"), vec![Some(Z)]);
+        assert_eq!(geometries("CCCCCCCC/C=C\\CCCCCCCC(=O)O"), vec![Some(Z)]);
+        assert_eq!(geometries("C/C=C/C=C\\C"), vec![Some(E), Some(Z)]);
+        assert_eq!(geometries("CC=CC"), vec![None]);
+        assert!(geometries("CC=C").is_empty());
+        assert!(geometries("CC(C)=CC").is_empty());
+        assert!(geometries("C1CC=CCC1").is_empty());
+    }
+
+    #[test]
+    fn summarises_descriptors() {
+        let molecule = Molecule::parse("C[C@@H](C(=O)O)N").expect("SMILES should parse");
+        assert_eq!(
+            stereo_descriptors(&molecule).summary(&molecule),
+            vec!["Chiral centre C2: S"]
+        );
+
+        let molecule = Molecule::parse("OC(=O)/C=C\\C(=O)O").expect("SMILES should parse");
+        assert_eq!(
+            stereo_descriptors(&molecule).summary(&molecule),
+            vec!["Double bond C4=C5: Z"]
+        );
+        assert!(descriptors("CCO").is_empty());
+    }
+}
@@ -0,0 +1,504 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::catalog::{Catalog, CatalogEntry};
+use crate::compound::{Compound, Formula, FormulaError, katex};
+use crate::functional_groups::FunctionalGroupKind;
+use crate::nomenclature;
+use crate::smiles::{Molecule, SmilesError};
+
+/// Problem detected while cross-checking the data of a single compound.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EntryProblem {
+    #[error("SMILES could not be parsed: {0}")]
+    InvalidSmiles(SmilesError),
+    #[error("molecular formula could not be parsed: {0}")]
+    InvalidFormula(FormulaError),
+    #[error("SMILES disagrees with molecular formula ({})", join_differences(.differences))]
+    FormulaMismatch { differences: Vec<CountDifference> },
+    #[error("KaTeX markup {katex} disagrees with {field} {plain}")]
+    KatexMismatch {
+        /// Plain field the markup was compared with, e.g. `"skeletal_formula"`.
+        field: String,
+        katex: String,
+        plain: String,
+    },
+    #[error(
+        "functional groups disagree with SMILES (declared only: {}; detected only: {})",
+        join_labels(.declared_only),
+        join_labels(.detected_only)
+    )]
+    FunctionalGroupMismatch {
+        declared_only: Vec<String>,
+        detected_only: Vec<String>,
+    },
+    #[error("name does not match the ions of the formula (expected {expected})")]
+    IonicNameMismatch { expected: String },
+    #[error("name does not match the structure (SMILES gives {generated})")]
+    SystematicNameMismatch { generated: String },
+}
+
+/// Difference in one element count (or in the net charge) between the declared
+/// molecular formula and the formula derived from SMILES.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountDifference {
+    /// Element symbol, or `"charge"` for the net charge.
+    pub label: String,
+    pub declared: i64,
+    pub derived: i64,
+}
+
+impl fmt::Display for CountDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: formula {}, SMILES {}",
+            self.label, self.declared, self.derived
+        )
+    }
+}
+
+fn join_differences(differences: &[CountDifference]) -> String {
+    differences
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn join_labels(labels: &[String]) -> String {
+    if labels.is_empty() {
+        "none".to_string()
+    } else {
+        labels.join(", ")
+    }
+}
+
+/// Problem reported for a catalog entry, with enough context to locate the source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryIssue {
+    pub iupac_name: String,
+    pub categories: Vec<String>,
+    pub problem: EntryProblem,
+}
+
+impl EntryIssue {
+    fn new(entry: &CatalogEntry, problem: EntryProblem) -> Self {
+        Self {
+            iupac_name: entry.compound.iupac_name.clone(),
+            categories: entry.categories.clone(),
+            problem,
+        }
+    }
+}
+
+impl fmt::Display for EntryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}]: {}",
+            self.iupac_name,
+            self.categories.join(" / "),
+            self.problem
+        )
+    }
+}
+
+/// Compares the formula derived from `compound.smiles` with `compound.molecular_formula`.
+///
+/// Compounds without SMILES are skipped. Element counts and net charge must both agree,
+/// so salts written without counter-ions or structures missing hydrogens are reported.
+pub fn check_structure_formula(compound: &Compound) -> Option<EntryProblem> {
+    let smiles = compound
+        .smiles
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())?;
+
+    let molecule = match Molecule::parse(smiles) {
+        Ok(molecule) => molecule,
+        Err(error) => return Some(EntryProblem::InvalidSmiles(error)),
+    };
+    let declared = match compound.formula() {
+        Ok(formula) => formula,
+        Err(error) => return Some(EntryProblem::InvalidFormula(error)),
+    };
+
+    let differences = count_differences(&declared, &molecule.formula());
+    (!differences.is_empty()).then_some(EntryProblem::FormulaMismatch { differences })
+}
+
+/// Runs [`check_structure_formula`] over every catalog entry. Entries that appear in
+/// several files are reported once per file so each copy can be corrected.
+pub fn check_catalog_structures(catalog: &Catalog) -> Vec<EntryIssue> {
+    catalog
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            check_structure_formula(&entry.compound).map(|problem| EntryIssue::new(entry, problem))
+        })
+        .collect()
+}
+
+/// Compares `katex_skeletal_formula` and `katex_molecular_formula` with the plain fields
+/// they render. Markup outside a single `\ce{}` formula and values either side that do not
+/// parse as formulas are skipped; otherwise both must give the same composition and charge.
+pub fn check_katex_formulas(compound: &Compound) -> Vec<EntryProblem> {
+    [
+        (
+            "skeletal_formula",
+            &compound.skeletal_formula,
+            &compound.katex_skeletal_formula,
+        ),
+        (
+            "molecular_formula",
+            &compound.molecular_formula,
+            &compound.katex_molecular_formula,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, plain, markup)| {
+        let markup = markup.as_deref()?;
+        let rendered = Formula::parse(&katex::from_mhchem(markup)?).ok()?;
+        let declared = Formula::parse(plain).ok()?;
+        (!rendered.same_composition(&declared)).then(|| EntryProblem::KatexMismatch {
+            field: field.to_string(),
+            katex: markup.to_string(),
+            plain: plain.clone(),
+        })
+    })
+    .collect()
+}
+
+/// Runs [`check_katex_formulas`] over every catalog entry, once per file like
+/// [`check_catalog_structures`].
+pub fn check_catalog_katex(catalog: &Catalog) -> Vec<EntryIssue> {
+    catalog
+        .entries()
+        .iter()
+        .flat_map(|entry| {
+            check_katex_formulas(&entry.compound)
+                .into_iter()
+                .map(move |problem| EntryIssue::new(entry, problem))
+        })
+        .collect()
+}
+
+/// Compares the hand-entered `functional_groups` with those detected in the SMILES graph.
+///
+/// Only compounds with a parsable SMILES string and at least one recognised organic group
+/// label are checked, so inorganic labels such as `"Sulfate"` and empty lists are skipped.
+pub fn check_functional_groups(compound: &Compound) -> Option<EntryProblem> {
+    let detected: BTreeSet<FunctionalGroupKind> =
+        compound.detected_functional_groups()?.into_iter().collect();
+    let declared: BTreeSet<FunctionalGroupKind> = compound
+        .functional_groups
+        .iter()
+        .filter_map(|group| FunctionalGroupKind::from_label(&group.name_en))
+        .collect();
+    if declared.is_empty() {
+        return None;
+    }
+
+    let names = |kinds: std::collections::btree_set::Difference<'_, FunctionalGroupKind>| {
+        kinds
+            .map(|kind| kind.name_en().to_string())
+            .collect::<Vec<_>>()
+    };
+    let declared_only = names(declared.difference(&detected));
+    let detected_only = names(detected.difference(&declared));
+
+    (!declared_only.is_empty() || !detected_only.is_empty()).then_some(
+        EntryProblem::FunctionalGroupMismatch {
+            declared_only,
+            detected_only,
+        },
+    )
+}
+
+/// Runs [`check_functional_groups`] over every catalog entry.
+pub fn check_catalog_functional_groups(catalog: &Catalog) -> Vec<EntryIssue> {
+    catalog
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            check_functional_groups(&entry.compound).map(|problem| EntryIssue::new(entry, problem))
+        })
+        .collect()
+}
+
+/// Compares `iupac_name` with the name composed from the ions of `molecular_formula`
+/// ("iron(III) sulfate" for `Fe2(SO4)3`). Formulas that do not split into known ions are
+/// skipped.
+pub fn check_ionic_name(compound: &Compound) -> Option<EntryProblem> {
+    let composition = compound.ionic_composition()?;
+    (!composition.matches_name(&compound.iupac_name)).then(|| EntryProblem::IonicNameMismatch {
+        expected: composition.name(),
+    })
+}
+
+/// Runs [`check_ionic_name`] over the entries under `Inorganic/Salts`. Other inorganic
+/// entries use names the ion tables do not cover, such as `potassium chromate(VI)` or
+/// complexes.
+pub fn check_catalog_salt_names(catalog: &Catalog) -> Vec<EntryIssue> {
+    catalog
+        .entries()
+        .iter()
+        .filter(|entry| entry.categories.starts_with(&SALTS_PATH.map(String::from)))
+        .filter_map(|entry| {
+            check_ionic_name(&entry.compound).map(|problem| EntryIssue::new(entry, problem))
+        })
+        .collect()
+}
+
+const SALTS_PATH: [&str; 2] = ["Inorganic", "Salts"];
+
+/// Compares `iupac_name` with the name generated from `smiles` by
+/// [`nomenclature::name_molecule`]. Compounds without SMILES, and structures the generator
+/// does not cover (amines, amides, aromatics, …), are skipped.
+pub fn check_systematic_name(compound: &Compound) -> Option<EntryProblem> {
+    let smiles = compound
+        .smiles
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())?;
+    let molecule = Molecule::parse(smiles).ok()?;
+    let generated = nomenclature::name_molecule(&molecule).ok()?;
+
+    (!generated.eq_ignore_ascii_case(compound.iupac_name.trim()))
+        .then_some(EntryProblem::SystematicNameMismatch { generated })
+}
+
+/// Runs [`check_systematic_name`] over the entries under `Organic/Aliphatic_compounds`.
+pub fn check_catalog_systematic_names(catalog: &Catalog) -> Vec<EntryIssue> {
+    catalog
+        .entries()
+        .iter()
+        .filter(|entry| {
+            entry
+                .categories
+                .starts_with(&ALIPHATIC_PATH.map(String::from))
+        })
+        .filter_map(|entry| {
+            check_systematic_name(&entry.compound).map(|problem| EntryIssue::new(entry, problem))
+        })
+        .collect()
+}
+
+const ALIPHATIC_PATH: [&str; 2] = ["Organic", "Aliphatic_compounds"];
+
+fn count_differences(declared: &Formula, derived: &Formula) -> Vec<CountDifference> {
+    let symbols: BTreeSet<&String> = declared
+        .elements()
+        .keys()
+        .chain(derived.elements().keys())
+        .collect();
+
+    let mut differences: Vec<CountDifference> = symbols
+        .into_iter()
+        .filter(|symbol| declared.count(symbol) != derived.count(symbol))
+        .map(|symbol| CountDifference {
+            label: symbol.clone(),
+            declared: i64::from(declared.count(symbol)),
+            derived: i64::from(derived.count(symbol)),
+        })
+        .collect();
+
+    if declared.charge() != derived.charge() {
+        differences.push(CountDifference {
+            label: "charge".to_string(),
+            declared: i64::from(declared.charge()),
+            derived: i64::from(derived.charge()),
+        });
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound(name: &str, molecular_formula: &str, smiles: Option<&str>) -> Compound {
+        Compound {
+            iupac_name: name.to_string(),
+            common_name: None,
+            local_name: None,
+            skeletal_formula: molecular_formula.to_string(),
+            molecular_formula: molecular_formula.to_string(),
+            series_general_formula: None,
+            functional_groups: Vec::new(),
+            notes: None,
+            smiles: smiles.map(str::to_string),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
+        }
+    }
+
+    #[test]
+    fn accepts_consistent_entries() {
+        assert_eq!(
+            check_structure_formula(&compound("hydrogen chloride", "HCl", Some("Cl"))),
+            None
+        );
+        assert_eq!(
+            check_structure_formula(&compound(
+                "copper(II) sulfate pentahydrate",
+                "CuSO4·5H2O",
+                Some("[Cu+2].[O-]S(=O)(=O)[O-].O.O.O.O.O"),
+            )),
+            None
+        );
+        assert_eq!(
+            check_structure_formula(&compound("starch", "(C6H10O5)n", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn reports_extra_hydrogen_and_charge() {
+        let problem = check_structure_formula(&compound(
+            "sodium nitrate",
+            "NaNO3",
+            Some("[Na+].[O-][N+](=O)O"),
+        ))
+        .expect("extra hydrogen should be reported");
+
+        assert_eq!(
+            problem,
+            EntryProblem::FormulaMismatch {
+                differences: vec![
+                    CountDifference {
+                        label: "H".to_string(),
+                        declared: 0,
+                        derived: 1,
+                    },
+                    CountDifference {
+                        label: "charge".to_string(),
+                        declared: 0,
+                        derived: 1,
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            problem.to_string(),
+            "SMILES disagrees with molecular formula (H: formula 0, SMILES 1; charge: formula 0, SMILES 1)"
+        );
+    }
+
+    #[test]
+    fn reports_unparseable_inputs() {
+        assert!(matches!(
+            check_structure_formula(&compound("dimethylamine", "C2H7N", Some("CN(C)H"))),
+            Some(EntryProblem::InvalidSmiles(_))
+        ));
+        assert!(matches!(
+            check_structure_formula(&compound("generic soap", "RCOOM", Some("C"))),
+            Some(EntryProblem::InvalidFormula(_))
+        ));
+    }
+
+    #[test]
+    fn catalog_check_reports_entry_context() {
+        let catalog = Catalog::new(vec![
+            CatalogEntry {
+                compound: compound("ethanol", "C2H6O", Some("CCO")),
+                categories: vec!["Organic".to_string()],
+            },
+            CatalogEntry {
+                compound: compound("octadecanoic acid", "C18H36O2", Some("CCCC(=O)O")),
+                categories: vec!["Organic".to_string(), "Lipids".to_string()],
+            },
+        ]);
+
+        let issues = check_catalog_structures(&catalog);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].iupac_name, "octadecanoic acid");
+        assert!(
+            issues[0]
+                .to_string()
+                .starts_with("octadecanoic acid [Organic / Lipids]: ")
+        );
+    }
+
+    #[test]
+    fn reports_katex_markup_that_disagrees_with_plain_formulas() {
+        let mut propyne = compound("propyne", "C3H4", None);
+        propyne.skeletal_formula = "CH3-C≡CH".to_string();
+        propyne.katex_skeletal_formula = Some("\\ce{CH3-C#CH}".to_string());
+        propyne.katex_molecular_formula = Some("\\ce{C3H6}".to_string());
+
+        assert_eq!(
+            check_katex_formulas(&propyne),
+            vec![EntryProblem::KatexMismatch {
+                field: "molecular_formula".to_string(),
+                katex: "\\ce{C3H6}".to_string(),
+                plain: "C3H4".to_string(),
+            }]
+        );
+
+        let mut hydrate = compound("copper(II) sulfate pentahydrate", "CuSO4·5H2O", None);
+        hydrate.katex_molecular_formula = Some("\\ce{CuSO4\\cdot 5H2O}".to_string());
+        hydrate.katex_skeletal_formula = Some("\\text{hydrated salt}".to_string());
+        assert!(check_katex_formulas(&hydrate).is_empty());
+    }
+
+    #[test]
+    fn reports_functional_groups_missing_from_the_structure() {
+        let mut ethanol = compound("ethanol", "C2H6O", Some("CCO"));
+        ethanol.functional_groups = vec![
+            FunctionalGroupKind::Hydroxyl.to_functional_group(),
+            FunctionalGroupKind::Ether.to_functional_group(),
+        ];
+
+        let problem = check_functional_groups(&ethanol).expect("ether is not in ethanol");
+        assert_eq!(
+            problem.to_string(),
+            "functional groups disagree with SMILES (declared only: Ether (alkoxy); detected only: none)"
+        );
+
+        ethanol.functional_groups.pop();
+        assert_eq!(check_functional_groups(&ethanol), None);
+    }
+
+    #[test]
+    fn reports_salt_names_that_disagree_with_their_ions() {
+        assert_eq!(
+            check_ionic_name(&compound("iron(III) sulfate", "Fe2(SO4)3", None)),
+            None
+        );
+        assert_eq!(
+            check_ionic_name(&compound("disodium hydrogen phosphate", "Na2HPO4", None)),
+            None
+        );
+        assert_eq!(check_ionic_name(&compound("ethanol", "C2H6O", None)), None);
+
+        let problem = check_ionic_name(&compound("iron(II) sulfate", "Fe2(SO4)3", None))
+            .expect("charge does not balance");
+        assert_eq!(
+            problem.to_string(),
+            "name does not match the ions of the formula (expected iron(III) sulfate)"
+        );
+    }
+
+    #[test]
+    fn reports_names_that_disagree_with_the_structure() {
+        assert_eq!(
+            check_systematic_name(&compound("butan-2-ol", "C4H10O", Some("CCC(O)C"))),
+            None
+        );
+        assert_eq!(
+            check_systematic_name(&compound("ethanamine", "C2H7N", Some("CCN"))),
+            None
+        );
+
+        let problem = check_systematic_name(&compound("butan-1-ol", "C4H10O", Some("CCC(O)C")))
+            .expect("hydroxyl is on carbon 2");
+        assert_eq!(
+            problem.to_string(),
+            "name does not match the structure (SMILES gives butan-2-ol)"
+        );
+    }
+}
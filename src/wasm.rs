@@ -1,8 +1,8 @@
 #![cfg(target_arch = "wasm32")]
 
 use crate::{
-    CatalogLeaf, CatalogManifest, CatalogNode, Compound, DEMO_OPTION_COUNT, QuizItem, QuizMode,
-    demo_compounds, generate_quiz,
+    CatalogLeaf, CatalogManifest, CatalogNode, Compound, DEMO_OPTION_COUNT, Molecule, QuizItem,
+    QuizMode, demo_compounds, fill_loaded_compound, generate_quiz, series_with_general_formula,
 };
 use gloo_net::http::Request;
 use js_sys::Reflect;
@@ -13,6 +13,42 @@ use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 use web_sys::{HtmlCanvasElement, HtmlDivElement, HtmlElement};
 
+/// Quiz modes offered in the mode switch, in display order, with their button labels.
+const MODE_BUTTONS: [(QuizMode, &str); 23] = [
+    (QuizMode::StructureToName, "Structure → Name"),
+    (QuizMode::NameToStructure, "Name → Structure"),
+    (QuizMode::NameToMolecularFormula, "Name → Molecular formula"),
+    (QuizMode::MolecularFormulaToName, "Molecular formula → Name"),
+    (QuizMode::NameToCondensedFormula, "Name → Condensed formula"),
+    (QuizMode::CondensedFormulaToName, "Condensed formula → Name"),
+    (
+        QuizMode::JapaneseNameToStructure,
+        "Japanese name → Structure",
+    ),
+    (
+        QuizMode::StructureToJapaneseName,
+        "Structure → Japanese name",
+    ),
+    (QuizMode::NameToJapaneseName, "IUPAC → Japanese name"),
+    (QuizMode::IupacToCommonName, "IUPAC → Common name"),
+    (QuizMode::CommonToIupacName, "Common name → IUPAC"),
+    (QuizMode::IsomerToStructure, "Which isomer?"),
+    (QuizMode::OxidationNumber, "Oxidation number"),
+    (QuizMode::IonsToFormula, "Ions → Formula"),
+    (QuizMode::NameDrill, "Naming drill"),
+    (QuizMode::GeometricIsomer, "Cis / trans"),
+    (QuizMode::ChiralCentres, "Chiral centres"),
+    (QuizMode::FunctionalGroups, "Functional groups"),
+    (QuizMode::StructureToSeries, "Homologous series"),
+    (
+        QuizMode::GeneralFormulaToCompound,
+        "General formula → Compound",
+    ),
+    (QuizMode::MassPercent, "Mass percent"),
+    (QuizMode::FormulaFromComposition, "Composition → Formula"),
+    (QuizMode::BalanceEquation, "Balance equations"),
+];
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
 struct CompoundList {
     compounds: Vec<Compound>,
@@ -33,13 +69,32 @@ enum ViewMode {
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum PromptContent {
     Name,
+    /// Name without the molecular formula underneath, for modes that ask for a formula.
+    NameOnly,
+    /// IUPAC name alone, for modes that ask for the Japanese name.
+    IupacName,
+    JapaneseName,
+    CommonName,
+    MolecularFormula,
+    CondensedFormula,
     Structure,
+    Question,
+    /// Question text above the compound's structure.
+    StructureQuestion,
+    /// Question text above the asked general formula, without naming the answer.
+    GeneralFormula,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum OptionContent {
     Name,
     Structure,
+    Value,
+    MolecularFormula,
+    CondensedFormula,
+    JapaneseName,
+    CommonName,
+    IupacName,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -74,17 +129,20 @@ struct FeedbackState {
     kind: FeedbackKind,
 }
 
-#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, PartialEq)]
 struct SessionScore {
     total: usize,
     correct: usize,
+    /// Sum of the per-question scores, so multi-answer questions count partial credit.
+    points: f64,
 }
 
 #[derive(Clone)]
 struct AnswerOverlay {
     quiz: QuizItem,
     compound: Option<Compound>,
-    selected: usize,
+    selected: Vec<usize>,
+    score: f64,
     is_correct: bool,
 }
 
@@ -136,7 +194,15 @@ async fn fetch_compound_file(path: &str) -> Result<Vec<Compound>, String> {
         .map_err(|error| error.to_string())?
         .json::<CompoundList>()
         .await
-        .map(|list| list.compounds)
+        .map(|list| {
+            list.compounds
+                .into_iter()
+                .map(|mut compound| {
+                    fill_loaded_compound(&mut compound);
+                    compound
+                })
+                .collect()
+        })
         .map_err(|error| error.to_string())
 }
 
@@ -162,21 +228,85 @@ fn find_by_structure(dataset: &[Compound], label: &str) -> Option<Compound> {
         .cloned()
 }
 
+fn find_by_formula(dataset: &[Compound], formula: &str) -> Option<Compound> {
+    dataset
+        .iter()
+        .find(|compound| compound.molecular_formula.trim() == formula)
+        .cloned()
+}
+
+fn find_by_iupac_name(dataset: &[Compound], name: &str) -> Option<Compound> {
+    dataset
+        .iter()
+        .find(|compound| compound.iupac_name == name)
+        .cloned()
+}
+
+fn find_by_common_name(dataset: &[Compound], name: &str) -> Option<Compound> {
+    dataset
+        .iter()
+        .find(|compound| compound.common_name.as_deref().map(str::trim) == Some(name))
+        .cloned()
+}
+
+fn find_by_japanese_name(dataset: &[Compound], name: &str) -> Option<Compound> {
+    dataset
+        .iter()
+        .find(|compound| compound.local_name.as_deref().map(str::trim) == Some(name))
+        .cloned()
+}
+
+fn find_by_condensed_formula(dataset: &[Compound], formula: &str) -> Option<Compound> {
+    dataset
+        .iter()
+        .find(|compound| compound.skeletal_formula.trim() == formula)
+        .cloned()
+}
+
 fn english_label(compound: &Compound) -> String {
     compound.english_label()
 }
 
+/// Session points as a whole number, or to one decimal place once partial credit is in.
+fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{points:.0}")
+    } else {
+        format!("{points:.1}")
+    }
+}
+
 fn japanese_label(compound: &Compound) -> Option<String> {
     compound.local_name.clone()
 }
 
-fn hint_from_compound(compound: &Compound) -> Option<String> {
-    if let Some(series) = &compound.series_general_formula {
+fn hint_from_compound(compound: &Compound, mode: QuizMode) -> Option<String> {
+    // The series name and the group list would answer questions about groups and series,
+    // and the series formula, atom counts or formula itself would answer formula questions.
+    let hide_series_and_groups = matches!(
+        mode,
+        QuizMode::FunctionalGroups
+            | QuizMode::StructureToSeries
+            | QuizMode::GeneralFormulaToCompound
+            | QuizMode::NameToMolecularFormula
+    );
+    let hide_formula = matches!(
+        mode,
+        QuizMode::NameToMolecularFormula | QuizMode::GeneralFormulaToCompound
+    );
+
+    if let Some(series) = compound
+        .series_general_formula
+        .as_ref()
+        .filter(|_| !hide_series_and_groups)
+    {
         return Some(format!("Series formula: {}", series));
     }
 
-    if !compound.functional_groups.is_empty() {
-        let groups: Vec<String> = compound
+    let mut with_groups = compound.clone();
+    with_groups.fill_functional_groups();
+    if !with_groups.functional_groups.is_empty() && !hide_series_and_groups {
+        let groups: Vec<String> = with_groups
             .functional_groups
             .iter()
             .map(|group| format!("{} ({})", group.name_en, group.pattern))
@@ -188,6 +318,14 @@ fn hint_from_compound(compound: &Compound) -> Option<String> {
         return Some(notes.clone());
     }
 
+    if hide_formula {
+        return None;
+    }
+
+    if let Some(descriptors) = compound.descriptors() {
+        return Some(format!("Structure: {}", descriptors.summary().join("; ")));
+    }
+
     if !compound.molecular_formula.is_empty() {
         return Some(format!("Molecular formula: {}", compound.molecular_formula));
     }
@@ -207,9 +345,10 @@ fn katex_render_available() -> Option<js_sys::Function> {
         .and_then(|value| value.dyn_into::<js_sys::Function>().ok())
 }
 
-fn render_formula_into(element: HtmlElement, formula: &str) {
+/// Renders KaTeX `markup` into `element`, falling back to the plain `text` when KaTeX is
+/// unavailable or fails.
+fn render_formula_into(element: HtmlElement, markup: &str, text: &str) {
     if let Some(render) = katex_render_available() {
-        let content = format!("\\ce{{{}}}", formula);
         let options = js_sys::Object::new();
         let _ = Reflect::set(
             &options,
@@ -220,7 +359,7 @@ fn render_formula_into(element: HtmlElement, formula: &str) {
         if render
             .call3(
                 &js_sys::global(),
-                &JsValue::from_str(&content),
+                &JsValue::from_str(markup),
                 &element.clone().into(),
                 &options,
             )
@@ -230,7 +369,7 @@ fn render_formula_into(element: HtmlElement, formula: &str) {
         }
     }
 
-    element.set_text_content(Some(formula));
+    element.set_text_content(Some(text));
 }
 
 #[wasm_bindgen(inline_js = r#"
@@ -257,13 +396,6 @@ function waitForRdkit() {
     return rdkitModulePromise;
 }
 
-function ensureKekule() {
-    if (typeof Kekule === 'undefined' || !Kekule.IO || !Kekule.Render) {
-        return Promise.reject(new Error('Kekule.js not loaded'));
-    }
-    return Promise.resolve(Kekule);
-}
-
 function copySkeletalCanvas(offscreen, target, theme) {
     const width = offscreen.width;
     const height = offscreen.height;
@@ -303,46 +435,13 @@ function copySkeletalCanvas(offscreen, target, theme) {
     }
 }
 
-function buildKekuleViewer(container, width, height, theme) {
-    const V = Kekule.ChemWidget;
-    const R = Kekule.Render;
-
-    while (container.firstChild) {
-        container.removeChild(container.firstChild);
-    }
-
-    const viewer = new V.Viewer(container);
-    viewer.setRenderType(R.RendererType.R2D);
-    viewer.setMoleculeDisplayType(R.Molecule2DDisplayType.CONDENSED);
-    viewer.setEnableToolbar(false);
-    viewer.setEnableDirectInteraction(false);
-    viewer.setEnableEdit(false);
-    viewer.setAutoSize(false);
-    viewer.setAutofit(true);
-    viewer.setPadding(12);
-    viewer.setDimension(width, height);
-
-    const moleculeConfigs = viewer.getRenderConfigs().getMoleculeDisplayConfigs();
-    moleculeConfigs.setDefHydrogenDisplayLevel(R.HydrogenDisplayLevel.ALL);
-    moleculeConfigs.setDefNodeDisplayMode(R.NodeLabelDisplayMode.SHOWN);
-
-    const colorConfigs = viewer.getRenderConfigs().getColorConfigs();
-    if (theme === 'dark') {
-        colorConfigs.setAtomColor('#ffffff');
-        colorConfigs.setBondColor('#ffffff');
-        colorConfigs.setLabelColor('#ffffff');
-    } else {
-        colorConfigs.setAtomColor('#000000');
-        colorConfigs.setBondColor('#000000');
-        colorConfigs.setLabelColor('#000000');
+export async function renderStructure(smiles, theme, skeletalCanvas) {
+    let rdkit = null;
+    try {
+        rdkit = await waitForRdkit();
+    } catch (err) {
+        return { ok: false, message: `${err}` };
     }
-    viewer.setBackgroundColor('transparent');
-
-    return viewer;
-}
-
-export async function renderStructure(smiles, theme, skeletalCanvas, fullDiv) {
-    const [rdkit] = await Promise.all([waitForRdkit(), ensureKekule()]);
     let molecule = null;
 
     try {
@@ -374,45 +473,14 @@ export async function renderStructure(smiles, theme, skeletalCanvas, fullDiv) {
     }
 
     copySkeletalCanvas(offscreen, skeletalCanvas, theme);
-
-    let molblock = '';
-    try {
-        molblock = molecule.get_molblock();
-    } catch (err) {
-        molblock = '';
-    }
-    if (molecule) {
-        molecule.delete();
-    }
-
-    if (molblock && fullDiv) {
-        const targetWidth = fullDiv.clientWidth || width;
-        const targetHeight = fullDiv.clientHeight || height;
-        const viewer = buildKekuleViewer(fullDiv, targetWidth, targetHeight, theme);
-
-        try {
-            const molObj = Kekule.IO.loadFormatData(molblock, 'mol');
-            if (!molObj) {
-                return { ok: false, message: 'Kekule failed to load Molfile' };
-            }
-            viewer.setChemObj(molObj);
-            viewer.requestRepaint();
-        } catch (err) {
-            return { ok: false, message: `Kekule render failed: ${err}` };
-        }
-    }
+    molecule.delete();
 
     return { ok: true };
 }
 "#)]
 extern "C" {
     #[wasm_bindgen(js_name = renderStructure)]
-    async fn render_structure(
-        smiles: &str,
-        theme: &str,
-        skeletal: HtmlCanvasElement,
-        full: HtmlDivElement,
-    ) -> JsValue;
+    async fn render_structure(smiles: &str, theme: &str, skeletal: HtmlCanvasElement) -> JsValue;
 }
 
 fn render_error_from(result: JsValue) -> Option<String> {
@@ -424,6 +492,28 @@ fn render_error_from(result: JsValue) -> Option<String> {
     }
 }
 
+/// Stand-in compound for a name drill, whose prompt is SMILES rather than a catalog entry.
+/// The name stays generic so the depiction's label does not give it away.
+fn drill_compound(smiles: &str) -> Option<Compound> {
+    let formula = Molecule::parse(smiles).ok()?.formula().hill_notation();
+    Some(Compound {
+        iupac_name: "unnamed structure".to_string(),
+        common_name: None,
+        local_name: None,
+        skeletal_formula: formula.clone(),
+        molecular_formula: formula,
+        series_general_formula: None,
+        functional_groups: Vec::new(),
+        notes: None,
+        smiles: Some(smiles.to_string()),
+        katex_skeletal_formula: None,
+        katex_molecular_formula: None,
+        state: None,
+        charge: None,
+        hydrate_water: None,
+    })
+}
+
 fn generate_from_dataset(dataset: &[Compound], mode: QuizMode) -> Result<QuizItem, String> {
     let mut rng = rand::rngs::StdRng::from_entropy();
     generate_quiz(&mut rng, dataset, mode, DEMO_OPTION_COUNT).map_err(|error| error.to_string())
@@ -431,7 +521,7 @@ fn generate_from_dataset(dataset: &[Compound], mode: QuizMode) -> Result<QuizIte
 
 fn layout_for_quiz(quiz: &QuizItem) -> QuestionLayout {
     match quiz.mode {
-        QuizMode::NameToStructure => QuestionLayout {
+        QuizMode::NameToStructure | QuizMode::IsomerToStructure => QuestionLayout {
             prompt: PromptContent::Name,
             options: [OptionContent::Structure; 4],
             layout_kind: LayoutKind::StandardVertical,
@@ -441,18 +531,119 @@ fn layout_for_quiz(quiz: &QuizItem) -> QuestionLayout {
             options: [OptionContent::Name; 4],
             layout_kind: LayoutKind::StandardVertical,
         },
+        QuizMode::NameToMolecularFormula => QuestionLayout {
+            prompt: PromptContent::NameOnly,
+            options: [OptionContent::MolecularFormula; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::MolecularFormulaToName => QuestionLayout {
+            prompt: PromptContent::MolecularFormula,
+            options: [OptionContent::Name; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::NameToCondensedFormula => QuestionLayout {
+            prompt: PromptContent::NameOnly,
+            options: [OptionContent::CondensedFormula; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::CondensedFormulaToName => QuestionLayout {
+            prompt: PromptContent::CondensedFormula,
+            options: [OptionContent::Name; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::JapaneseNameToStructure => QuestionLayout {
+            prompt: PromptContent::JapaneseName,
+            options: [OptionContent::Structure; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::StructureToJapaneseName => QuestionLayout {
+            prompt: PromptContent::Structure,
+            options: [OptionContent::JapaneseName; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::NameToJapaneseName => QuestionLayout {
+            prompt: PromptContent::IupacName,
+            options: [OptionContent::JapaneseName; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::IupacToCommonName => QuestionLayout {
+            prompt: PromptContent::IupacName,
+            options: [OptionContent::CommonName; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::CommonToIupacName => QuestionLayout {
+            prompt: PromptContent::CommonName,
+            options: [OptionContent::IupacName; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::NameDrill => QuestionLayout {
+            prompt: PromptContent::Structure,
+            options: [OptionContent::Value; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::GeometricIsomer => QuestionLayout {
+            prompt: PromptContent::Structure,
+            options: [OptionContent::Name; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::ChiralCentres | QuizMode::FunctionalGroups | QuizMode::StructureToSeries => {
+            QuestionLayout {
+                prompt: PromptContent::StructureQuestion,
+                options: [OptionContent::Value; 4],
+                layout_kind: LayoutKind::StandardVertical,
+            }
+        }
+        QuizMode::GeneralFormulaToCompound => QuestionLayout {
+            prompt: PromptContent::GeneralFormula,
+            options: [OptionContent::Name; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
+        QuizMode::OxidationNumber
+        | QuizMode::IonsToFormula
+        | QuizMode::MassPercent
+        | QuizMode::FormulaFromComposition
+        | QuizMode::BalanceEquation => QuestionLayout {
+            prompt: PromptContent::Question,
+            options: [OptionContent::Value; 4],
+            layout_kind: LayoutKind::StandardVertical,
+        },
     }
 }
 
 fn compound_for_prompt(dataset: &[Compound], quiz: &QuizItem) -> Option<Compound> {
     match quiz.mode {
-        QuizMode::NameToStructure => find_by_name(dataset, &quiz.prompt),
-        QuizMode::StructureToName => find_by_structure(dataset, &quiz.prompt),
+        QuizMode::NameToStructure
+        | QuizMode::IsomerToStructure
+        | QuizMode::StructureToName
+        | QuizMode::NameToMolecularFormula
+        | QuizMode::MolecularFormulaToName
+        | QuizMode::NameToCondensedFormula
+        | QuizMode::CondensedFormulaToName
+        | QuizMode::JapaneseNameToStructure
+        | QuizMode::StructureToJapaneseName
+        | QuizMode::NameToJapaneseName
+        | QuizMode::IupacToCommonName
+        | QuizMode::CommonToIupacName
+        | QuizMode::OxidationNumber
+        | QuizMode::MassPercent
+        | QuizMode::IonsToFormula
+        | QuizMode::ChiralCentres
+        | QuizMode::FunctionalGroups
+        | QuizMode::StructureToSeries
+        | QuizMode::GeneralFormulaToCompound
+        | QuizMode::BalanceEquation
+        | QuizMode::GeometricIsomer => quiz
+            .compound_index
+            .and_then(|index| dataset.get(index))
+            .cloned(),
+        QuizMode::NameDrill => drill_compound(&quiz.prompt),
+        // Isomers share the answer formula, and the hint for any of them would give it away.
+        QuizMode::FormulaFromComposition => None,
     }
 }
 
 #[component]
-fn FormulaBadge(formula: String) -> impl IntoView {
+fn FormulaBadge(markup: String, text: String) -> impl IntoView {
     let node_ref = create_node_ref::<html::Div>();
 
     create_effect(move |_| {
@@ -460,7 +651,8 @@ fn FormulaBadge(formula: String) -> impl IntoView {
             // Clone the underlying HtmlDivElement, then cast the clone to web_sys::HtmlElement
             render_formula_into(
                 <HtmlDivElement as Clone>::clone(&element).unchecked_into::<HtmlElement>(),
-                &formula,
+                &markup,
+                &text,
             );
         }
     });
@@ -476,36 +668,41 @@ fn StructureTile(
     size: StructureViewSize,
 ) -> impl IntoView {
     let skeletal_ref = create_node_ref::<html::Canvas>();
-    let full_ref = create_node_ref::<html::Div>();
     let (render_message, set_render_message) = create_signal::<Option<String>>(None);
+    let (native_skeletal, set_native_skeletal) = create_signal::<Option<String>>(None);
 
     let smiles = compound.smiles.clone();
+    let fallback_skeletal = compound.skeletal_svg();
+    let full_structure = compound.full_structure_svg();
     let effect_smiles = smiles.clone();
     let iupac_name = compound.iupac_name.clone();
     let skeletal_formula = compound.skeletal_formula.clone();
-    let formula_badge = (!skeletal_formula.is_empty())
-        .then(|| view! { <FormulaBadge formula=skeletal_formula.clone() /> });
+    let skeletal_markup = compound.katex_skeletal();
+    let formula_badge = (!skeletal_formula.is_empty()).then(|| {
+        view! { <FormulaBadge markup=skeletal_markup.clone() text=skeletal_formula.clone() /> }
+    });
 
     create_effect(move |_| {
         let current_theme = theme.get();
         set_render_message.set(None);
+        set_native_skeletal.set(None);
 
-        if let (Some(smiles_value), Some(canvas), Some(full)) =
-            (effect_smiles.clone(), skeletal_ref.get(), full_ref.get())
-        {
+        if let (Some(smiles_value), Some(canvas)) = (effect_smiles.clone(), skeletal_ref.get()) {
             let status = set_render_message;
+            let native = set_native_skeletal;
+            let fallback = fallback_skeletal.clone();
             let skeletal_element: HtmlCanvasElement = (*canvas).clone().unchecked_into();
-            let full_element: HtmlDivElement = (*full).clone().unchecked_into();
 
             spawn_local(async move {
-                let result = render_structure(
-                    &smiles_value,
-                    &current_theme,
-                    skeletal_element,
-                    full_element,
-                )
-                .await;
-                status.set(render_error_from(result));
+                let result =
+                    render_structure(&smiles_value, &current_theme, skeletal_element).await;
+                let error = render_error_from(result);
+                // Without RDKit the canvas stays blank; show the Rust drawing instead.
+                if error.is_some() && fallback.is_some() {
+                    native.set(fallback);
+                } else {
+                    status.set(error);
+                }
             });
         }
     });
@@ -516,12 +713,16 @@ fn StructureTile(
     };
 
     let visuals = smiles.map(|_| {
+        let full_structure = full_structure.clone();
+        let has_full_structure = full_structure.is_some();
         view! {
             <div class=container_class>
                 <canvas
                     node_ref=skeletal_ref
                     style=move || {
-                        if view_mode.get() == ViewMode::Skeletal {
+                        if view_mode.get() == ViewMode::Skeletal
+                            && native_skeletal.get().is_none()
+                        {
                             "display:block".to_string()
                         } else {
                             "display:none".to_string()
@@ -531,10 +732,24 @@ fn StructureTile(
                     aria-label=format!("Skeletal depiction for {}", iupac_name.clone())
                 ></canvas>
                 <div
-                    node_ref=full_ref
-                    class="kekule-container"
+                    class="native-structure"
+                    style=move || {
+                        if view_mode.get() == ViewMode::Skeletal
+                            && native_skeletal.get().is_some()
+                        {
+                            "display:block".to_string()
+                        } else {
+                            "display:none".to_string()
+                        }
+                    }
+                    role="img"
+                    aria-label=format!("Skeletal depiction for {}", iupac_name.clone())
+                    inner_html=move || native_skeletal.get().unwrap_or_default()
+                ></div>
+                <div
+                    class="native-structure"
                     style=move || {
-                        if view_mode.get() == ViewMode::Full {
+                        if view_mode.get() == ViewMode::Full && has_full_structure {
                             "display:block".to_string()
                         } else {
                             "display:none".to_string()
@@ -542,6 +757,7 @@ fn StructureTile(
                     }
                     role="img"
                     aria-label=format!("Full structural formula for {}", iupac_name.clone())
+                    inner_html=full_structure.unwrap_or_default()
                 ></div>
                 {formula_badge.clone()}
             </div>
@@ -687,23 +903,37 @@ fn QuizCard(
     dataset: Vec<Compound>,
     theme: ReadSignal<String>,
     view_mode: ReadSignal<ViewMode>,
-    selected: Option<usize>,
+    selected: Vec<usize>,
     feedback: FeedbackState,
     hint: Option<String>,
     hint_visible: bool,
     reveal: bool,
     layout: QuestionLayout,
     on_select: Option<Callback<usize>>,
+    on_submit: Option<Callback<()>>,
     on_toggle_hint: Callback<()>,
 ) -> impl IntoView {
-    let heading_label = match layout
-        .options
-        .first()
-        .copied()
-        .unwrap_or(OptionContent::Name)
-    {
-        OptionContent::Structure => "Choose the correct structure",
-        OptionContent::Name => "Choose the correct name",
+    let multiple_answers = quiz.mode.allows_multiple_answers();
+    let submit_disabled = reveal || selected.is_empty();
+    let heading_label = if multiple_answers {
+        "Select all that apply"
+    } else {
+        match layout
+            .options
+            .first()
+            .copied()
+            .unwrap_or(OptionContent::Name)
+        {
+            OptionContent::Structure => "Choose the correct structure",
+            OptionContent::Name => "Choose the correct name",
+            OptionContent::Value => "Choose the correct value",
+            OptionContent::MolecularFormula | OptionContent::CondensedFormula => {
+                "Choose the correct formula"
+            }
+            OptionContent::JapaneseName => "Choose the correct Japanese name",
+            OptionContent::CommonName => "Choose the correct common name",
+            OptionContent::IupacName => "Choose the correct IUPAC name",
+        }
     };
 
     let prompt_compound = compound_for_prompt(&dataset, &quiz);
@@ -747,6 +977,45 @@ fn QuizCard(
                                 }
                                 .into_view()
                             }
+                            PromptContent::NameOnly => {
+                                let english = english_label(&compound);
+                                let japanese = japanese_label(&compound);
+
+                                view! {
+                                    <div>
+                                        <div class="prompt-name-main">{english}</div>
+                                        {japanese
+                                            .map(|name| view! { <div class="prompt-name-ja">{name}</div> })}
+                                    </div>
+                                }
+                                .into_view()
+                            }
+                            PromptContent::IupacName => {
+                                view! { <div class="prompt-name-main">{compound.iupac_name.clone()}</div> }
+                                    .into_view()
+                            }
+                            PromptContent::JapaneseName | PromptContent::CommonName => {
+                                view! { <div class="prompt-name-main">{quiz.prompt.clone()}</div> }
+                                    .into_view()
+                            }
+                            PromptContent::MolecularFormula => {
+                                view! {
+                                    <FormulaBadge
+                                        markup=compound.katex_molecular()
+                                        text=compound.molecular_formula.clone()
+                                    />
+                                }
+                                .into_view()
+                            }
+                            PromptContent::CondensedFormula => {
+                                view! {
+                                    <FormulaBadge
+                                        markup=compound.katex_skeletal()
+                                        text=compound.skeletal_formula.clone()
+                                    />
+                                }
+                                .into_view()
+                            }
                             PromptContent::Structure => {
                                 view! {
                                     <div class="structure-container">
@@ -766,6 +1035,57 @@ fn QuizCard(
                                 }
                                 .into_view()
                             }
+                            PromptContent::Question => {
+                                view! {
+                                    <div>
+                                        <div class="prompt-name-main">{quiz.prompt.clone()}</div>
+                                        <div class="prompt-formula-text">{english_label(&compound)}</div>
+                                    </div>
+                                }
+                                .into_view()
+                            }
+                            PromptContent::StructureQuestion => {
+                                view! {
+                                    <div>
+                                        <div class="prompt-name-main">{quiz.prompt.clone()}</div>
+                                        <div class="structure-container">
+                                            <div class="viewer-card">
+                                                <div class="viewer-title-row">
+                                                    <div class="viewer-label">Skeletal / full structure</div>
+                                                    <div class="viewer-badge">Prompt</div>
+                                                </div>
+                                                <StructureTile
+                                                    compound=compound
+                                                    theme=theme
+                                                    view_mode=view_mode
+                                                    size=StructureViewSize::Prompt
+                                                />
+                                            </div>
+                                        </div>
+                                    </div>
+                                }
+                                .into_view()
+                            }
+                            PromptContent::GeneralFormula => {
+                                let general = quiz
+                                    .prompt
+                                    .strip_prefix("Which compound matches the general formula ")
+                                    .and_then(|rest| rest.strip_suffix('?'))
+                                    .unwrap_or_default()
+                                    .to_string();
+                                let markup = series_with_general_formula(&general)
+                                    .first()
+                                    .map(|series| series.katex_general_formula.to_string())
+                                    .unwrap_or_default();
+
+                                view! {
+                                    <div>
+                                        <div class="prompt-name-main">"Which compound matches this general formula?"</div>
+                                        <FormulaBadge markup=markup text=general />
+                                    </div>
+                                }
+                                .into_view()
+                            }
                         })
                         .unwrap_or_else(|| view! { <p class="prompt-formula-text">{quiz.prompt.clone()}</p> }.into_view())}
                 </div>
@@ -781,8 +1101,8 @@ fn QuizCard(
                         .iter()
                         .enumerate()
                         .map(|(index, option)| {
-                            let is_selected = selected == Some(index);
-                            let is_correct = index == quiz.correct_index;
+                            let is_selected = selected.contains(&index);
+                            let is_correct = quiz.is_correct(index);
 
                             let mut classes = vec!["option-btn".to_string()];
                             if reveal {
@@ -793,11 +1113,37 @@ fn QuizCard(
                                 } else if is_selected {
                                     classes.push("option-wrong".to_string());
                                 }
+                            } else if is_selected {
+                                classes.push("option-selected".to_string());
                             }
 
                             let compound = match quiz.mode {
-                                QuizMode::NameToStructure => find_by_structure(&dataset, option),
-                                QuizMode::StructureToName => find_by_name(&dataset, option),
+                                QuizMode::NameToStructure
+                                | QuizMode::IsomerToStructure
+                                | QuizMode::JapaneseNameToStructure => find_by_structure(&dataset, option),
+                                QuizMode::StructureToJapaneseName | QuizMode::NameToJapaneseName => {
+                                    find_by_japanese_name(&dataset, option)
+                                }
+                                QuizMode::IupacToCommonName => find_by_common_name(&dataset, option),
+                                QuizMode::CommonToIupacName => find_by_iupac_name(&dataset, option),
+                                QuizMode::StructureToName
+                                | QuizMode::GeometricIsomer
+                                | QuizMode::GeneralFormulaToCompound
+                                | QuizMode::MolecularFormulaToName
+                                | QuizMode::CondensedFormulaToName => find_by_name(&dataset, option),
+                                QuizMode::NameToMolecularFormula => find_by_formula(&dataset, option),
+                                QuizMode::NameToCondensedFormula => {
+                                    find_by_condensed_formula(&dataset, option)
+                                }
+                                QuizMode::OxidationNumber
+                                | QuizMode::IonsToFormula
+                                | QuizMode::NameDrill
+                                | QuizMode::ChiralCentres
+                                | QuizMode::MassPercent
+                                | QuizMode::FormulaFromComposition
+                                | QuizMode::BalanceEquation
+                                | QuizMode::FunctionalGroups
+                                | QuizMode::StructureToSeries => None,
                             };
 
                             let click_handler = {
@@ -851,6 +1197,38 @@ fn QuizCard(
                                                 }
                                                 .into_view()
                                             }
+                                            OptionContent::Value => {
+                                                view! { <p class="prompt-formula-text">{option.clone()}</p> }
+                                                    .into_view()
+                                            }
+                                            OptionContent::JapaneseName
+                                            | OptionContent::CommonName
+                                            | OptionContent::IupacName => {
+                                                view! {
+                                                    <div class="option-name-inner">
+                                                        <p class="option-name-main">{option.clone()}</p>
+                                                    </div>
+                                                }
+                                                .into_view()
+                                            }
+                                            OptionContent::MolecularFormula => {
+                                                view! {
+                                                    <FormulaBadge
+                                                        markup=compound.katex_molecular()
+                                                        text=option.clone()
+                                                    />
+                                                }
+                                                .into_view()
+                                            }
+                                            OptionContent::CondensedFormula => {
+                                                view! {
+                                                    <FormulaBadge
+                                                        markup=compound.katex_skeletal()
+                                                        text=option.clone()
+                                                    />
+                                                }
+                                                .into_view()
+                                            }
                                         })
                                         .unwrap_or_else(|| view! { <p class="prompt-formula-text">{option.clone()}</p> }
                                             .into_view())}
@@ -863,6 +1241,23 @@ fn QuizCard(
                 <div class="controls-row">
                     <div class=feedback_class>{feedback.message}</div>
                     <div class="controls-buttons">
+                        {multiple_answers
+                            .then(|| {
+                                view! {
+                                    <button
+                                        class="btn btn-primary"
+                                        type="button"
+                                        on:click=move |_| {
+                                            if let Some(ref callback) = on_submit {
+                                                callback.call(());
+                                            }
+                                        }
+                                        disabled=submit_disabled
+                                    >
+                                        "Submit"
+                                    </button>
+                                }
+                            })}
                         <button
                             class="btn"
                             type="button"
@@ -895,7 +1290,7 @@ fn App() -> impl IntoView {
     let (error, set_error) = create_signal::<Option<String>>(None);
     let (active_dataset, set_active_dataset) = create_signal::<Vec<Compound>>(demo_compounds());
     let (scene, set_scene) = create_signal(Scene::Menu);
-    let (selected_option, set_selected_option) = create_signal::<Option<usize>>(None);
+    let (selected_options, set_selected_options) = create_signal::<Vec<usize>>(Vec::new());
     let (score, set_score) = create_signal(SessionScore::default());
     let (answer_overlay, set_answer_overlay) = create_signal::<Option<AnswerOverlay>>(None);
     let overlay_ref = create_node_ref::<html::Div>();
@@ -935,14 +1330,14 @@ fn App() -> impl IntoView {
         let set_feedback = set_feedback.clone();
         let set_quiz = set_quiz.clone();
         let set_active_dataset = set_active_dataset.clone();
-        let set_selected_option = set_selected_option.clone();
+        let set_selected_options = set_selected_options.clone();
         let set_hint_visible = set_hint_visible.clone();
         let set_answer_overlay = set_answer_overlay.clone();
         let compounds = compounds.clone();
         let active_dataset = active_dataset.clone();
 
         Rc::new(move || -> bool {
-            set_selected_option.set(None);
+            set_selected_options.set(Vec::new());
             set_answer_overlay.set(None);
             set_hint_visible.set(false);
 
@@ -955,10 +1350,16 @@ fn App() -> impl IntoView {
 
                     let prompt_compound = compound_for_prompt(&dataset, &item);
 
-                    set_hint
-                        .set(prompt_compound.and_then(|compound| hint_from_compound(&compound)));
+                    set_hint.set(
+                        prompt_compound
+                            .and_then(|compound| hint_from_compound(&compound, item.mode)),
+                    );
                     set_feedback.set(FeedbackState::neutral(
-                        "Select an option to submit your answer.",
+                        if item.mode.allows_multiple_answers() {
+                            "Select every option that applies, then submit."
+                        } else {
+                            "Select an option to submit your answer."
+                        },
                     ));
                     set_quiz.set(Some(item));
                     true
@@ -994,7 +1395,7 @@ fn App() -> impl IntoView {
     let return_to_menu = {
         let set_scene = set_scene.clone();
         let set_quiz = set_quiz.clone();
-        let set_selected_option = set_selected_option.clone();
+        let set_selected_options = set_selected_options.clone();
         let set_feedback = set_feedback.clone();
         let set_hint = set_hint.clone();
         let set_hint_visible = set_hint_visible.clone();
@@ -1003,7 +1404,7 @@ fn App() -> impl IntoView {
         Callback::new(move |_| {
             set_scene.set(Scene::Menu);
             set_quiz.set(None);
-            set_selected_option.set(None);
+            set_selected_options.set(Vec::new());
             set_answer_overlay.set(None);
             set_feedback.set(FeedbackState::neutral(
                 "Load a catalog entry and start a quiz.",
@@ -1013,33 +1414,40 @@ fn App() -> impl IntoView {
         })
     };
 
-    let choose_option = {
+    let submit_answer = {
         let scene = scene.clone();
-        let selected_option = selected_option.clone();
         let quiz = quiz.clone();
-        let set_selected_option = set_selected_option.clone();
+        let selected_options = selected_options.clone();
+        let answer_overlay = answer_overlay.clone();
         let set_score = set_score.clone();
         let active_dataset = active_dataset.clone();
         let set_feedback = set_feedback.clone();
         let set_answer_overlay = set_answer_overlay.clone();
 
-        Callback::new(move |index: usize| {
-            if scene.get() != Scene::Game || selected_option.get().is_some() {
+        Callback::new(move |_| {
+            let selected = selected_options.get();
+            if scene.get() != Scene::Game || answer_overlay.get().is_some() || selected.is_empty() {
                 return;
             }
 
             if let Some(item) = quiz.get() {
-                set_selected_option.set(Some(index));
+                let points = item.score(&selected);
+                let is_correct = points >= 1.0;
                 set_score.update(|state| {
                     state.total += 1;
-                    if index == item.correct_index {
+                    state.points += points;
+                    if is_correct {
                         state.correct += 1;
                     }
                 });
 
-                let is_correct = index == item.correct_index;
                 let feedback_message = if is_correct {
                     FeedbackState::correct("Correct! View the overlay for details.")
+                } else if points > 0.0 {
+                    FeedbackState::wrong(format!(
+                        "Partly correct ({:.0}%). Review the highlighted answers and open the overlay.",
+                        points * 100.0
+                    ))
                 } else {
                     FeedbackState::wrong(
                         "Not quite. Review the highlighted answer and open the overlay.",
@@ -1052,21 +1460,54 @@ fn App() -> impl IntoView {
                 set_answer_overlay.set(Some(AnswerOverlay {
                     quiz: item.clone(),
                     compound,
-                    selected: index,
+                    selected,
+                    score: points,
                     is_correct,
                 }));
             }
         })
     };
 
+    // Single-answer questions are submitted on the first click; multi-answer ones toggle
+    // the option until the Submit button is pressed.
+    let choose_option = {
+        let scene = scene.clone();
+        let quiz = quiz.clone();
+        let answer_overlay = answer_overlay.clone();
+        let set_selected_options = set_selected_options.clone();
+        let submit_answer = submit_answer.clone();
+
+        Callback::new(move |index: usize| {
+            if scene.get() != Scene::Game || answer_overlay.get().is_some() {
+                return;
+            }
+
+            if let Some(item) = quiz.get() {
+                if item.mode.allows_multiple_answers() {
+                    set_selected_options.update(|selected| {
+                        if let Some(position) = selected.iter().position(|chosen| *chosen == index)
+                        {
+                            selected.remove(position);
+                        } else {
+                            selected.push(index);
+                        }
+                    });
+                } else {
+                    set_selected_options.set(vec![index]);
+                    submit_answer.call(());
+                }
+            }
+        })
+    };
+
     let next_question = {
         let regenerate = regenerate.clone();
         let set_scene = set_scene.clone();
         let set_answer_overlay = set_answer_overlay.clone();
-        let set_selected_option = set_selected_option.clone();
+        let set_selected_options = set_selected_options.clone();
 
         Callback::new(move |_| {
-            set_selected_option.set(None);
+            set_selected_options.set(Vec::new());
             set_answer_overlay.set(None);
             if regenerate() {
                 set_scene.set(Scene::Game);
@@ -1175,7 +1616,7 @@ fn App() -> impl IntoView {
                     <header class="app-header">
                         <div class="app-title">"Molecular Structure Quiz (compounds.json)"</div>
                         <div class="app-subtitle">
-                            "Structure → Name / Name → Structure, using Kekule.js where SMILES are available."
+                            "Structure → Name / Name → Structure, with native structure drawings where SMILES are available."
                         </div>
                     </header>
 
@@ -1187,32 +1628,26 @@ fn App() -> impl IntoView {
                                     "Quiz mode"
                                 </div>
                                 <div class="mode-switch">
-                                    <button
-                                        class=move || {
-                                            if mode.get() == QuizMode::StructureToName {
-                                                "mode-btn active".to_string()
-                                            } else {
-                                                "mode-btn".to_string()
-                                            }
-                                        }
-                                        type="button"
-                                        on:click=move |_| set_mode.set(QuizMode::StructureToName)
-                                    >
-                                        "Structure → Name"
-                                    </button>
-                                    <button
-                                        class=move || {
-                                            if mode.get() == QuizMode::NameToStructure {
-                                                "mode-btn active".to_string()
-                                            } else {
-                                                "mode-btn".to_string()
+                                    {MODE_BUTTONS
+                                        .into_iter()
+                                        .map(move |(button_mode, label)| {
+                                            view! {
+                                                <button
+                                                    class=move || {
+                                                        if mode.get() == button_mode {
+                                                            "mode-btn active".to_string()
+                                                        } else {
+                                                            "mode-btn".to_string()
+                                                        }
+                                                    }
+                                                    type="button"
+                                                    on:click=move |_| set_mode.set(button_mode)
+                                                >
+                                                    {label}
+                                                </button>
                                             }
-                                        }
-                                        type="button"
-                                        on:click=move |_| set_mode.set(QuizMode::NameToStructure)
-                                    >
-                                        "Name → Structure"
-                                    </button>
+                                        })
+                                        .collect_view()}
                                 </div>
                                 <div style="display:inline-block;margin-left:10px;vertical-align:middle;">"Structure view"</div>
                                 <div class="mode-switch" id="viewModeSwitch" style="--gap:6px;">
@@ -1246,7 +1681,7 @@ fn App() -> impl IntoView {
                             </div>
                             <div class="score-badge">
                                 <span>"Score:"</span>
-                                <strong><span>{move || score.get().correct}</span></strong>
+                                <strong><span>{move || format_points(score.get().points)}</span></strong>
                                 <span>"/ "<span>{move || score.get().total}</span></span>
                             </div>
                         </div>
@@ -1326,11 +1761,11 @@ fn App() -> impl IntoView {
                         {move || {
                             if let Some(item) = quiz.get() {
                                 let layout = layout_for_quiz(&item);
-                                let reveal = selected_option.get().is_some();
-                                let on_select = if answer_overlay.get().is_some() {
-                                    None
+                                let reveal = answer_overlay.get().is_some();
+                                let (on_select, on_submit) = if reveal {
+                                    (None, None)
                                 } else {
-                                    Some(choose_option.clone())
+                                    (Some(choose_option.clone()), Some(submit_answer.clone()))
                                 };
 
                                 view! {
@@ -1340,13 +1775,14 @@ fn App() -> impl IntoView {
                                             dataset=active_dataset.get()
                                             theme=theme
                                             view_mode=view_mode
-                                            selected=selected_option.get()
+                                            selected=selected_options.get()
                                             feedback=feedback.get()
                                             hint=hint.get()
                                             hint_visible=hint_visible.get()
                                             reveal=reveal
                                             layout=layout
                                             on_select=on_select
+                                            on_submit=on_submit
                                             on_toggle_hint=toggle_hint.clone()
                                         />
                                     </section>
@@ -1421,9 +1857,11 @@ fn App() -> impl IntoView {
                                 };
                                 let stop_click = move |ev: ev::MouseEvent| ev.stop_propagation();
                                 let message = if overlay.is_correct {
-                                    "Correct"
+                                    "Correct".to_string()
+                                } else if overlay.score > 0.0 {
+                                    format!("Partly correct ({:.0}%)", overlay.score * 100.0)
                                 } else {
-                                    "Incorrect"
+                                    "Incorrect".to_string()
                                 };
                                 let outcome_class = if overlay.is_correct {
                                     "result-title correct"
@@ -1438,6 +1876,7 @@ fn App() -> impl IntoView {
                                         let english = english_label(compound);
                                         let japanese = compound.local_name.clone();
                                         let molecular = compound.molecular_formula.clone();
+                                        let molecular_markup = compound.katex_molecular();
                                         let skeletal = compound.skeletal_formula.clone();
 
                                         view! {
@@ -1448,7 +1887,7 @@ fn App() -> impl IntoView {
                                                         .map(|name| view! { <p class="result-name-ja">{name}</p> })}
                                                 </div>
                                                 <div class="result-formula-block">
-                                                    <FormulaBadge formula=molecular />
+                                                    <FormulaBadge markup=molecular_markup text=molecular />
                                                 </div>
                                                 <p class="result-structure">{skeletal}</p>
                                             </div>
@@ -1,4 +1,9 @@
-use chemquiz::{Catalog, CatalogError, QuizMode, demo_catalog, generate_quiz};
+use chemquiz::{
+    Catalog, CatalogError, Configuration, Geometry, Molecule, PhysicalState, QuizMode,
+    check_catalog_katex, check_catalog_salt_names, check_catalog_structures,
+    check_catalog_systematic_names, demo_catalog, empirical_formula_from_percentages,
+    generate_quiz, molecular_formula_from_empirical, parse_name, systematic_name,
+};
 use rand::SeedableRng;
 
 fn organic_alcohols_path() -> Vec<String> {
@@ -82,3 +87,452 @@ fn smiles_are_loaded_when_present() {
 
     assert!(arenes.iter().any(|compound| compound.smiles.is_some()));
 }
+
+#[test]
+fn structure_check_covers_catalog_entries() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let issues = check_catalog_structures(&catalog);
+
+    assert!(issues.iter().all(|issue| !issue.categories.is_empty()));
+    assert!(
+        !issues
+            .iter()
+            .any(|issue| issue.iupac_name == "ethanol" || issue.iupac_name == "benzene")
+    );
+
+    // Known data problems: SMILES that stand in for network solids, radicals and complexes,
+    // nitrates and phosphates written with an extra OH, a thiosulfate with an extra S and O,
+    // unbracketed Cr and Si, `CN(C)H`, and sugar and fatty-acid SMILES a carbon short or
+    // long. A new name in this list means a new catalog mistake.
+    let mut flagged: Vec<&str> = issues
+        .iter()
+        .map(|issue| issue.iupac_name.as_str())
+        .collect();
+    flagged.sort_unstable();
+    flagged.dedup();
+    assert_eq!(
+        flagged,
+        [
+            "D-fructose",
+            "D-ribose",
+            "ammonium dihydrogen phosphate",
+            "ammonium nitrate",
+            "carbon (diamond)",
+            "carbon (graphite)",
+            "carbon monoxide",
+            "copper(II) nitrate",
+            "diamminesilver(I) complex",
+            "dimethylamine",
+            "dinitrogen monoxide",
+            "disodium hydrogen phosphate",
+            "nitrogen dioxide",
+            "octadecanoic acid",
+            "potassium chromate(VI)",
+            "potassium dichromate(VI)",
+            "potassium hexacyanidoferrate(II)",
+            "potassium nitrate",
+            "silicon carbide",
+            "silver nitrate",
+            "sodium dihydrogen phosphate",
+            "sodium hexadecanoate",
+            "sodium metasilicate",
+            "sodium nitrate",
+            "sodium thiosulfate pentahydrate",
+            "tetraphosphorus",
+            "tetraphosphorus decoxide",
+            "tricalcium phosphate",
+        ]
+    );
+}
+
+#[test]
+fn catalog_katex_markup_matches_plain_formulas() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let issues = check_catalog_katex(&catalog);
+
+    assert!(issues.is_empty(), "unexpected KaTeX issues: {:?}", issues);
+}
+
+#[test]
+fn catalog_groups_ethanol_with_its_ether_isomer() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let groups = catalog.isomer_groups();
+    let c2h6o = groups
+        .get("C2H6O")
+        .expect("C2H6O has isomers in the catalog");
+
+    assert!(
+        c2h6o
+            .iter()
+            .any(|compound| compound.iupac_name == "ethanol")
+    );
+    assert!(
+        c2h6o
+            .iter()
+            .any(|compound| compound.iupac_name == "methoxymethane")
+    );
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+    let quiz = generate_quiz(
+        &mut rng,
+        &catalog.all_compounds(),
+        QuizMode::IsomerToStructure,
+        2,
+    )
+    .expect("catalog should have isomer pairs");
+    assert_eq!(quiz.options.len(), 2);
+}
+
+#[test]
+fn inorganic_catalog_has_oxidation_states() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let inorganic = catalog
+        .compounds_for(&["Inorganic".to_string()])
+        .expect("inorganic category should exist");
+
+    let permanganate = inorganic
+        .iter()
+        .find(|compound| compound.molecular_formula == "KMnO4")
+        .and_then(|compound| compound.oxidation_states())
+        .expect("KMnO4 should be solvable");
+    assert_eq!(
+        permanganate.state_of("Mn").map(|state| state.to_string()),
+        Some("+7".to_string())
+    );
+
+    let unsolved: Vec<&str> = inorganic
+        .iter()
+        .filter(|compound| compound.oxidation_states().is_none())
+        .map(|compound| compound.molecular_formula.as_str())
+        .collect();
+    assert!(unsolved.is_empty(), "unsolved formulas: {:?}", unsolved);
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(12);
+    let quiz = generate_quiz(&mut rng, &inorganic, QuizMode::OxidationNumber, 4)
+        .expect("inorganic compounds should yield oxidation-number questions");
+    assert!(quiz.prompt.starts_with("What is the oxidation number of"));
+}
+
+#[test]
+fn salt_names_match_their_ions() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let issues = check_catalog_salt_names(&catalog);
+
+    assert!(
+        issues.is_empty(),
+        "unexpected salt name issues: {:?}",
+        issues
+    );
+
+    let salts = catalog
+        .compounds_for(&["Inorganic".to_string(), "Salts".to_string()])
+        .expect("salts category should exist");
+    let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+    let quiz = generate_quiz(&mut rng, &salts, QuizMode::IonsToFormula, 4)
+        .expect("salts should yield ion questions");
+    assert!(quiz.prompt.starts_with("Which formula do"));
+}
+
+#[test]
+fn aliphatic_names_match_their_structures() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let issues = check_catalog_systematic_names(&catalog);
+
+    assert!(
+        issues.is_empty(),
+        "unexpected systematic name issues: {:?}",
+        issues
+    );
+}
+
+#[test]
+fn aliphatic_names_parse_to_their_structures() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let mut checked = 0;
+
+    for entry in catalog.entries() {
+        let compound = &entry.compound;
+        if !entry.categories.starts_with(&organic_alcohols_path()[..2])
+            || compound.smiles.is_none()
+            || systematic_name(compound.smiles.as_deref().unwrap_or_default()).is_err()
+        {
+            continue;
+        }
+        assert!(
+            compound.accepts_name(&compound.iupac_name),
+            "{}",
+            compound.iupac_name
+        );
+        let parsed = parse_name(&compound.iupac_name)
+            .unwrap_or_else(|error| panic!("{}: {}", compound.iupac_name, error));
+        let expected = Molecule::parse(compound.smiles.as_deref().unwrap_or_default())
+            .expect("catalog SMILES should parse");
+        assert_eq!(
+            parsed.canonical_key(),
+            expected.canonical_key(),
+            "{}",
+            compound.iupac_name
+        );
+        checked += 1;
+    }
+
+    assert!(checked > 50, "only {checked} names checked");
+}
+
+#[test]
+fn amino_acids_are_written_as_l_enantiomers() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let amino_acids = catalog
+        .compounds_for(&[
+            "Organic".to_string(),
+            "Biomolecules".to_string(),
+            "Amino_acids_and_proteins".to_string(),
+            "Amino_acids".to_string(),
+        ])
+        .expect("amino acid category should exist");
+
+    for compound in &amino_acids {
+        let centres = compound
+            .stereo_descriptors()
+            .expect("amino acids have SMILES")
+            .centres;
+        let expected = match compound.common_name.as_deref() {
+            Some("glycine") => vec![],
+            Some("cysteine") => vec![Some(Configuration::R)],
+            _ => vec![Some(Configuration::S)],
+        };
+        let found: Vec<_> = centres.iter().map(|centre| centre.configuration).collect();
+        assert_eq!(found, expected, "{}", compound.iupac_name);
+    }
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+    let quiz = generate_quiz(&mut rng, &amino_acids, QuizMode::ChiralCentres, 4)
+        .expect("amino acids should yield chiral-centre questions");
+    assert!(quiz.prompt.starts_with("How many chiral centres does"));
+}
+
+#[test]
+fn geometric_isomers_are_told_apart() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let organic = catalog
+        .compounds_for(&["Organic".to_string()])
+        .expect("organic category should exist");
+
+    let geometry = |name: &str| {
+        let compound = organic
+            .iter()
+            .find(|compound| compound.common_name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("{name} should be in the catalog"));
+        compound
+            .stereo_descriptors()
+            .and_then(|descriptors| descriptors.double_bonds.first().copied())
+            .and_then(|bond| bond.geometry)
+    };
+    assert_eq!(geometry("maleic acid"), Some(Geometry::Z));
+    assert_eq!(geometry("fumaric acid"), Some(Geometry::E));
+    assert_eq!(geometry("cis-2-butene"), Some(Geometry::Z));
+    assert_eq!(geometry("trans-2-butene"), Some(Geometry::E));
+    assert_eq!(geometry("oleic acid"), Some(Geometry::Z));
+
+    for seed in 0..10 {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let quiz = generate_quiz(&mut rng, &organic, QuizMode::GeometricIsomer, 4)
+            .expect("the catalog has cis/trans pairs");
+        let partner = if quiz.options[quiz.correct_index].contains("acid") {
+            "acid"
+        } else {
+            "butene"
+        };
+        let matching = quiz
+            .options
+            .iter()
+            .filter(|option| option.contains(partner) && option.contains(")-but"))
+            .count();
+        assert_eq!(matching, 2, "{:?}", quiz.options);
+    }
+}
+
+#[test]
+fn unspecified_but_2_ene_is_not_offered_beside_its_configured_isomers() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let alkenes = catalog
+        .compounds_for(&[
+            "Organic".to_string(),
+            "Aliphatic_compounds".to_string(),
+            "Hydrocarbons".to_string(),
+            "Alkenes".to_string(),
+        ])
+        .expect("alkene category should exist");
+    assert!(
+        alkenes
+            .iter()
+            .any(|compound| compound.iupac_name == "but-2-ene")
+    );
+
+    for seed in 0..300 {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let quiz = generate_quiz(&mut rng, &alkenes, QuizMode::StructureToName, 4)
+            .expect("the alkenes offer enough options");
+        assert!(
+            !quiz
+                .options
+                .iter()
+                .any(|option| option.starts_with("but-2-ene")),
+            "{:?}",
+            quiz.options
+        );
+    }
+}
+
+#[test]
+fn compositions_solve_back_to_catalog_formulas() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let glucose = catalog
+        .all_compounds()
+        .into_iter()
+        .find(|compound| compound.iupac_name == "D-glucose")
+        .expect("glucose should be in the catalog");
+
+    assert_eq!(
+        glucose
+            .empirical_formula()
+            .map(|formula| formula.hill_notation()),
+        Some("CH2O".to_string())
+    );
+    let percentages = glucose
+        .mass_composition()
+        .expect("glucose formula parses")
+        .rounded();
+    let empirical =
+        empirical_formula_from_percentages(&percentages).expect("percentages should solve");
+    let molecular = molecular_formula_from_empirical(&empirical, 180.2)
+        .expect("molar mass is a multiple of CH2O");
+    assert_eq!(molecular.hill_notation(), "C6H12O6");
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+    let quiz = generate_quiz(
+        &mut rng,
+        &catalog.all_compounds(),
+        QuizMode::FormulaFromComposition,
+        4,
+    )
+    .expect("catalog compounds should yield composition problems");
+    assert!(quiz.prompt.ends_with("What is its molecular formula?"));
+}
+
+#[test]
+fn aggregate_copies_are_detected_and_selected_once() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let duplicates = catalog.duplicate_entries();
+
+    let ethanol = duplicates
+        .iter()
+        .find(|group| group[0].compound.iupac_name == "ethanol")
+        .expect("ethanol is listed in leaf and aggregate files");
+    assert!(ethanol.len() > 1);
+    assert!(
+        ethanol
+            .iter()
+            .any(|entry| entry.categories == ["Organic".to_string()])
+    );
+
+    let organic = catalog
+        .compounds_for(&["Organic".to_string()])
+        .expect("organic category should exist");
+    let mut keys: Vec<String> = organic
+        .iter()
+        .map(|compound| compound.equivalence_key())
+        .collect();
+    let selected = keys.len();
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), selected);
+
+    let disaccharides = catalog
+        .compounds_for(&[
+            "Organic".to_string(),
+            "Biomolecules".to_string(),
+            "Carbohydrates".to_string(),
+            "Disaccharides".to_string(),
+        ])
+        .expect("disaccharide category should exist");
+    assert_eq!(disaccharides.len(), 3);
+}
+
+#[test]
+fn allotropes_and_solutions_are_not_merged() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let inorganic = catalog
+        .compounds_for(&["Inorganic".to_string()])
+        .expect("inorganic category should exist");
+    let count = |name: &str, skeletal: &str| {
+        inorganic
+            .iter()
+            .filter(|compound| {
+                compound.iupac_name == name && compound.skeletal_formula.contains(skeletal)
+            })
+            .count()
+    };
+
+    assert_eq!(count("carbon (diamond)", ""), 1);
+    assert_eq!(count("carbon (graphite)", ""), 1);
+    assert_eq!(count("ammonia", "(aq)"), 1);
+    assert_eq!(count("azane", ""), 1);
+}
+
+#[test]
+fn formula_annotations_are_typed_on_load() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let compounds = catalog.all_compounds();
+    let find = |formula: &str| {
+        compounds
+            .iter()
+            .find(|compound| compound.molecular_formula == formula)
+            .unwrap_or_else(|| panic!("{formula} should be in the catalog"))
+    };
+
+    assert_eq!(find("Ag(NH3)2^+").charge, Some(1));
+    assert_eq!(find("CuSO4·5H2O").hydrate_water, Some(5));
+    assert!(
+        compounds
+            .iter()
+            .any(|compound| compound.skeletal_formula == "HF (aq)"
+                && compound.state == Some(PhysicalState::Aqueous))
+    );
+    assert!(
+        compounds
+            .iter()
+            .filter(|compound| compound.formula().is_ok())
+            .all(|compound| compound.charge.is_some() && compound.hydrate_water.is_some())
+    );
+}
+
+#[test]
+fn homologous_series_modes_run_on_the_catalog() {
+    let catalog = Catalog::from_directory("catalog").expect("catalog folder should load");
+    let compounds = catalog.all_compounds();
+
+    let alkynes: Vec<&str> = compounds
+        .iter()
+        .filter(|compound| {
+            compound
+                .homologous_series()
+                .is_some_and(|series| series.general_formula == "CnH2n-2")
+        })
+        .map(|compound| compound.iupac_name.as_str())
+        .collect();
+    assert!(alkynes.contains(&"propyne"), "{alkynes:?}");
+    assert!(!alkynes.contains(&"buta-1,3-diene"), "{alkynes:?}");
+
+    for seed in 0..20 {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        for mode in [
+            QuizMode::StructureToSeries,
+            QuizMode::GeneralFormulaToCompound,
+        ] {
+            let quiz = generate_quiz(&mut rng, &compounds, mode, 4)
+                .unwrap_or_else(|error| panic!("{mode:?} should generate: {error}"));
+            assert_eq!(quiz.options.len(), 4);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::Path;
+
+use chemquiz::{Molecule, full_structure_svg, skeletal_svg};
+
+/// Compares a drawing against `tests/snapshots/<name>.svg`. Run with `UPDATE_SNAPSHOTS=1`
+/// to accept intentional changes to the renderer.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new("tests/snapshots").join(format!("{name}.svg"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).expect("snapshot should be writable");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}; run with UPDATE_SNAPSHOTS=1",
+            path.display()
+        )
+    });
+    assert_eq!(actual, expected, "snapshot {name} differs");
+}
+
+fn skeletal(smiles: &str) -> String {
+    skeletal_svg(&Molecule::parse(smiles).expect("SMILES should parse"))
+}
+
+fn full(smiles: &str) -> String {
+    full_structure_svg(&Molecule::parse(smiles).expect("SMILES should parse"))
+}
+
+#[test]
+fn skeletal_snapshots() {
+    for (name, smiles) in [
+        ("ethanol_skeletal", "CCO"),
+        ("acetic_acid_skeletal", "CC(=O)O"),
+        ("isobutane_skeletal", "CC(C)C"),
+        ("toluene_skeletal", "Cc1ccccc1"),
+        ("naphthalene_skeletal", "c1ccc2ccccc2c1"),
+        ("cyclohexene_skeletal", "C1=CCCCC1"),
+        ("acetonitrile_skeletal", "CC#N"),
+        ("calcium_carbonate_skeletal", "[Ca+2].[O-]C(=O)[O-]"),
+    ] {
+        assert_snapshot(name, &skeletal(smiles));
+    }
+}
+
+#[test]
+fn full_structure_snapshots() {
+    for (name, smiles) in [
+        ("ethanol_full", "CCO"),
+        ("acetic_acid_full", "CC(=O)O"),
+        ("isobutane_full", "CC(C)C"),
+        ("ethyne_full", "C#C"),
+        ("benzene_full", "c1ccccc1"),
+        ("ammonium_chloride_full", "[NH4+].[Cl-]"),
+    ] {
+        assert_snapshot(name, &full(smiles));
+    }
+}
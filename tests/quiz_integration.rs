@@ -13,6 +13,11 @@ fn sample_compounds() -> Vec<Compound> {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("CCO".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         },
         Compound {
             iupac_name: "methanol".to_string(),
@@ -24,6 +29,11 @@ fn sample_compounds() -> Vec<Compound> {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("CO".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         },
         Compound {
             iupac_name: "propanone".to_string(),
@@ -35,6 +45,11 @@ fn sample_compounds() -> Vec<Compound> {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("CC(=O)C".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         },
         Compound {
             iupac_name: "benzene".to_string(),
@@ -46,6 +61,11 @@ fn sample_compounds() -> Vec<Compound> {
             functional_groups: Vec::new(),
             notes: None,
             smiles: Some("c1ccccc1".to_string()),
+            katex_skeletal_formula: None,
+            katex_molecular_formula: None,
+            state: None,
+            charge: None,
+            hydrate_water: None,
         },
     ]
 }